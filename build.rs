@@ -1,31 +1,72 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+// build.rs
+//
+// Exports `.env` entries to the app as compile-time variables, read with
+// `option_env!` (see `config::Config::default`). `.env.<profile>`, e.g.
+// `.env.release`, overrides `.env` for that cargo profile, and variables
+// already set in the environment win over both.
+#[path = "build/dotenv.rs"]
+mod dotenv;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+
+// Only keys starting with one of these reach the binary, so unrelated
+// secrets kept in `.env` aren't compiled into the public bundle
+const EXPORTED_PREFIXES: [&str; 3] = ["API_", "APP_", "COPYRIGHT_"];
 
 fn main() {
-    // Tell cargo to invalidate the build if the .env file changes.
-    println!("cargo:rerun-if-changed=.env");
+    let mut files = vec![".env".to_string()];
+    match env::var("PROFILE") {
+        Ok(profile) if !profile.is_empty() => files.push(format!(".env.{}", profile)),
+        _ => {}
+    }
 
-    if Path::new(".env").exists() {
-        if let Ok(lines) = read_lines(".env") {
-            for line in lines {
-                if let Ok(line) = line {
-                    let parts: Vec<&str> = line.splitn(2, '=').collect();
-                    if parts.len() == 2 {
-                        let key = parts[0].trim();
-                        let value = parts[1].trim();
-                        println!("cargo:rustc-env={}={}", key, value);
-                    }
-                }
+    let mut vars: BTreeMap<String, String> = BTreeMap::new();
+    for file in &files {
+        println!("cargo:rerun-if-changed={}", file);
+        let source = match fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                println!("cargo:warning={}: cannot read: {}", file, err);
+                continue;
             }
+        };
+
+        let parsed = dotenv::parse(&source, |name| vars.get(name).cloned().or_else(|| env::var(name).ok()));
+        for warning in parsed.warnings {
+            println!("cargo:warning={}: {}", file, warning);
+        }
+        let mut skipped = Vec::new();
+        for entry in parsed.entries {
+            if !EXPORTED_PREFIXES.iter().any(|prefix| entry.key.starts_with(prefix)) {
+                skipped.push(entry.key);
+                continue;
+            }
+            // Cargo reads instructions line by line
+            if entry.value.contains(['\n', '\r']) {
+                println!("cargo:warning={}: line {}: `{}` spans several lines and is not exported", file, entry.line, entry.key);
+                continue;
+            }
+            vars.insert(entry.key, entry.value);
+        }
+        // One line per file, so a `.env` shared with other tools stays quiet
+        if !skipped.is_empty() {
+            println!(
+                "cargo:warning={}: {} not exported, only keys starting with {} are",
+                file,
+                skipped.join(", "),
+                EXPORTED_PREFIXES.join(", ")
+            );
         }
     }
-}
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    for (key, value) in vars {
+        println!("cargo:rerun-if-env-changed={}", key);
+        if env::var_os(&key).is_none() {
+            println!("cargo:rustc-env={}={}", key, value);
+        }
+    }
 }
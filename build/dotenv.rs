@@ -0,0 +1,224 @@
+// build/dotenv.rs
+//
+// `.env` parser used by build.rs. Follows the usual dotenv rules:
+//
+//   # comment                    KEY=value # trailing comment
+//   export KEY=value             KEY='literal, no escapes or $expansion'
+//   KEY="line\nbreak \"quoted\" ${OTHER} $OTHER ${MISSING:-fallback}"
+//
+// Quoted values may span several lines. Problems are reported per line and
+// the offending entry is skipped, so one typo doesn't lose the whole file.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Parsed {
+    pub entries: Vec<Entry>,
+    pub warnings: Vec<String>,
+}
+
+// `lookup` resolves `$NAME` references to keys not defined earlier in `source`
+pub fn parse(source: &str, lookup: impl Fn(&str) -> Option<String>) -> Parsed {
+    let mut parsed = Parsed::default();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let number = index + 1;
+        let line = lines[index].trim();
+        index += 1;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(str::trim_start)
+            .unwrap_or(line);
+        let Some((key, raw)) = line.split_once('=') else {
+            parsed.warnings.push(format!("line {}: expected KEY=VALUE", number));
+            continue;
+        };
+        let key = key.trim();
+        if let Err(problem) = check_key(key) {
+            parsed.warnings.push(format!("line {}: {}", number, problem));
+            continue;
+        }
+
+        let raw = raw.trim_start();
+        let quote = raw.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let value = match quote {
+            Some(quote) => {
+                // Pull in following lines until the closing quote
+                let mut text = raw[1..].to_string();
+                let mut closing = find_closing(&text, quote);
+                while closing.is_none() && index < lines.len() {
+                    text.push('\n');
+                    text.push_str(lines[index]);
+                    index += 1;
+                    closing = find_closing(&text, quote);
+                }
+                let Some(closing) = closing else {
+                    parsed.warnings.push(format!("line {}: unterminated {} quote", number, quote));
+                    continue;
+                };
+                let rest = text[closing + 1..].trim();
+                if !(rest.is_empty() || rest.starts_with('#')) {
+                    parsed
+                        .warnings
+                        .push(format!("line {}: unexpected `{}` after the closing quote", number, rest));
+                    continue;
+                }
+                let inner = &text[..closing];
+                if quote == '\'' {
+                    inner.to_string()
+                } else {
+                    expand(inner, true, &parsed.entries, &lookup, number, &mut parsed.warnings)
+                }
+            }
+            None => {
+                let value = strip_comment(raw).trim_end();
+                expand(value, false, &parsed.entries, &lookup, number, &mut parsed.warnings)
+            }
+        };
+
+        parsed.entries.push(Entry {
+            key: key.to_string(),
+            value,
+            line: number,
+        });
+    }
+
+    parsed
+}
+
+fn check_key(key: &str) -> Result<(), String> {
+    let mut chars = key.chars();
+    match chars.next() {
+        None => Err("missing key before `=`".to_string()),
+        Some(first) if !(first.is_ascii_alphabetic() || first == '_') => {
+            Err(format!("key `{}` must start with a letter or `_`", key))
+        }
+        _ if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+            Err(format!("key `{}` may only contain letters, digits and `_`", key))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Index of the quote ending a value, skipping backslash-escaped ones in
+// double quotes
+fn find_closing(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            c if c == quote => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+// An unquoted value ends at a `#` preceded by whitespace
+fn strip_comment(value: &str) -> &str {
+    let mut previous = ' ';
+    for (index, c) in value.char_indices() {
+        if c == '#' && previous.is_whitespace() {
+            return &value[..index];
+        }
+        previous = c;
+    }
+    value
+}
+
+// Substitutes `$NAME`, `${NAME}` and `${NAME:-default}`; `\$` is a literal
+// dollar sign. With `escapes`, as inside double quotes, `\n`, `\r`, `\t`,
+// `\"` and `\\` are translated too.
+fn expand(
+    value: &str,
+    escapes: bool,
+    earlier: &[Entry],
+    lookup: &impl Fn(&str) -> Option<String>,
+    line: usize,
+    warnings: &mut Vec<String>,
+) -> String {
+    let resolve = |name: &str| {
+        earlier
+            .iter()
+            .rev()
+            .find(|entry| entry.key == name)
+            .map(|entry| entry.value.clone())
+            .or_else(|| lookup(name))
+    };
+
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '\\' => {
+                let next = rest.chars().next();
+                let translated = match next {
+                    Some('$') => Some('$'),
+                    Some('n') if escapes => Some('\n'),
+                    Some('r') if escapes => Some('\r'),
+                    Some('t') if escapes => Some('\t'),
+                    Some('"') if escapes => Some('"'),
+                    Some('\\') if escapes => Some('\\'),
+                    _ => None,
+                };
+                match (translated, next) {
+                    (Some(translated), Some(next)) => {
+                        out.push(translated);
+                        rest = &rest[next.len_utf8()..];
+                    }
+                    _ => out.push('\\'),
+                }
+            }
+            '$' => {
+                let (name, default, consumed) = if let Some(braced) = rest.strip_prefix('{') {
+                    let Some(end) = braced.find('}') else {
+                        warnings.push(format!("line {}: unclosed `${{`", line));
+                        out.push('$');
+                        continue;
+                    };
+                    let inner = &braced[..end];
+                    match inner.split_once(":-") {
+                        Some((name, default)) => (name, Some(default), end + 2),
+                        None => (inner, None, end + 2),
+                    }
+                } else {
+                    let end = rest
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                        .unwrap_or(rest.len());
+                    (&rest[..end], None, end)
+                };
+                if name.is_empty() {
+                    // A lone `$` is taken literally
+                    out.push('$');
+                    continue;
+                }
+
+                match resolve(name) {
+                    Some(value) if !value.is_empty() => out.push_str(&value),
+                    found => match default {
+                        Some(default) => out.push_str(default),
+                        None if found.is_none() => warnings.push(format!("line {}: `{}` is not set", line, name)),
+                        None => {}
+                    },
+                }
+                rest = &rest[consumed..];
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,172 @@
+// mock-server/src/auth.rs
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Account {
+    pub username: String,
+    pub password: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+}
+
+// Same wire format as the app's `TokenResponse`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Tokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: u64,
+    pub user: User,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+// Issued tokens. Refresh tokens stay valid until logout, so concurrent
+// refreshes from one client don't lock each other out.
+#[derive(Debug)]
+pub struct Sessions {
+    accounts: Vec<Account>,
+    ttl: Duration,
+    access: HashMap<String, (String, Instant)>,
+    refresh: HashMap<String, String>,
+}
+
+impl Sessions {
+    pub fn new(accounts: Vec<Account>, ttl: Duration) -> Self {
+        Self {
+            accounts,
+            ttl,
+            access: HashMap::new(),
+            refresh: HashMap::new(),
+        }
+    }
+
+    pub fn login(&mut self, request: &LoginRequest, rng: &mut impl Rng, now: Instant) -> Option<Tokens> {
+        let username = self
+            .accounts
+            .iter()
+            .find(|account| account.username == request.username && account.password == request.password)?
+            .username
+            .clone();
+        let refresh_token = token(rng);
+        self.refresh.insert(refresh_token.clone(), username.clone());
+        self.issue(&username, refresh_token, rng, now)
+    }
+
+    pub fn refresh(&mut self, refresh_token: &str, rng: &mut impl Rng, now: Instant) -> Option<Tokens> {
+        let username = self.refresh.get(refresh_token)?.clone();
+        self.issue(&username, refresh_token.to_string(), rng, now)
+    }
+
+    pub fn logout(&mut self, refresh_token: &str) {
+        if let Some(username) = self.refresh.remove(refresh_token) {
+            self.access.retain(|_, (owner, _)| *owner != username);
+        }
+    }
+
+    // Username behind a live access token
+    pub fn user(&self, access_token: &str, now: Instant) -> Option<&str> {
+        match self.access.get(access_token) {
+            Some((username, expires)) if *expires > now => Some(username),
+            _ => None,
+        }
+    }
+
+    fn issue(&mut self, username: &str, refresh_token: String, rng: &mut impl Rng, now: Instant) -> Option<Tokens> {
+        let account = self.accounts.iter().find(|account| account.username == username)?;
+        let access_token = token(rng);
+        self.access
+            .insert(access_token.clone(), (username.to_string(), now + self.ttl));
+
+        Some(Tokens {
+            access_token,
+            refresh_token,
+            expires_in: self.ttl.as_secs(),
+            user: User {
+                id: account.username.clone(),
+                name: account.name.clone(),
+            },
+        })
+    }
+}
+
+fn token(rng: &mut impl Rng) -> String {
+    format!("{:032x}", rng.gen::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn sessions() -> Sessions {
+        let accounts = vec![Account {
+            username: "demo".to_string(),
+            password: "demo".to_string(),
+            name: "Demo User".to_string(),
+        }];
+        Sessions::new(accounts, Duration::from_secs(60))
+    }
+
+    fn credentials(password: &str) -> LoginRequest {
+        LoginRequest {
+            username: "demo".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[test]
+    fn login_checks_password() {
+        let mut sessions = sessions();
+        let mut rng = StdRng::seed_from_u64(1);
+        let now = Instant::now();
+
+        assert!(sessions.login(&credentials("wrong"), &mut rng, now).is_none());
+        let tokens = sessions.login(&credentials("demo"), &mut rng, now).unwrap();
+        assert_eq!(tokens.user.name, "Demo User");
+        assert_eq!(sessions.user(&tokens.access_token, now), Some("demo"));
+    }
+
+    #[test]
+    fn access_tokens_expire_and_refresh() {
+        let mut sessions = sessions();
+        let mut rng = StdRng::seed_from_u64(1);
+        let now = Instant::now();
+        let tokens = sessions.login(&credentials("demo"), &mut rng, now).unwrap();
+        let later = now + Duration::from_secs(61);
+
+        assert_eq!(sessions.user(&tokens.access_token, later), None);
+        let refreshed = sessions.refresh(&tokens.refresh_token, &mut rng, later).unwrap();
+        assert_eq!(sessions.user(&refreshed.access_token, later), Some("demo"));
+    }
+
+    #[test]
+    fn logout_revokes_tokens() {
+        let mut sessions = sessions();
+        let mut rng = StdRng::seed_from_u64(1);
+        let now = Instant::now();
+        let tokens = sessions.login(&credentials("demo"), &mut rng, now).unwrap();
+
+        sessions.logout(&tokens.refresh_token);
+        assert_eq!(sessions.user(&tokens.access_token, now), None);
+        assert!(sessions.refresh(&tokens.refresh_token, &mut rng, now).is_none());
+    }
+}
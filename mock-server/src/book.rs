@@ -0,0 +1,272 @@
+// mock-server/src/book.rs
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Levels kept on each side
+const LEVELS: usize = 50;
+// Updates and trades kept for clients that poll
+const UPDATE_HISTORY: usize = 200;
+const TRADE_HISTORY: usize = 100;
+// Rough value of a level, in the quote currency
+const LEVEL_VALUE: f64 = 25_000.0;
+
+// Same wire format as the app's `BookLevel`, `OrderBook`, `BookUpdate` and `Trade`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    pub price: Decimal,
+    pub amount: Decimal,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub sequence: u64,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Update {
+    pub sequence: u64,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub id: u64,
+    pub price: Decimal,
+    pub amount: Decimal,
+    pub side: Side,
+    pub timestamp: DateTime<Utc>,
+}
+
+// Simulated level-2 book on a fixed price grid around an asset's price,
+// with the changes and trades of recent ticks
+#[derive(Clone, Debug)]
+pub struct Book {
+    step: Decimal,
+    sequence: u64,
+    bids: Vec<Level>,
+    asks: Vec<Level>,
+    updates: VecDeque<Update>,
+    trades: VecDeque<Trade>,
+    next_trade: u64,
+}
+
+impl Book {
+    // Starts with the same amounts every run so fixtures stay predictable
+    pub fn new(price: Decimal) -> Self {
+        let places = if price >= Decimal::ONE { 2 } else { 6 };
+        let step = (price * Decimal::new(5, 4)).round_dp(places).max(Decimal::new(1, places));
+        let mut book = Self {
+            step,
+            sequence: 1,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            updates: VecDeque::new(),
+            trades: VecDeque::new(),
+            next_trade: 1,
+        };
+        let (best_bid, best_ask) = book.best_prices(price);
+        book.bids = (0..LEVELS)
+            .map(|index| Level {
+                price: best_bid - step * Decimal::from(index),
+                amount: amount(((index % 7) + 1) as f64 / 10.0, price),
+            })
+            .collect();
+        book.asks = (0..LEVELS)
+            .map(|index| Level {
+                price: best_ask + step * Decimal::from(index),
+                amount: amount(((index % 5) + 1) as f64 / 8.0, price),
+            })
+            .collect();
+        book
+    }
+
+    pub fn snapshot(&self, depth: usize) -> Snapshot {
+        Snapshot {
+            sequence: self.sequence,
+            bids: self.bids.iter().take(depth).cloned().collect(),
+            asks: self.asks.iter().take(depth).cloned().collect(),
+        }
+    }
+
+    // Retained updates after `since`, oldest first. When `since` is older than
+    // the history the first one returned skips ahead and the client reloads.
+    pub fn updates_since(&self, since: u64) -> Vec<Update> {
+        self.updates.iter().filter(|update| update.sequence > since).cloned().collect()
+    }
+
+    // Newest first
+    pub fn trades(&self, limit: usize) -> Vec<Trade> {
+        self.trades.iter().take(limit).cloned().collect()
+    }
+
+    // Trades a little at the best prices, then re-centres the book on `price`,
+    // refreshing some amounts, and records the difference as one update
+    pub fn tick(&mut self, price: Decimal, rng: &mut impl Rng, now: DateTime<Utc>) {
+        for _ in 0..rng.gen_range(1..=3) {
+            let side = if rng.gen_bool(0.5) { Side::Buy } else { Side::Sell };
+            let Some(best) = (match side {
+                Side::Buy => self.asks.first(),
+                Side::Sell => self.bids.first(),
+            }) else {
+                continue;
+            };
+            self.trades.push_front(Trade {
+                id: self.next_trade,
+                price: best.price,
+                amount: amount(rng.gen_range(0.001..0.05), price),
+                side,
+                timestamp: now,
+            });
+            self.next_trade += 1;
+        }
+        self.trades.truncate(TRADE_HISTORY);
+
+        let (best_bid, best_ask) = self.best_prices(price);
+        let step = self.step;
+        let bids = rebuild(&self.bids, (0..LEVELS).map(|index| best_bid - step * Decimal::from(index)), price, rng);
+        let asks = rebuild(&self.asks, (0..LEVELS).map(|index| best_ask + step * Decimal::from(index)), price, rng);
+
+        self.sequence += 1;
+        self.updates.push_back(Update {
+            sequence: self.sequence,
+            bids: changes(&self.bids, &bids),
+            asks: changes(&self.asks, &asks),
+        });
+        while self.updates.len() > UPDATE_HISTORY {
+            self.updates.pop_front();
+        }
+        self.bids = bids;
+        self.asks = asks;
+    }
+
+    // Grid prices either side of `price`, never equal to it
+    fn best_prices(&self, price: Decimal) -> (Decimal, Decimal) {
+        let bid = (price / self.step).ceil() * self.step - self.step;
+        let ask = (price / self.step).floor() * self.step + self.step;
+        (bid.max(self.step), ask)
+    }
+}
+
+// Levels at `prices`, keeping most existing amounts
+fn rebuild(levels: &[Level], prices: impl Iterator<Item = Decimal>, price: Decimal, rng: &mut impl Rng) -> Vec<Level> {
+    prices
+        .filter(|at| *at > Decimal::ZERO)
+        .map(|at| {
+            let existing = levels.iter().find(|level| level.price == at);
+            let amount = match existing {
+                Some(level) if rng.gen_bool(0.8) => level.amount,
+                _ => amount(rng.gen_range(0.05..1.0), price),
+            };
+            Level { price: at, amount }
+        })
+        .collect()
+}
+
+// New and changed levels of `next`, plus removed ones with a zero amount
+fn changes(previous: &[Level], next: &[Level]) -> Vec<Level> {
+    let removed = previous
+        .iter()
+        .filter(|level| next.iter().all(|kept| kept.price != level.price))
+        .map(|level| Level {
+            price: level.price,
+            amount: Decimal::ZERO,
+        });
+    let changed = next.iter().filter(|level| !previous.contains(level)).cloned();
+    removed.chain(changed).collect()
+}
+
+// `share` of `LEVEL_VALUE` worth of the asset at `price`
+fn amount(share: f64, price: Decimal) -> Decimal {
+    let units = share * LEVEL_VALUE / price.to_f64().unwrap_or(1.0).max(f64::EPSILON);
+    Decimal::from_f64(units)
+        .unwrap_or(Decimal::ONE)
+        .round_dp(4)
+        .max(Decimal::new(1, 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 14, 15, 30, 0).unwrap()
+    }
+
+    // What the app does with updates: replace, insert or remove by price
+    fn apply(levels: &mut Vec<Level>, changes: &[Level]) {
+        for change in changes {
+            levels.retain(|level| level.price != change.price);
+            if !change.amount.is_zero() {
+                levels.push(change.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn new_books_straddle_the_price() {
+        let book = Book::new(Decimal::new(6500050, 2));
+        let snapshot = book.snapshot(10);
+
+        assert_eq!(snapshot.sequence, 1);
+        assert_eq!(snapshot.bids.len(), 10);
+        assert!(snapshot.bids[0].price < Decimal::new(6500050, 2));
+        assert!(snapshot.asks[0].price > Decimal::new(6500050, 2));
+        assert!(snapshot.bids.windows(2).all(|pair| pair[0].price > pair[1].price));
+        assert!(snapshot.asks.windows(2).all(|pair| pair[0].price < pair[1].price));
+    }
+
+    #[test]
+    fn updates_replay_onto_the_previous_book() {
+        let mut book = Book::new(Decimal::from(100));
+        let mut rng = StdRng::seed_from_u64(7);
+        let before = book.snapshot(LEVELS);
+
+        book.tick(Decimal::new(10030, 2), &mut rng, now());
+        book.tick(Decimal::new(9990, 2), &mut rng, now());
+
+        let updates = book.updates_since(before.sequence);
+        assert_eq!(updates.iter().map(|update| update.sequence).collect::<Vec<_>>(), [2, 3]);
+        let (mut bids, mut asks) = (before.bids, before.asks);
+        for update in &updates {
+            apply(&mut bids, &update.bids);
+            apply(&mut asks, &update.asks);
+        }
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+        let after = book.snapshot(LEVELS);
+        assert_eq!((bids, asks), (after.bids, after.asks));
+        assert!(book.updates_since(3).is_empty());
+    }
+
+    #[test]
+    fn trades_are_newest_first_and_capped() {
+        let mut book = Book::new(Decimal::from(100));
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..TRADE_HISTORY {
+            book.tick(Decimal::from(100), &mut rng, now());
+        }
+
+        let trades = book.trades(1000);
+        assert_eq!(trades.len(), TRADE_HISTORY);
+        assert!(trades.windows(2).all(|pair| pair[0].id > pair[1].id));
+        assert_eq!(book.trades(5).len(), 5);
+    }
+}
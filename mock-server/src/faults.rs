@@ -0,0 +1,144 @@
+// mock-server/src/faults.rs
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::options::Options;
+use crate::routes::query_param;
+
+// What to do to a request before it is answered
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fault {
+    pub latency: Duration,
+    pub status: Option<u16>,
+}
+
+// Per-request overrides from the `mock_latency` and `mock_status` query parameters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Overrides {
+    pub latency: Option<Duration>,
+    pub status: Option<u16>,
+}
+
+impl Overrides {
+    pub fn from_query(query: &str) -> Self {
+        Self {
+            latency: query_param(query, "mock_latency")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis),
+            status: query_param(query, "mock_status").and_then(|value| value.parse().ok()),
+        }
+    }
+}
+
+pub fn plan(options: &Options, overrides: &Overrides, rng: &mut impl Rng) -> Fault {
+    let latency = overrides.latency.unwrap_or_else(|| {
+        let jitter = options.jitter.as_millis() as u64;
+        let extra = if jitter > 0 { rng.gen_range(0..=jitter) } else { 0 };
+        options.latency + Duration::from_millis(extra)
+    });
+    // Only error statuses are injected; a lower override answers normally
+    let status = match overrides.status {
+        Some(status) => (status >= 400).then_some(status),
+        None => rng.gen_bool(options.error_rate).then_some(500),
+    };
+
+    Fault { latency, status }
+}
+
+// Fixed one-minute window shared by all clients
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    started: Instant,
+    used: u32,
+}
+
+impl RateLimiter {
+    pub fn per_minute(limit: u32, now: Instant) -> Self {
+        Self {
+            limit,
+            window: Duration::from_secs(60),
+            started: now,
+            used: 0,
+        }
+    }
+
+    // Counts the request, or returns how long until the window resets
+    pub fn check(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.duration_since(self.started);
+        if elapsed >= self.window {
+            self.started = now;
+            self.used = 0;
+        }
+        if self.used < self.limit {
+            self.used += 1;
+            Ok(())
+        } else {
+            Err(self.window - now.duration_since(self.started))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn rate_limiter_resets_each_window() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::per_minute(2, start);
+
+        assert_eq!(limiter.check(start), Ok(()));
+        assert_eq!(limiter.check(start), Ok(()));
+        assert_eq!(limiter.check(start + Duration::from_secs(20)), Err(Duration::from_secs(40)));
+        assert_eq!(limiter.check(start + Duration::from_secs(60)), Ok(()));
+    }
+
+    #[test]
+    fn query_overrides_win() {
+        let options = Options {
+            latency: Duration::from_millis(100),
+            error_rate: 1.0,
+            ..Options::default()
+        };
+        let overrides = Overrides::from_query("days=7&mock_latency=5&mock_status=429");
+        let fault = plan(&options, &overrides, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(fault.latency, Duration::from_millis(5));
+        assert_eq!(fault.status, Some(429));
+    }
+
+    #[test]
+    fn success_overrides_answer_normally() {
+        let options = Options {
+            error_rate: 1.0,
+            ..Options::default()
+        };
+        for query in ["mock_status=200", "mock_status=204", "mock_status=302"] {
+            let fault = plan(&options, &Overrides::from_query(query), &mut StdRng::seed_from_u64(1));
+            assert_eq!(fault.status, None, "{}", query);
+        }
+        let fault = plan(&options, &Overrides::from_query("mock_status=400"), &mut StdRng::seed_from_u64(1));
+        assert_eq!(fault.status, Some(400));
+    }
+
+    #[test]
+    fn error_rate_and_jitter_apply_without_overrides() {
+        let options = Options {
+            latency: Duration::from_millis(100),
+            jitter: Duration::from_millis(50),
+            error_rate: 1.0,
+            ..Options::default()
+        };
+        let fault = plan(&options, &Overrides::default(), &mut StdRng::seed_from_u64(1));
+
+        assert!(fault.latency >= Duration::from_millis(100));
+        assert!(fault.latency <= Duration::from_millis(150));
+        assert_eq!(fault.status, Some(500));
+        assert_eq!(plan(&Options::default(), &Overrides::default(), &mut StdRng::seed_from_u64(1)).status, None);
+    }
+}
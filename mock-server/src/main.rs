@@ -0,0 +1,79 @@
+// mock-server/src/main.rs
+//
+// Development stand-in for the tracker's HTTP API. Serves the fixture files
+// with simulated price movement, order books, trades, per-exchange tickers
+// and wallet balances behind node-like RPC endpoints, and optional latency,
+// failures and rate limiting. Point the app at it with
+// `API_BASE_URL=http://localhost:8787` and sign in as one of the accounts in
+// `fixtures/users.json`.
+mod auth;
+mod book;
+mod faults;
+mod market;
+mod options;
+mod routes;
+mod rpc;
+mod server;
+
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use chrono::Utc;
+
+use market::Fixtures;
+use server::State;
+
+const WORKERS: usize = 4;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", options::USAGE);
+        return;
+    }
+    let options = options::parse(args).unwrap_or_else(|message| {
+        eprintln!("error: {}\n\n{}", message, options::USAGE);
+        process::exit(2);
+    });
+    let fixtures = Fixtures::load(&options.fixtures).unwrap_or_else(|error| {
+        eprintln!("error: cannot load fixtures from {}: {}", options.fixtures.display(), error);
+        process::exit(1);
+    });
+
+    let http = tiny_http::Server::http(("127.0.0.1", options.port)).unwrap_or_else(|error| {
+        eprintln!("error: cannot listen on port {}: {}", options.port, error);
+        process::exit(1);
+    });
+    let http = Arc::new(http);
+    let state = Arc::new(State::new(options, fixtures));
+    println!("mock API listening on http://localhost:{}", state.options.port);
+
+    if !state.options.tick.is_zero() {
+        let state = state.clone();
+        thread::spawn(move || loop {
+            thread::sleep(state.options.tick);
+            let mut rng = state.rng.lock().unwrap();
+            state
+                .market
+                .lock()
+                .unwrap()
+                .tick(&mut *rng, state.options.volatility, state.options.tick, Utc::now());
+        });
+    }
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let http = http.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                for request in http.incoming_requests() {
+                    server::handle(&state, request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
@@ -0,0 +1,373 @@
+// mock-server/src/market.rs
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use rand::Rng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::Account;
+use crate::book::{Book, Snapshot, Trade, Update};
+use crate::rpc::Wallets;
+
+// Same wire format as the app's `Cryptocurrency`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub price: Decimal,
+    pub market_cap: f64,
+    pub volume_24h: f64,
+    pub price_change_24h: f64,
+    pub decimals: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub timestamp: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+// Same wire format as the app's `Ticker`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ticker {
+    pub bid: Decimal,
+    pub ask: Decimal,
+    pub last: Decimal,
+    pub volume_24h: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioItem {
+    pub crypto_id: String,
+    pub amount: Decimal,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioDocument {
+    pub revision: u64,
+    pub items: Vec<PortfolioItem>,
+}
+
+// Body of a portfolio write
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PortfolioUpdate {
+    pub base_revision: u64,
+    pub items: Vec<PortfolioItem>,
+}
+
+impl PortfolioDocument {
+    // Accepts writes based on the current revision; stale ones are refused
+    // so the client can merge and retry
+    pub fn apply(&mut self, update: PortfolioUpdate) -> bool {
+        if update.base_revision != self.revision {
+            return false;
+        }
+        self.revision += 1;
+        self.items = update.items;
+        true
+    }
+}
+
+// Contents of the fixtures directory:
+//   cryptocurrencies.json, users.json, portfolio.json (every account's
+//   starting portfolio) and history/<id>.json (daily candles, oldest first)
+#[derive(Clone, Debug)]
+pub struct Fixtures {
+    pub assets: Vec<Asset>,
+    pub history: HashMap<String, Vec<Candle>>,
+    pub accounts: Vec<Account>,
+    pub portfolio: PortfolioDocument,
+    pub wallets: Wallets,
+}
+
+impl Fixtures {
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let assets: Vec<Asset> = read_json(&dir.join("cryptocurrencies.json"))?;
+        let accounts = read_json(&dir.join("users.json"))?;
+        let portfolio = read_json(&dir.join("portfolio.json"))?;
+        let wallets = Wallets::new(read_json(&dir.join("wallets.json"))?, read_json(&dir.join("tokens.json"))?);
+
+        let mut history = HashMap::new();
+        for asset in &assets {
+            let path = dir.join("history").join(format!("{}.json", asset.id));
+            if path.exists() {
+                history.insert(asset.id.clone(), read_json(&path)?);
+            }
+        }
+
+        Ok(Self {
+            assets,
+            history,
+            accounts,
+            portfolio,
+            wallets,
+        })
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let text = fs::read_to_string(path)?;
+    serde_json::from_str(&text)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), error)))
+}
+
+#[derive(Clone, Debug)]
+pub struct Market {
+    assets: Vec<Asset>,
+    history: HashMap<String, Vec<Candle>>,
+    books: HashMap<String, Book>,
+}
+
+impl Market {
+    // Fixture history is shifted so its newest candle is today's
+    pub fn new(fixtures: &Fixtures, now: DateTime<Utc>) -> Self {
+        let today = start_of_day(now);
+        let history = fixtures
+            .history
+            .iter()
+            .map(|(id, candles)| {
+                let shift = candles
+                    .last()
+                    .map(|last| today - start_of_day(last.timestamp))
+                    .unwrap_or_default();
+                let candles = candles
+                    .iter()
+                    .map(|candle| Candle {
+                        timestamp: candle.timestamp + shift,
+                        ..candle.clone()
+                    })
+                    .collect();
+                (id.clone(), candles)
+            })
+            .collect();
+
+        let books = fixtures
+            .assets
+            .iter()
+            .map(|asset| (asset.id.clone(), Book::new(asset.price)))
+            .collect();
+
+        Self {
+            assets: fixtures.assets.clone(),
+            history,
+            books,
+        }
+    }
+
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+
+    pub fn asset(&self, id: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.id == id)
+    }
+
+    // The newest `days` candles; empty when the asset has no history fixture
+    pub fn history(&self, id: &str, days: usize) -> Option<&[Candle]> {
+        self.asset(id)?;
+        let candles = self.history.get(id).map(Vec::as_slice).unwrap_or_default();
+        Some(&candles[candles.len().saturating_sub(days)..])
+    }
+
+    pub fn book(&self, id: &str, depth: usize) -> Option<Snapshot> {
+        Some(self.books.get(id)?.snapshot(depth))
+    }
+
+    pub fn book_updates(&self, id: &str, since: u64) -> Option<Vec<Update>> {
+        Some(self.books.get(id)?.updates_since(since))
+    }
+
+    pub fn trades(&self, id: &str, limit: usize) -> Option<Vec<Trade>> {
+        Some(self.books.get(id)?.trades(limit))
+    }
+
+    // The asset as quoted on `exchange`: any name is accepted and always
+    // trades the same small distance from the market price, with its own
+    // spread and share of the volume
+    pub fn ticker(&self, exchange: &str, id: &str) -> Option<Ticker> {
+        let asset = self.asset(id)?;
+        let hash = exchange.bytes().fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+        let fraction = |shift: u32| f64::from((hash >> shift) & 0xff) / 255.0;
+
+        let places = if asset.price >= Decimal::ONE { 2 } else { 6 };
+        let offset = Decimal::from_f64((fraction(0) - 0.5) * 0.006).unwrap_or_default();
+        let last = (asset.price * (Decimal::ONE + offset)).round_dp(places);
+        let half_spread = (last * Decimal::from_f64(0.0001 + fraction(8) * 0.0004).unwrap_or_default())
+            .round_dp(places)
+            .max(Decimal::new(1, places));
+        Some(Ticker {
+            bid: last - half_spread,
+            ask: last + half_spread,
+            last,
+            volume_24h: asset.volume_24h * (0.05 + fraction(16) * 0.3),
+        })
+    }
+
+    // Moves every price one log-normal step, folds it into today's candle and
+    // moves the asset's order book along
+    pub fn tick(&mut self, rng: &mut impl Rng, volatility: f64, elapsed: Duration, now: DateTime<Utc>) {
+        let today = start_of_day(now);
+        let share_of_day = elapsed.as_secs_f64() / 86_400.0;
+
+        for asset in &mut self.assets {
+            let previous = asset.price.to_f64().unwrap_or_default();
+            let factor = (volatility * standard_normal(rng)).exp();
+            let places = if previous >= 1.0 { 2 } else { 6 };
+            let Some(price) = Decimal::from_f64(previous * factor) else {
+                continue;
+            };
+            asset.price = price.round_dp(places);
+            asset.market_cap *= factor;
+            let close = asset.price.to_f64().unwrap_or_default();
+
+            let candles = self.history.entry(asset.id.clone()).or_default();
+            if candles.last().is_none_or(|last| last.timestamp < today) {
+                candles.push(Candle {
+                    timestamp: today,
+                    open: previous,
+                    high: previous,
+                    low: previous,
+                    close: previous,
+                    volume: 0.0,
+                });
+            }
+            let candle = candles.last_mut().unwrap();
+            candle.close = close;
+            candle.high = candle.high.max(close);
+            candle.low = candle.low.min(close);
+            candle.volume += asset.volume_24h * share_of_day;
+
+            asset.price_change_24h = ((close / candle.open - 1.0) * 10_000.0).round() / 100.0;
+
+            self.books
+                .entry(asset.id.clone())
+                .or_insert_with(|| Book::new(asset.price))
+                .tick(asset.price, rng, now);
+        }
+    }
+}
+
+fn start_of_day(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+// Box-Muller transform
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn fixtures() -> Fixtures {
+        Fixtures::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 14, 15, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn bundled_fixtures_load() {
+        let fixtures = fixtures();
+
+        assert_eq!(fixtures.assets.len(), 5);
+        assert!(!fixtures.accounts.is_empty());
+        assert!(fixtures.assets.iter().all(|asset| fixtures.history.contains_key(&asset.id)));
+    }
+
+    #[test]
+    fn portfolio_rejects_stale_writes() {
+        let mut portfolio = fixtures().portfolio;
+        let update = |base_revision| PortfolioUpdate {
+            base_revision,
+            items: Vec::new(),
+        };
+
+        assert!(!portfolio.apply(update(0)));
+        assert_eq!(portfolio.revision, 1);
+        assert_eq!(portfolio.items.len(), 2);
+        assert!(portfolio.apply(update(1)));
+        assert_eq!(portfolio.revision, 2);
+        assert!(portfolio.items.is_empty());
+    }
+
+    #[test]
+    fn history_is_rebased_onto_today() {
+        let market = Market::new(&fixtures(), now());
+        let candles = market.history("bitcoin", 7).unwrap();
+
+        assert_eq!(candles.len(), 7);
+        assert_eq!(candles.last().unwrap().timestamp, start_of_day(now()));
+        assert!(market.history("bitcoin", 10_000).unwrap().len() < 10_000);
+        assert!(market.history("dogecoin", 7).is_none());
+    }
+
+    #[test]
+    fn tick_moves_prices_into_todays_candle() {
+        let mut market = Market::new(&fixtures(), now());
+        let mut rng = StdRng::seed_from_u64(42);
+        let before = market.history("bitcoin", 1000).unwrap().len();
+
+        for _ in 0..20 {
+            market.tick(&mut rng, 0.01, Duration::from_secs(2), now());
+        }
+
+        let bitcoin = market.asset("bitcoin").unwrap();
+        let today = market.history("bitcoin", 1).unwrap()[0].clone();
+        assert_eq!(market.history("bitcoin", 1000).unwrap().len(), before);
+        assert_eq!(today.close, bitcoin.price.to_f64().unwrap());
+        assert!(today.low <= today.close && today.close <= today.high);
+        assert!(market.assets().iter().all(|asset| asset.price > Decimal::ZERO));
+        let book = market.book("bitcoin", 1).unwrap();
+        assert_eq!(book.sequence, 21);
+        assert!(book.bids[0].price < bitcoin.price && bitcoin.price < book.asks[0].price);
+        assert_eq!(market.book_updates("bitcoin", 11).unwrap().len(), 10);
+        assert!(!market.trades("bitcoin", 5).unwrap().is_empty());
+        assert!(market.book("dogecoin", 1).is_none());
+    }
+
+    #[test]
+    fn exchanges_quote_near_the_market_price() {
+        let market = Market::new(&fixtures(), now());
+        let bitcoin = market.asset("bitcoin").unwrap().price;
+        let binance = market.ticker("binance", "bitcoin").unwrap();
+        let kraken = market.ticker("kraken", "bitcoin").unwrap();
+
+        assert_ne!(binance.last, kraken.last);
+        assert_eq!(market.ticker("binance", "bitcoin"), Some(binance.clone()));
+        for ticker in [binance, kraken] {
+            assert!(ticker.bid < ticker.last && ticker.last < ticker.ask);
+            assert!((ticker.last - bitcoin).abs() / bitcoin < Decimal::new(5, 3));
+        }
+        assert!(market.ticker("binance", "dogecoin").is_none());
+    }
+
+    #[test]
+    fn tick_opens_a_new_candle_after_midnight() {
+        let mut market = Market::new(&fixtures(), now());
+        let before = market.history("bitcoin", 1000).unwrap().len();
+
+        market.tick(&mut StdRng::seed_from_u64(1), 0.01, Duration::from_secs(2), now() + chrono::Duration::days(1));
+
+        assert_eq!(market.history("bitcoin", 1000).unwrap().len(), before + 1);
+    }
+}
@@ -0,0 +1,135 @@
+// mock-server/src/options.rs
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: mock-server [OPTIONS]
+
+Options:
+  --port <PORT>            Port to listen on [default: 8787]
+  --fixtures <DIR>         Directory holding the fixture files [default: mock-server/fixtures]
+  --latency <MS>           Delay added to every response [default: 0]
+  --jitter <MS>            Random extra delay of up to this many ms [default: 0]
+  --error-rate <RATIO>     Share of requests answered with a 500, 0 to 1 [default: 0]
+  --rate-limit <N>         Requests allowed per minute before answering 429 [default: unlimited]
+  --tick <MS>              Interval between price updates, 0 to freeze prices [default: 2000]
+  --volatility <RATIO>     Standard deviation of each price step [default: 0.002]
+  --token-ttl <SECS>       Lifetime of issued access tokens [default: 900]
+  --seed <N>               Seed for latency, failures and price moves [default: random]
+  -h, --help               Print this help
+
+Per request, `mock_latency=<MS>` and `mock_status=<CODE>` query parameters
+override the delay and force an error status; a status below 400 answers
+normally, with no injected failure.";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub port: u16,
+    pub fixtures: PathBuf,
+    pub latency: Duration,
+    pub jitter: Duration,
+    pub error_rate: f64,
+    pub rate_limit: Option<u32>,
+    pub tick: Duration,
+    pub volatility: f64,
+    pub token_ttl: Duration,
+    pub seed: Option<u64>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            port: 8787,
+            fixtures: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures"),
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            error_rate: 0.0,
+            rate_limit: None,
+            tick: Duration::from_secs(2),
+            volatility: 0.002,
+            token_ttl: Duration::from_secs(900),
+            seed: None,
+        }
+    }
+}
+
+// Accepts both `--flag value` and `--flag=value`
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+
+        match flag.as_str() {
+            "--port" => options.port = number(&flag, &value()?)?,
+            "--fixtures" => options.fixtures = PathBuf::from(value()?),
+            "--latency" => options.latency = Duration::from_millis(number(&flag, &value()?)?),
+            "--jitter" => options.jitter = Duration::from_millis(number(&flag, &value()?)?),
+            "--error-rate" => options.error_rate = ratio(&flag, &value()?)?,
+            "--rate-limit" => options.rate_limit = Some(number(&flag, &value()?)?),
+            "--tick" => options.tick = Duration::from_millis(number(&flag, &value()?)?),
+            "--volatility" => options.volatility = ratio(&flag, &value()?)?,
+            "--token-ttl" => options.token_ttl = Duration::from_secs(number(&flag, &value()?)?),
+            "--seed" => options.seed = Some(number(&flag, &value()?)?),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a whole number, got `{}`", flag, value))
+}
+
+fn ratio(flag: &str, value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(ratio) if (0.0..=1.0).contains(&ratio) => Ok(ratio),
+        _ => Err(format!("{} expects a number between 0 and 1, got `{}`", flag, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn no_arguments_gives_defaults() {
+        assert_eq!(parse(Vec::new()), Ok(Options::default()));
+    }
+
+    #[test]
+    fn parses_both_flag_forms() {
+        let options = parse(args(&["--port", "9000", "--latency=250", "--error-rate", "0.1", "--rate-limit=30", "--seed", "7"])).unwrap();
+
+        assert_eq!(options.port, 9000);
+        assert_eq!(options.latency, Duration::from_millis(250));
+        assert_eq!(options.error_rate, 0.1);
+        assert_eq!(options.rate_limit, Some(30));
+        assert_eq!(options.seed, Some(7));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse(args(&["--port"])).is_err());
+        assert!(parse(args(&["--port", "http"])).is_err());
+        assert!(parse(args(&["--error-rate", "1.5"])).is_err());
+        assert!(parse(args(&["--verbose"])).is_err());
+    }
+}
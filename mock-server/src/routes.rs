@@ -0,0 +1,101 @@
+// mock-server/src/routes.rs
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Route<'a> {
+    Cryptocurrencies,
+    Cryptocurrency(&'a str),
+    History(&'a str),
+    Book(&'a str),
+    BookUpdates(&'a str),
+    Trades(&'a str),
+    // An asset's ticker on an exchange
+    Ticker { exchange: &'a str, id: &'a str },
+    // JSON-RPC node of a chain
+    Rpc(&'a str),
+    // Esplora's address endpoint, for Bitcoin
+    RpcAddress { chain: &'a str, address: &'a str },
+    Portfolio,
+    Login,
+    Refresh,
+    Logout,
+    // Restores fixture prices and portfolio between end-to-end tests
+    Reset,
+}
+
+pub fn route(path: &str) -> Option<Route<'_>> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["cryptocurrencies"] => Some(Route::Cryptocurrencies),
+        ["cryptocurrencies", id] if !id.is_empty() => Some(Route::Cryptocurrency(id)),
+        ["cryptocurrencies", id, "history"] if !id.is_empty() => Some(Route::History(id)),
+        ["cryptocurrencies", id, "book"] if !id.is_empty() => Some(Route::Book(id)),
+        ["cryptocurrencies", id, "book", "updates"] if !id.is_empty() => Some(Route::BookUpdates(id)),
+        ["cryptocurrencies", id, "trades"] if !id.is_empty() => Some(Route::Trades(id)),
+        ["exchanges", exchange, "cryptocurrencies", id, "ticker"] if !exchange.is_empty() && !id.is_empty() => {
+            Some(Route::Ticker { exchange, id })
+        }
+        ["rpc", chain] if !chain.is_empty() => Some(Route::Rpc(chain)),
+        ["rpc", chain, "address", address] if !chain.is_empty() && !address.is_empty() => {
+            Some(Route::RpcAddress { chain, address })
+        }
+        ["portfolio"] => Some(Route::Portfolio),
+        ["auth", "login"] => Some(Route::Login),
+        ["auth", "refresh"] => Some(Route::Refresh),
+        ["auth", "logout"] => Some(Route::Logout),
+        ["__mock", "reset"] => Some(Route::Reset),
+        _ => None,
+    }
+}
+
+// Splits a request URL into path and query string
+pub fn split_url(url: &str) -> (&str, &str) {
+    url.split_once('?').unwrap_or((url, ""))
+}
+
+pub fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_known_paths() {
+        assert_eq!(route("/cryptocurrencies"), Some(Route::Cryptocurrencies));
+        assert_eq!(route("/cryptocurrencies/"), Some(Route::Cryptocurrencies));
+        assert_eq!(route("/cryptocurrencies/bitcoin"), Some(Route::Cryptocurrency("bitcoin")));
+        assert_eq!(route("/cryptocurrencies/bitcoin/history"), Some(Route::History("bitcoin")));
+        assert_eq!(route("/cryptocurrencies/bitcoin/book"), Some(Route::Book("bitcoin")));
+        assert_eq!(route("/cryptocurrencies/bitcoin/book/updates"), Some(Route::BookUpdates("bitcoin")));
+        assert_eq!(route("/cryptocurrencies/bitcoin/trades"), Some(Route::Trades("bitcoin")));
+        assert_eq!(
+            route("/exchanges/kraken/cryptocurrencies/bitcoin/ticker"),
+            Some(Route::Ticker { exchange: "kraken", id: "bitcoin" })
+        );
+        assert_eq!(route("/rpc/solana"), Some(Route::Rpc("solana")));
+        assert_eq!(
+            route("/rpc/bitcoin/address/bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+            Some(Route::RpcAddress { chain: "bitcoin", address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq" })
+        );
+        assert_eq!(route("/portfolio"), Some(Route::Portfolio));
+        assert_eq!(route("/auth/refresh"), Some(Route::Refresh));
+        assert_eq!(route("/__mock/reset"), Some(Route::Reset));
+        assert_eq!(route("/cryptocurrencies//history"), None);
+        assert_eq!(route("/wallets"), None);
+    }
+
+    #[test]
+    fn reads_query_parameters() {
+        let (path, query) = split_url("/cryptocurrencies/bitcoin/history?days=7&mock_status=503");
+
+        assert_eq!(path, "/cryptocurrencies/bitcoin/history");
+        assert_eq!(query_param(query, "days"), Some("7"));
+        assert_eq!(query_param(query, "interval"), None);
+        assert_eq!(split_url("/portfolio"), ("/portfolio", ""));
+    }
+}
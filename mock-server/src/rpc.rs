@@ -0,0 +1,320 @@
+// mock-server/src/rpc.rs
+//
+// Stand-ins for the block explorers and nodes the app reads watched wallet
+// balances from: Esplora's address endpoint for Bitcoin and JSON-RPC for the
+// other chains, including ERC-20 `balanceOf` calls and SPL token accounts.
+// Addresses missing from `fixtures/wallets.json` and `fixtures/tokens.json`
+// hold nothing.
+use std::collections::HashMap;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// Chains served and the decimals of their smallest unit
+const CHAINS: [(&str, u32); 6] = [
+    ("bitcoin", 8),
+    ("ethereum", 18),
+    ("arbitrum", 18),
+    ("optimism", 18),
+    ("base", 18),
+    ("solana", 9),
+];
+
+// `balanceOf(address)`
+const BALANCE_OF_SELECTOR: &str = "0x70a08231";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+pub fn is_chain(chain: &str) -> bool {
+    CHAINS.iter().any(|(name, _)| *name == chain)
+}
+
+#[derive(Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Vec<Value>,
+}
+
+// A single call or a batch, answered with an array in the same order
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum RpcBody {
+    Batch(Vec<RpcRequest>),
+    Single(RpcRequest),
+}
+
+// A token held by an address: an ERC-20 contract or an SPL mint
+#[derive(Clone, Debug, Deserialize)]
+pub struct TokenBalance {
+    contract: String,
+    decimals: u32,
+    // In whole tokens
+    amount: Decimal,
+}
+
+// Balances in whole coins by chain, then address
+pub type Balances = HashMap<String, HashMap<String, Decimal>>;
+// Token balances by chain, then owner
+pub type TokenBalances = HashMap<String, HashMap<String, Vec<TokenBalance>>>;
+
+#[derive(Clone, Debug, Default)]
+pub struct Wallets {
+    balances: Balances,
+    tokens: TokenBalances,
+}
+
+// EVM addresses are compared without case, base58 ones as they are
+fn same_address(known: &str, address: &str) -> bool {
+    known == address || (address.starts_with("0x") && known.eq_ignore_ascii_case(address))
+}
+
+fn to_units(amount: Decimal, decimals: u32) -> u128 {
+    (amount * Decimal::from(10_u128.pow(decimals))).trunc().to_u128().unwrap_or(0)
+}
+
+fn lookup<'a, T>(by_address: Option<&'a HashMap<String, T>>, address: &str) -> Option<&'a T> {
+    by_address?.iter().find(|(known, _)| same_address(known, address)).map(|(_, value)| value)
+}
+
+impl Wallets {
+    pub fn new(balances: Balances, tokens: TokenBalances) -> Self {
+        Self { balances, tokens }
+    }
+
+    // In the chain's smallest unit
+    fn units(&self, chain: &str, address: &str) -> u128 {
+        let Some(decimals) = CHAINS.iter().find(|(name, _)| *name == chain).map(|(_, decimals)| *decimals) else {
+            return 0;
+        };
+        lookup(self.balances.get(chain), address).map_or(0, |balance| to_units(*balance, decimals))
+    }
+
+    fn tokens_of(&self, chain: &str, owner: &str) -> &[TokenBalance] {
+        lookup(self.tokens.get(chain), owner).map_or(&[], Vec::as_slice)
+    }
+
+    // `eth_call` of `balanceOf(owner)` on a token contract, as a uint256 word.
+    // Any other call reverts.
+    fn eth_call(&self, chain: &str, call: &Value) -> Result<Value, (i64, &'static str)> {
+        let (Some(contract), Some(data)) = (call["to"].as_str(), call["data"].as_str()) else {
+            return Err((-32602, "Invalid params"));
+        };
+        let Some(word) = data.strip_prefix(BALANCE_OF_SELECTOR).filter(|word| word.len() == 64) else {
+            return Err((3, "execution reverted"));
+        };
+        let owner = format!("0x{}", &word[24..]);
+        let units = self
+            .tokens_of(chain, &owner)
+            .iter()
+            .find(|token| token.contract.eq_ignore_ascii_case(contract))
+            .map_or(0, |token| to_units(token.amount, token.decimals));
+        Ok(json!(format!("0x{:064x}", units)))
+    }
+
+    // `getTokenAccountsByOwner` with `jsonParsed` encoding, one account per
+    // held mint
+    fn token_accounts(&self, owner: &str) -> Value {
+        let accounts: Vec<Value> = self
+            .tokens_of("solana", owner)
+            .iter()
+            .enumerate()
+            .map(|(index, token)| {
+                let units = to_units(token.amount, token.decimals);
+                json!({
+                    "pubkey": format!("TokenAccount{}{}", index, &owner[..8.min(owner.len())]),
+                    "account": {
+                        "data": {
+                            "parsed": {
+                                "info": {
+                                    "isNative": false,
+                                    "mint": token.contract,
+                                    "owner": owner,
+                                    "state": "initialized",
+                                    "tokenAmount": {
+                                        "amount": units.to_string(),
+                                        "decimals": token.decimals,
+                                        "uiAmountString": token.amount.normalize().to_string(),
+                                    },
+                                },
+                                "type": "account",
+                            },
+                            "program": "spl-token",
+                            "space": 165,
+                        },
+                        "executable": false,
+                        "lamports": 2_039_280,
+                        "owner": TOKEN_PROGRAM,
+                    },
+                })
+            })
+            .collect();
+        json!({ "context": { "slot": 1 }, "value": accounts })
+    }
+
+    // Responses to a single call or a batch on `chain`
+    pub fn handle(&self, chain: &str, body: &RpcBody) -> Value {
+        match body {
+            RpcBody::Batch(requests) => requests.iter().map(|request| self.call(chain, request)).collect(),
+            RpcBody::Single(request) => self.call(chain, request),
+        }
+    }
+
+    // Esplora's `GET /address/{address}`
+    pub fn esplora_address(&self, address: &str) -> Value {
+        let sats = self.units("bitcoin", address) as u64;
+        let funded = u64::from(sats > 0);
+        json!({
+            "address": address,
+            "chain_stats": {
+                "funded_txo_count": funded,
+                "funded_txo_sum": sats,
+                "spent_txo_count": 0,
+                "spent_txo_sum": 0,
+                "tx_count": funded,
+            },
+            "mempool_stats": {
+                "funded_txo_count": 0,
+                "funded_txo_sum": 0,
+                "spent_txo_count": 0,
+                "spent_txo_sum": 0,
+                "tx_count": 0,
+            },
+        })
+    }
+
+    // A JSON-RPC 2.0 response to `request` on `chain`
+    pub fn call(&self, chain: &str, request: &RpcRequest) -> Value {
+        let address = request.params.first().and_then(Value::as_str);
+        let result = match (chain, request.method.as_str(), address) {
+            ("solana", "getBalance", Some(address)) => Ok(json!({
+                "context": { "slot": 1 },
+                "value": self.units(chain, address) as u64,
+            })),
+            ("solana", "getBalance", None) => Err((-32602, "Invalid params")),
+            (_, "eth_getBalance", Some(address)) if chain != "solana" && chain != "bitcoin" => {
+                Ok(json!(format!("{:#x}", self.units(chain, address))))
+            }
+            (_, "eth_getBalance", None) if chain != "solana" && chain != "bitcoin" => Err((-32602, "Invalid params")),
+            ("solana", "getTokenAccountsByOwner", Some(owner)) => Ok(self.token_accounts(owner)),
+            ("solana", "getTokenAccountsByOwner", None) => Err((-32602, "Invalid params")),
+            (_, "eth_call", _) if chain != "solana" && chain != "bitcoin" => {
+                self.eth_call(chain, request.params.first().unwrap_or(&Value::Null))
+            }
+            _ => Err((-32601, "Method not found")),
+        };
+
+        match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id, "result": result }),
+            Err((code, message)) => json!({ "jsonrpc": "2.0", "id": request.id, "error": { "code": code, "message": message } }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+    const WBTC: &str = "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599";
+
+    fn wallets() -> Wallets {
+        let balances = serde_json::from_str(
+            r#"{
+                "bitcoin": { "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq": "0.42" },
+                "ethereum": { "0xDE0B295669a9FD93d5F28D9Ec85E40f4cb697BAe": "2" },
+                "solana": { "So11111111111111111111111111111111111111112": 1.5 }
+            }"#,
+        )
+        .unwrap();
+        let tokens = serde_json::from_str(
+            r#"{
+                "ethereum": { "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae": [
+                    { "contract": "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599", "decimals": 8, "amount": "0.15" }
+                ] },
+                "solana": { "So11111111111111111111111111111111111111112": [
+                    { "contract": "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh", "decimals": 8, "amount": "0.02" }
+                ] }
+            }"#,
+        )
+        .unwrap();
+        Wallets::new(balances, tokens)
+    }
+
+    fn request(method: &str, params: Value) -> RpcRequest {
+        serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params })).unwrap()
+    }
+
+    #[test]
+    fn esplora_reports_confirmed_satoshis() {
+        let known = wallets().esplora_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(known["chain_stats"]["funded_txo_sum"], 42_000_000);
+        assert_eq!(known["chain_stats"]["spent_txo_sum"], 0);
+
+        let unknown = wallets().esplora_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+        assert_eq!(unknown["chain_stats"]["funded_txo_sum"], 0);
+    }
+
+    #[test]
+    fn json_rpc_balances_are_in_the_smallest_unit() {
+        let wallets = wallets();
+        let response = wallets.call("ethereum", &request("eth_getBalance", json!(["0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae", "latest"])));
+        assert_eq!(response["result"], "0x1bc16d674ec80000");
+        assert_eq!(response["id"], 7);
+
+        let response = wallets.call("base", &request("eth_getBalance", json!(["0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae", "latest"])));
+        assert_eq!(response["result"], "0x0");
+
+        let response = wallets.call("solana", &request("getBalance", json!(["So11111111111111111111111111111111111111112"])));
+        assert_eq!(response["result"]["value"], 1_500_000_000_u64);
+    }
+
+    #[test]
+    fn unknown_methods_and_missing_params_are_rpc_errors() {
+        let wallets = wallets();
+        assert_eq!(wallets.call("solana", &request("eth_getBalance", json!(["x"])))["error"]["code"], -32601);
+        assert_eq!(wallets.call("ethereum", &request("eth_getBalance", json!([])))["error"]["code"], -32602);
+        assert!(is_chain("optimism"));
+        assert!(!is_chain("dogecoin"));
+    }
+
+    #[test]
+    fn token_balances_answer_balance_of_calls_and_token_account_queries() {
+        let wallets = wallets();
+        let balance_of = |contract: &str, data: String| {
+            request("eth_call", json!([{ "to": contract, "data": data }, "latest"]))
+        };
+        let data = format!("{}{:0>64}", BALANCE_OF_SELECTOR, &OWNER[2..]);
+
+        let response = wallets.call("ethereum", &balance_of(WBTC, data.clone()));
+        assert_eq!(response["result"], format!("0x{:064x}", 15_000_000));
+        // Held on another chain, or not at all
+        let response = wallets.call("arbitrum", &balance_of(WBTC, data.clone()));
+        assert_eq!(response["result"], format!("0x{:064x}", 0));
+        let response = wallets.call("ethereum", &balance_of(WBTC, "0x18160ddd".to_string()));
+        assert_eq!(response["error"]["message"], "execution reverted");
+
+        let params = json!(["So11111111111111111111111111111111111111112", { "programId": TOKEN_PROGRAM }, { "encoding": "jsonParsed" }]);
+        let response = wallets.call("solana", &request("getTokenAccountsByOwner", params));
+        let info = &response["result"]["value"][0]["account"]["data"]["parsed"]["info"];
+        assert_eq!(info["mint"], "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh");
+        assert_eq!(info["tokenAmount"]["amount"], "2000000");
+    }
+
+    #[test]
+    fn batches_are_answered_in_order() {
+        let body: RpcBody = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 0, "method": "eth_getBalance", "params": [OWNER, "latest"] },
+            { "jsonrpc": "2.0", "id": 1, "method": "eth_chainId" },
+        ]))
+        .unwrap();
+        let responses = wallets().handle("ethereum", &body);
+
+        assert_eq!(responses[0]["id"], 0);
+        assert_eq!(responses[0]["result"], "0x1bc16d674ec80000");
+        assert_eq!(responses[1]["error"]["code"], -32601);
+    }
+}
@@ -0,0 +1,283 @@
+// mock-server/src/server.rs
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::auth::{LoginRequest, RefreshRequest, Sessions};
+use crate::faults::{self, Overrides, RateLimiter};
+use crate::market::{Fixtures, Market, PortfolioDocument, PortfolioUpdate};
+use crate::options::Options;
+use crate::routes::{self, Route};
+use crate::rpc::{self, RpcBody};
+
+const DEFAULT_HISTORY_DAYS: usize = 30;
+const DEFAULT_BOOK_DEPTH: usize = 50;
+const DEFAULT_TRADE_LIMIT: usize = 50;
+
+type Reply = Response<Cursor<Vec<u8>>>;
+
+pub struct State {
+    pub options: Options,
+    fixtures: Fixtures,
+    pub market: Mutex<Market>,
+    sessions: Mutex<Sessions>,
+    // Keyed by username; accounts start from the fixture portfolio
+    portfolios: Mutex<HashMap<String, PortfolioDocument>>,
+    limiter: Mutex<Option<RateLimiter>>,
+    pub rng: Mutex<StdRng>,
+}
+
+impl State {
+    pub fn new(options: Options, fixtures: Fixtures) -> Self {
+        let rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let limiter = options
+            .rate_limit
+            .map(|limit| RateLimiter::per_minute(limit, Instant::now()));
+
+        Self {
+            market: Mutex::new(Market::new(&fixtures, Utc::now())),
+            sessions: Mutex::new(Sessions::new(fixtures.accounts.clone(), options.token_ttl)),
+            portfolios: Mutex::new(HashMap::new()),
+            limiter: Mutex::new(limiter),
+            rng: Mutex::new(rng),
+            options,
+            fixtures,
+        }
+    }
+
+    fn reset(&self) {
+        *self.market.lock().unwrap() = Market::new(&self.fixtures, Utc::now());
+        self.portfolios.lock().unwrap().clear();
+    }
+}
+
+pub fn handle(state: &State, mut request: Request) {
+    let reply = respond(state, &mut request);
+    println!("{} {} -> {}", request.method(), request.url(), reply.status_code().0);
+    if let Err(error) = request.respond(reply) {
+        eprintln!("failed to send response: {}", error);
+    }
+}
+
+fn respond(state: &State, request: &mut Request) -> Reply {
+    if *request.method() == Method::Options {
+        return cors(Response::from_data(Vec::new()).with_status_code(204));
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = routes::split_url(&url);
+    let Some(route) = routes::route(path) else {
+        return error(404, "Not found");
+    };
+    if route == Route::Reset {
+        state.reset();
+        return cors(Response::from_data(Vec::new()).with_status_code(204));
+    }
+
+    if let Some(limiter) = state.limiter.lock().unwrap().as_mut() {
+        if let Err(retry_after) = limiter.check(Instant::now()) {
+            return rate_limited(retry_after.as_secs().max(1));
+        }
+    }
+
+    let fault = faults::plan(&state.options, &Overrides::from_query(query), &mut *state.rng.lock().unwrap());
+    thread::sleep(fault.latency);
+    match fault.status {
+        Some(429) => return rate_limited(60),
+        Some(status) => return error(status, "Injected failure"),
+        None => {}
+    }
+
+    match (request.method(), route) {
+        (Method::Get, Route::Cryptocurrencies) => json(200, state.market.lock().unwrap().assets()),
+        (Method::Get, Route::Cryptocurrency(id)) => match state.market.lock().unwrap().asset(id) {
+            Some(asset) => json(200, asset),
+            None => error(404, "Cryptocurrency not found"),
+        },
+        (Method::Get, Route::History(id)) => {
+            let days = match positive_param(query, "days", DEFAULT_HISTORY_DAYS) {
+                Ok(days) => days,
+                Err(reply) => return reply,
+            };
+            match state.market.lock().unwrap().history(id, days) {
+                Some(candles) => json(200, candles),
+                None => error(404, "Cryptocurrency not found"),
+            }
+        }
+        (Method::Get, Route::Book(id)) => {
+            let depth = match positive_param(query, "depth", DEFAULT_BOOK_DEPTH) {
+                Ok(depth) => depth,
+                Err(reply) => return reply,
+            };
+            match state.market.lock().unwrap().book(id, depth) {
+                Some(book) => json(200, &book),
+                None => error(404, "Cryptocurrency not found"),
+            }
+        }
+        (Method::Get, Route::BookUpdates(id)) => {
+            let since = match routes::query_param(query, "since").map(str::parse::<u64>) {
+                Some(Ok(since)) => since,
+                _ => return error(400, "`since` must be a sequence number"),
+            };
+            match state.market.lock().unwrap().book_updates(id, since) {
+                Some(updates) => json(200, &updates),
+                None => error(404, "Cryptocurrency not found"),
+            }
+        }
+        (Method::Get, Route::Trades(id)) => {
+            let limit = match positive_param(query, "limit", DEFAULT_TRADE_LIMIT) {
+                Ok(limit) => limit,
+                Err(reply) => return reply,
+            };
+            match state.market.lock().unwrap().trades(id, limit) {
+                Some(trades) => json(200, &trades),
+                None => error(404, "Cryptocurrency not found"),
+            }
+        }
+        (Method::Get, Route::Ticker { exchange, id }) => match state.market.lock().unwrap().ticker(exchange, id) {
+            Some(ticker) => json(200, &ticker),
+            None => error(404, "Cryptocurrency not found"),
+        },
+        (Method::Post, Route::Rpc(chain)) if rpc::is_chain(chain) && chain != "bitcoin" => {
+            match read_json::<RpcBody>(request) {
+                Ok(body) => json(200, &state.fixtures.wallets.handle(chain, &body)),
+                Err(reply) => reply,
+            }
+        }
+        (Method::Get, Route::RpcAddress { chain: "bitcoin", address }) => {
+            json(200, &state.fixtures.wallets.esplora_address(address))
+        }
+        (_, Route::Rpc(_) | Route::RpcAddress { .. }) => error(404, "Unknown chain"),
+        (Method::Get | Method::Put | Method::Post, Route::Portfolio) => {
+            let Some(username) = authenticated_user(state, request) else {
+                return error(401, "Sign in to access the portfolio")
+                    .with_header(header("WWW-Authenticate", "Bearer"));
+            };
+            if *request.method() == Method::Get {
+                let mut portfolios = state.portfolios.lock().unwrap();
+                let portfolio = portfolios.entry(username).or_insert_with(|| state.fixtures.portfolio.clone());
+                return json(200, &*portfolio);
+            }
+
+            let update = match read_json::<PortfolioUpdate>(request) {
+                Ok(update) => update,
+                Err(reply) => return reply,
+            };
+            let mut portfolios = state.portfolios.lock().unwrap();
+            let portfolio = portfolios.entry(username).or_insert_with(|| state.fixtures.portfolio.clone());
+            // A stale write gets the current copy back so the client can merge
+            let status = if portfolio.apply(update) { 200 } else { 409 };
+            json(status, &*portfolio)
+        }
+        (Method::Post, Route::Login) => {
+            let login = match read_json::<LoginRequest>(request) {
+                Ok(login) => login,
+                Err(reply) => return reply,
+            };
+            let tokens = state
+                .sessions
+                .lock()
+                .unwrap()
+                .login(&login, &mut *state.rng.lock().unwrap(), Instant::now());
+            match tokens {
+                Some(tokens) => json(200, &tokens),
+                None => error(401, "Invalid username or password"),
+            }
+        }
+        (Method::Post, Route::Refresh) => {
+            let refresh = match read_json::<RefreshRequest>(request) {
+                Ok(refresh) => refresh,
+                Err(reply) => return reply,
+            };
+            let tokens = state.sessions.lock().unwrap().refresh(
+                &refresh.refresh_token,
+                &mut *state.rng.lock().unwrap(),
+                Instant::now(),
+            );
+            match tokens {
+                Some(tokens) => json(200, &tokens),
+                None => error(401, "Refresh token is invalid or revoked"),
+            }
+        }
+        (Method::Post, Route::Logout) => {
+            let refresh = match read_json::<RefreshRequest>(request) {
+                Ok(refresh) => refresh,
+                Err(reply) => return reply,
+            };
+            state.sessions.lock().unwrap().logout(&refresh.refresh_token);
+            cors(Response::from_data(Vec::new()).with_status_code(204))
+        }
+        _ => error(405, "Method not allowed"),
+    }
+}
+
+fn positive_param(query: &str, name: &str, default: usize) -> Result<usize, Reply> {
+    match routes::query_param(query, name).map(str::parse::<usize>) {
+        None => Ok(default),
+        Some(Ok(value)) if value > 0 => Ok(value),
+        Some(_) => Err(error(400, &format!("`{}` must be a positive whole number", name))),
+    }
+}
+
+fn authenticated_user(state: &State, request: &Request) -> Option<String> {
+    let value = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))?
+        .value
+        .as_str();
+    let token = value.strip_prefix("Bearer ")?;
+    state
+        .sessions
+        .lock()
+        .unwrap()
+        .user(token, Instant::now())
+        .map(str::to_string)
+}
+
+fn read_json<T: DeserializeOwned>(request: &mut Request) -> Result<T, Reply> {
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() {
+        return Err(error(400, "Request body is not valid UTF-8"));
+    }
+    serde_json::from_str(&body).map_err(|parse_error| error(400, &format!("Invalid request body: {}", parse_error)))
+}
+
+fn json<T: Serialize + ?Sized>(status: u16, body: &T) -> Reply {
+    let body = serde_json::to_vec(body).expect("response bodies always serialize");
+    cors(Response::from_data(body).with_status_code(StatusCode(status)))
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn error(status: u16, message: &str) -> Reply {
+    json(status, &serde_json::json!({ "error": message }))
+}
+
+fn rate_limited(retry_after_secs: u64) -> Reply {
+    error(429, "Too many requests").with_header(header("Retry-After", &retry_after_secs.to_string()))
+}
+
+// The app is served from a different origin during development
+fn cors(response: Reply) -> Reply {
+    response
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST, PUT, OPTIONS"))
+        .with_header(header("Access-Control-Allow-Headers", "Content-Type, Authorization"))
+        .with_header(header("Access-Control-Expose-Headers", "Retry-After, WWW-Authenticate"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header names are valid")
+}
@@ -1,10 +1,18 @@
-use std::env;
-
-use crate::pages::{Details, Home, NotFound, Portfolio};
-use crate::store::{Store, StoreAction, StoreContext, StoreProvider, StoreState};
-use dotenv::dotenv;
-use log;
+use crate::auth::{use_auth, AuthProvider};
+use crate::components::asset_search::AssetSearch;
+use crate::components::install_prompt::InstallButton;
+use crate::components::language_selector::LanguageSelector;
+use crate::components::offline_banner::OfflineBanner;
+use crate::components::require_auth::RequireAuth;
+use crate::components::undo_toast::UndoToast;
+use crate::config::{use_config, ConfigProvider};
+use crate::i18n::{use_i18n, I18nProvider};
+use crate::pages::{Arbitrage, Details, Home, Login, NotFound, Portfolio, Simulate};
+use crate::services::api;
+use crate::ssr::InitialState;
+use crate::store::{use_store, StoreAction, StoreProvider};
 use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
 use yew_router::prelude::*;
 
 #[derive(Clone, Routable, PartialEq)]
@@ -15,59 +23,189 @@ pub enum Route {
     Details { id: String },
     #[at("/portfolio")]
     Portfolio,
+    #[at("/arbitrage")]
+    Arbitrage,
+    #[at("/simulate")]
+    Simulate,
+    #[at("/login")]
+    Login,
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
+impl Route {
+    // Pages that need a signed-in user
+    pub fn requires_auth(&self) -> bool {
+        matches!(self, Route::Portfolio)
+    }
+}
+
+#[derive(Properties, PartialEq, Default)]
+pub struct AppProps {
+    // State of the server-rendered page being hydrated
+    #[prop_or_default]
+    pub initial: Option<InitialState>,
+}
+
 pub struct App;
 
 impl Component for App {
     type Message = ();
-    type Properties = ();
+    type Properties = AppProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        // Use COPYRIGHT_TEXT from build-time env, fallback to default if not set
-        // dotenv and std::env are not supported in WASM/browser
-        let copyright_text = option_env!("COPYRIGHT_TEXT")
-            .unwrap_or("© 2023 Crypto Tracker");
-        log::info!("Copyright text: {}", copyright_text);
-
+    fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
-            <StoreProvider>
+            <Providers initial={ctx.props().initial.clone()}>
                 <BrowserRouter>
-                    <div class="app-container min-h-screen flex flex-col bg-gray-50">
-                        <header class="bg-white shadow p-4 flex flex-col md:flex-row md:items-center md:justify-between">
-                            <h1 class="text-2xl font-bold text-blue-700 mb-2 md:mb-0">{"Crypto Tracker"}</h1>
-                            <nav class="flex space-x-4">
-                                <Link<Route> to={Route::Home} classes="text-gray-700 hover:text-blue-600 font-medium transition">{"Home"}</Link<Route>>
-                                <Link<Route> to={Route::Portfolio} classes="text-gray-700 hover:text-blue-600 font-medium transition">{"Portfolio"}</Link<Route>>
-                            </nav>
-                        </header>
-
-                        <main class="flex-1 container mx-auto px-4 py-8">
-                            <Switch<Route> render={switch} />
-                        </main>
-
-                        <footer class="bg-white text-center text-gray-500 py-4 border-t">
-                            <p>{ copyright_text }</p>
-                        </footer>
-                    </div>
+                    <Shell />
                 </BrowserRouter>
-            </StoreProvider>
+            </Providers>
         }
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct ServerAppProps {
+    pub initial: InitialState,
+}
+
+// Server-side counterpart of `App`, rendered by `ssr-server` at `initial.path`
+#[function_component(ServerApp)]
+pub fn server_app(props: &ServerAppProps) -> Html {
+    let history = AnyHistory::from(MemoryHistory::new());
+    history.push(&props.initial.path);
+
+    html! {
+        <Providers initial={Some(props.initial.clone())}>
+            <Router history={history}>
+                <Shell />
+            </Router>
+        </Providers>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ProvidersProps {
+    children: Html,
+    initial: Option<InitialState>,
+}
+
+// With server-rendered state the providers start from it and load the
+// browser's saved state (locale, session, portfolio) once mounted
+#[function_component(Providers)]
+fn providers(props: &ProvidersProps) -> Html {
+    let hydrate = props.initial.is_some();
+    let config = props.initial.as_ref().map(|initial| initial.config.clone());
+    let locale = props.initial.as_ref().map(|initial| initial.locale);
+    let store = props.initial.as_ref().map(|initial| initial.store.clone());
+
+    html! {
+        <ConfigProvider config={config}>
+            <I18nProvider locale={locale} hydrate={hydrate}>
+                <AuthProvider hydrate={hydrate}>
+                    <StoreProvider initial={store} hydrate={hydrate}>
+                        {props.children.clone()}
+                    </StoreProvider>
+                </AuthProvider>
+            </I18nProvider>
+        </ConfigProvider>
+    }
+}
+
+// Page chrome rendered inside the providers so it can use their hooks
+#[function_component(Shell)]
+fn shell() -> Html {
+    let i18n = use_i18n();
+    let config = use_config();
+    let auth = use_auth();
+    let (_, dispatch) = use_store();
+    let navigator = use_navigator().unwrap();
+
+    let on_logout = Callback::from(move |_| {
+        dispatch.emit(StoreAction::ResetPortfolio);
+        wasm_bindgen_futures::spawn_local(api::logout());
+        navigator.push(&Route::Home);
+    });
+
+    // Branding from config.json (or COPYRIGHT_TEXT at build time), else the translated defaults
+    let app_name = config.branding.app_name.clone().unwrap_or_else(|| i18n.t("app.title"));
+    let copyright_text = config
+        .branding
+        .copyright
+        .clone()
+        .unwrap_or_else(|| i18n.t("app.copyright"));
+
+    html! {
+        <div class="app-container min-h-screen flex flex-col bg-gray-50">
+            <header class="bg-white shadow p-4 flex flex-col md:flex-row md:items-center md:justify-between">
+                <h1 class="text-2xl font-bold text-blue-700 mb-2 md:mb-0">{app_name}</h1>
+                <div class="flex items-center space-x-6">
+                    <nav class="flex space-x-4">
+                        <Link<Route> to={Route::Home} classes="text-gray-700 hover:text-blue-600 font-medium transition">{i18n.t("nav.home")}</Link<Route>>
+                        <Link<Route> to={Route::Portfolio} classes="text-gray-700 hover:text-blue-600 font-medium transition">{i18n.t("nav.portfolio")}</Link<Route>>
+                        <Link<Route> to={Route::Arbitrage} classes="text-gray-700 hover:text-blue-600 font-medium transition">{i18n.t("nav.arbitrage")}</Link<Route>>
+                        <Link<Route> to={Route::Simulate} classes="text-gray-700 hover:text-blue-600 font-medium transition">{i18n.t("nav.simulate")}</Link<Route>>
+                    </nav>
+                    <AssetSearch />
+                    {
+                        match &auth.session {
+                            Some(session) => html! {
+                                <div class="flex items-center space-x-3 text-sm">
+                                    <span class="text-gray-500">{i18n.t_args("auth.signed_in_as", &[("name", session.user.name.clone())])}</span>
+                                    <button class="text-gray-700 hover:text-blue-600 font-medium transition" onclick={on_logout}>
+                                        {i18n.t("auth.logout")}
+                                    </button>
+                                </div>
+                            },
+                            None => html! {
+                                <Link<Route> to={Route::Login} classes="text-gray-700 hover:text-blue-600 font-medium transition">{i18n.t("auth.title")}</Link<Route>>
+                            },
+                        }
+                    }
+                    if config.features.install_prompt {
+                        <InstallButton />
+                    }
+                    if config.features.language_selector {
+                        <LanguageSelector />
+                    }
+                </div>
+            </header>
+            if config.features.offline_banner {
+                <OfflineBanner />
+            }
+
+            <main class="flex-1 container mx-auto px-4 py-8">
+                <Switch<Route> render={switch} />
+            </main>
+
+            <footer class="bg-white text-center text-gray-500 py-4 border-t">
+                <p>{ copyright_text }</p>
+            </footer>
+
+            <UndoToast />
+        </div>
+    }
+}
+
 fn switch(routes: Route) -> Html {
-    match routes {
+    let page = match &routes {
         Route::Home => html! { <Home /> },
-        Route::Details { id } => html! { <Details id={id} /> },
+        Route::Details { id } => html! { <Details id={id.clone()} /> },
         Route::Portfolio => html! { <Portfolio /> },
+        Route::Arbitrage => html! { <Arbitrage /> },
+        Route::Simulate => html! { <Simulate /> },
+        Route::Login => html! { <Login /> },
         Route::NotFound => html! { <NotFound /> },
+    };
+
+    if routes.requires_auth() {
+        html! { <RequireAuth>{page}</RequireAuth> }
+    } else {
+        page
     }
 }
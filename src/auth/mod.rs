@@ -0,0 +1,63 @@
+// src/auth/mod.rs
+use yew::prelude::*;
+
+use crate::models::auth::Session;
+use crate::services::session;
+
+// Logging in and out goes through `services::api::{login, logout}`, which
+// store the session; the provider picks changes up from there
+#[derive(Clone, PartialEq)]
+pub struct AuthContext {
+    pub session: Option<Session>,
+}
+
+impl AuthContext {
+    pub fn is_authenticated(&self) -> bool {
+        self.session.is_some()
+    }
+}
+
+#[hook]
+pub fn use_auth() -> AuthContext {
+    use_context::<AuthContext>().expect("Auth context not set")
+}
+
+#[derive(Properties, Debug, PartialEq)]
+pub struct AuthProviderProps {
+    #[prop_or_default]
+    pub children: Html,
+    // Signed-in session to start with, e.g. for tests; defaults to the stored one
+    #[prop_or_default]
+    pub session: Option<Session>,
+    // Render signed out like the server did and load the stored session once mounted
+    #[prop_or_default]
+    pub hydrate: bool,
+}
+
+#[function_component]
+pub fn AuthProvider(props: &AuthProviderProps) -> Html {
+    let seeded = props.session.clone();
+    let hydrate = props.hydrate;
+    let current = use_state(move || if hydrate { seeded } else { seeded.or_else(session::current) });
+
+    {
+        let current = current.clone();
+        use_effect_with((), move |_| {
+            if hydrate && current.is_none() {
+                current.set(session::current());
+            }
+            session::subscribe(Some(Callback::from(move |session| current.set(session))));
+            || session::subscribe(None)
+        });
+    }
+
+    let context = AuthContext {
+        session: (*current).clone(),
+    };
+
+    html! {
+        <ContextProvider<AuthContext> context={context}>
+            {props.children.clone()}
+        </ContextProvider<AuthContext>>
+    }
+}
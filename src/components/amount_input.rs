@@ -0,0 +1,359 @@
+// src/components/amount_input.rs
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::i18n::{use_i18n, I18n};
+use crate::models::crypto::MAX_AMOUNT;
+
+// Fiat entries are limited to cents
+const FIAT_DECIMALS: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmountMode {
+    Quantity,
+    Fiat,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AmountError {
+    Empty,
+    Invalid,
+    Negative,
+    Zero,
+    TooLarge,
+    TooPrecise(u32),
+    NoPrice,
+}
+
+impl AmountError {
+    pub fn message(&self, i18n: &I18n) -> String {
+        match self {
+            AmountError::Empty => i18n.t("amount.error.empty"),
+            AmountError::Invalid => i18n.t("amount.error.invalid"),
+            AmountError::Negative => i18n.t("amount.error.negative"),
+            AmountError::Zero => i18n.t("amount.error.zero"),
+            AmountError::TooLarge => i18n.t("amount.error.too_large"),
+            AmountError::TooPrecise(decimals) => {
+                i18n.t_args("amount.error.too_precise", &[("decimals", decimals.to_string())])
+            }
+            AmountError::NoPrice => i18n.t("amount.error.no_price"),
+        }
+    }
+}
+
+// Parse a user-entered number. The locale decimal separator is always a decimal
+// point; the other of '.' / ',' is treated as a thousands separator, except when
+// it appears once without a trailing group of three digits ("0.5" in a comma locale).
+pub fn parse_amount(input: &str, decimal_separator: char) -> Result<Decimal, AmountError> {
+    let compact: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{a0}' && *c != '\u{202f}' && *c != '\'')
+        .collect();
+    if compact.is_empty() {
+        return Err(AmountError::Empty);
+    }
+
+    let (negative, unsigned) = match compact.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, compact.strip_prefix('+').unwrap_or(&compact)),
+    };
+    if unsigned.is_empty() || !unsigned.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return Err(AmountError::Invalid);
+    }
+
+    let group_separator = if decimal_separator == ',' { '.' } else { ',' };
+    let normalized = if unsigned.contains(decimal_separator) {
+        if unsigned.matches(decimal_separator).count() > 1 {
+            return Err(AmountError::Invalid);
+        }
+        unsigned.replace(group_separator, "").replace(decimal_separator, ".")
+    } else {
+        let groups: Vec<&str> = unsigned.split(group_separator).collect();
+        let is_grouping = groups.len() > 1
+            && !groups[0].is_empty()
+            && groups[1..].iter().all(|group| group.len() == 3);
+        if groups.len() == 2 && !is_grouping {
+            unsigned.replace(group_separator, ".")
+        } else if groups.len() == 1 || is_grouping {
+            unsigned.replace(group_separator, "")
+        } else {
+            return Err(AmountError::Invalid);
+        }
+    };
+
+    let value = Decimal::from_str(&normalized).map_err(|_| AmountError::Invalid)?;
+    if negative && !value.is_zero() {
+        return Err(AmountError::Negative);
+    }
+    Ok(value)
+}
+
+// Validate input in the given mode and return the resulting asset quantity
+pub fn validate_amount(
+    input: &str,
+    mode: AmountMode,
+    decimal_separator: char,
+    price: Decimal,
+    decimals: u32,
+) -> Result<Decimal, AmountError> {
+    let value = parse_amount(input, decimal_separator)?;
+    if value.is_zero() {
+        return Err(AmountError::Zero);
+    }
+    if value > Decimal::from(MAX_AMOUNT) {
+        return Err(AmountError::TooLarge);
+    }
+
+    match mode {
+        AmountMode::Quantity => {
+            if value.normalize().scale() > decimals {
+                return Err(AmountError::TooPrecise(decimals));
+            }
+            Ok(value)
+        }
+        AmountMode::Fiat => {
+            if value.normalize().scale() > FIAT_DECIMALS {
+                return Err(AmountError::TooPrecise(FIAT_DECIMALS));
+            }
+            if price <= Decimal::ZERO {
+                return Err(AmountError::NoPrice);
+            }
+            // A tiny price can make the quantity overflow
+            let quantity = match value.checked_div(price) {
+                Some(quantity) if quantity <= Decimal::from(MAX_AMOUNT) => quantity.round_dp(decimals),
+                _ => return Err(AmountError::TooLarge),
+            };
+            if quantity.is_zero() {
+                return Err(AmountError::Zero);
+            }
+            Ok(quantity.normalize())
+        }
+    }
+}
+
+// Render a value for editing: no grouping, locale decimal separator
+pub fn to_input_text(value: Decimal, decimal_separator: char) -> String {
+    value.normalize().to_string().replace('.', &decimal_separator.to_string())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AmountInputProps {
+    // Quantity shown when the input is first rendered or reset
+    pub initial: Decimal,
+    pub price: Decimal,
+    pub symbol: String,
+    pub decimals: u32,
+    // Emits the validated quantity, or `None` while the input is invalid
+    pub on_change: Callback<Option<Decimal>>,
+}
+
+#[function_component(AmountInput)]
+pub fn amount_input(props: &AmountInputProps) -> Html {
+    let i18n = use_i18n();
+    let decimal_separator = i18n.locale().number_symbols().decimal;
+    let mode = use_state(|| AmountMode::Quantity);
+    let text = use_state(|| {
+        if props.initial.is_zero() {
+            String::new()
+        } else {
+            to_input_text(props.initial, decimal_separator)
+        }
+    });
+    let result = use_memo(
+        ((*text).clone(), *mode, decimal_separator, props.price, props.decimals),
+        |(text, mode, separator, price, decimals)| {
+            validate_amount(text, *mode, *separator, *price, *decimals)
+        },
+    );
+
+    {
+        let on_change = props.on_change.clone();
+        use_effect_with(result.clone(), move |result| {
+            on_change.emit(result.as_ref().as_ref().ok().copied());
+            || ()
+        });
+    }
+
+    let on_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    // Switching modes converts the current entry so the quantity is preserved
+    let on_mode_change = {
+        let mode = mode.clone();
+        let text = text.clone();
+        let result = result.clone();
+        let price = props.price;
+        Callback::from(move |next: AmountMode| {
+            if *mode == next {
+                return;
+            }
+            if let Ok(quantity) = result.as_ref() {
+                let converted = match next {
+                    AmountMode::Quantity => *quantity,
+                    AmountMode::Fiat => (*quantity * price).round_dp(FIAT_DECIMALS),
+                };
+                text.set(to_input_text(converted, decimal_separator));
+            }
+            mode.set(next);
+        })
+    };
+
+    let mode_button = |target: AmountMode, label: String| {
+        let on_mode_change = on_mode_change.clone();
+        let active = *mode == target;
+        html! {
+            <button
+                type="button"
+                class={classes!(
+                    "px-2", "py-1", "text-xs", "font-medium", "transition",
+                    if active { "bg-blue-600 text-white" } else { "bg-white text-gray-600 hover:bg-gray-100" }
+                )}
+                onclick={Callback::from(move |_| on_mode_change.emit(target))}
+            >
+                {label}
+            </button>
+        }
+    };
+
+    let hint = match (result.as_ref(), *mode) {
+        (Ok(quantity), AmountMode::Quantity) => Some(i18n.t_args(
+            "amount.equivalent",
+            &[("value", i18n.currency(&(*quantity * props.price)))],
+        )),
+        (Ok(quantity), AmountMode::Fiat) => Some(i18n.t_args(
+            "amount.equivalent",
+            &[("value", format!("{} {}", i18n.quantity(quantity), props.symbol))],
+        )),
+        (Err(_), _) => None,
+    };
+    // Don't nag about an empty field the user hasn't touched yet
+    let error = match result.as_ref() {
+        Err(AmountError::Empty) => None,
+        Err(err) => Some(err.message(&i18n)),
+        Ok(_) => None,
+    };
+
+    html! {
+        <div class="amount-field flex flex-col space-y-1">
+            <div class="flex items-center space-x-2">
+                <input
+                    type="text"
+                    inputmode="decimal"
+                    autocomplete="off"
+                    placeholder={i18n.t("card.enter_amount")}
+                    value={(*text).clone()}
+                    oninput={on_input}
+                    aria-invalid={error.is_some().to_string()}
+                    class={classes!(
+                        "border", "rounded", "px-2", "py-1", "w-32", "focus:outline-none", "focus:ring-2",
+                        if error.is_some() { "border-red-500 focus:ring-red-400" } else { "focus:ring-blue-400" }
+                    )}
+                />
+                <div class="amount-mode inline-flex border rounded overflow-hidden">
+                    {mode_button(AmountMode::Quantity, props.symbol.clone())}
+                    {mode_button(AmountMode::Fiat, i18n.t("amount.fiat"))}
+                </div>
+            </div>
+            {
+                if let Some(error) = error {
+                    html! { <p class="amount-error text-xs text-red-600">{error}</p> }
+                } else if let Some(hint) = hint {
+                    html! { <p class="amount-hint text-xs text-gray-500">{hint}</p> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn parses_plain_numbers() {
+        assert_eq!(parse_amount("1.5", '.'), Ok(dec!(1.5)));
+        assert_eq!(parse_amount(" 42 ", '.'), Ok(dec!(42)));
+        assert_eq!(parse_amount("+0.25", '.'), Ok(dec!(0.25)));
+        assert_eq!(parse_amount(".5", '.'), Ok(dec!(0.5)));
+    }
+
+    #[test]
+    fn parses_locale_decimal_separator() {
+        assert_eq!(parse_amount("1,5", ','), Ok(dec!(1.5)));
+        assert_eq!(parse_amount("1.234,56", ','), Ok(dec!(1234.56)));
+        assert_eq!(parse_amount("1 234,56", ','), Ok(dec!(1234.56)));
+        assert_eq!(parse_amount("1\u{202f}234,5", ','), Ok(dec!(1234.5)));
+    }
+
+    #[test]
+    fn parses_grouping_separators() {
+        assert_eq!(parse_amount("1,234", '.'), Ok(dec!(1234)));
+        assert_eq!(parse_amount("1,234,567.89", '.'), Ok(dec!(1234567.89)));
+        assert_eq!(parse_amount("1.234.567", ','), Ok(dec!(1234567)));
+    }
+
+    #[test]
+    fn treats_lone_foreign_separator_as_decimal() {
+        assert_eq!(parse_amount("0.5", ','), Ok(dec!(0.5)));
+        assert_eq!(parse_amount("0,00012", '.'), Ok(dec!(0.00012)));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(parse_amount("", '.'), Err(AmountError::Empty));
+        assert_eq!(parse_amount("   ", '.'), Err(AmountError::Empty));
+        assert_eq!(parse_amount("abc", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("NaN", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("inf", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("1e5", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("1.2.3", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("1,2,3", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount("-", '.'), Err(AmountError::Invalid));
+        assert_eq!(parse_amount(".", '.'), Err(AmountError::Invalid));
+    }
+
+    #[test]
+    fn rejects_negative_values() {
+        assert_eq!(parse_amount("-1", '.'), Err(AmountError::Negative));
+        assert_eq!(parse_amount("-0", '.'), Ok(dec!(0)));
+    }
+
+    #[test]
+    fn validates_quantity_bounds_and_precision() {
+        let validate = |input| validate_amount(input, AmountMode::Quantity, '.', dec!(100), 6);
+        assert_eq!(validate("0.000001"), Ok(dec!(0.000001)));
+        assert_eq!(validate("0.0000001"), Err(AmountError::TooPrecise(6)));
+        assert_eq!(validate("0"), Err(AmountError::Zero));
+        assert_eq!(validate("1000000000000001"), Err(AmountError::TooLarge));
+        assert_eq!(validate("1.500000"), Ok(dec!(1.5)));
+    }
+
+    #[test]
+    fn converts_fiat_to_quantity() {
+        let validate = |input, price| validate_amount(input, AmountMode::Fiat, '.', price, 8);
+        assert_eq!(validate("100", dec!(50)), Ok(dec!(2)));
+        assert_eq!(validate("1", dec!(3)), Ok(dec!(0.33333333)));
+        assert_eq!(validate("10.001", dec!(3)), Err(AmountError::TooPrecise(2)));
+        assert_eq!(validate("10", dec!(0)), Err(AmountError::NoPrice));
+        assert_eq!(validate("0.01", dec!(100000000000)), Err(AmountError::Zero));
+    }
+
+    #[test]
+    fn fiat_for_a_tiny_price_is_too_large_rather_than_overflowing() {
+        let validate = |input, price| validate_amount(input, AmountMode::Fiat, '.', price, 8);
+        assert_eq!(validate("1000000000000000", dec!(0.00000000000001)), Err(AmountError::TooLarge));
+        assert_eq!(validate("1000", dec!(0.00000000000001)), Err(AmountError::TooLarge));
+        assert_eq!(validate("1", dec!(0.0000001)), Ok(dec!(10000000)));
+    }
+}
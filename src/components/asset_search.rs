@@ -0,0 +1,216 @@
+// src/components/asset_search.rs
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::i18n::use_i18n;
+use crate::models::crypto::Cryptocurrency;
+use crate::services::api;
+use crate::store::{use_store, RecentAsset, StoreAction};
+
+// Most results shown in the dropdown at once
+const MAX_RESULTS: usize = 8;
+
+// Assets whose name, symbol or id contains `query`, those starting with it first
+pub fn search<'a>(query: &str, cryptocurrencies: &'a [Cryptocurrency]) -> Vec<&'a Cryptocurrency> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut found: Vec<&Cryptocurrency> = cryptocurrencies
+        .iter()
+        .filter(|crypto| {
+            [&crypto.name, &crypto.symbol, &crypto.id]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+        })
+        .collect();
+    found.sort_by_key(|crypto| {
+        let prefix = crypto.symbol.to_lowercase().starts_with(&query) || crypto.name.to_lowercase().starts_with(&query);
+        !prefix
+    });
+    found.truncate(MAX_RESULTS);
+    found
+}
+
+// Header search box. While empty its dropdown offers the recently viewed assets.
+#[function_component(AssetSearch)]
+pub fn asset_search() -> Html {
+    let i18n = use_i18n();
+    let (store, dispatch) = use_store();
+    let navigator = use_navigator().unwrap();
+    let query = use_state(String::new);
+    let open = use_state(|| false);
+    // Market data fetched for searching on pages that haven't loaded the list
+    let fetched = use_state(|| None::<Vec<Cryptocurrency>>);
+
+    let market = if store.cryptocurrencies.is_empty() {
+        fetched.as_deref().unwrap_or_default()
+    } else {
+        &store.cryptocurrencies[..]
+    };
+    let results: Vec<RecentAsset> = search(&query, market).into_iter().map(RecentAsset::from).collect();
+
+    let go_to = {
+        let query = query.clone();
+        let open = open.clone();
+        Callback::from(move |id: String| {
+            query.set(String::new());
+            open.set(false);
+            navigator.push(&Route::Details { id });
+        })
+    };
+
+    let on_focus = {
+        let open = open.clone();
+        let fetched = fetched.clone();
+        let needs_market = store.cryptocurrencies.is_empty() && fetched.is_none();
+        Callback::from(move |_: FocusEvent| {
+            open.set(true);
+            if needs_market {
+                let fetched = fetched.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api::get_cryptocurrencies().await {
+                        Ok(cryptocurrencies) => fetched.set(Some(cryptocurrencies)),
+                        Err(err) => log::warn!("Failed to load assets for search: {}", err),
+                    }
+                });
+            }
+        })
+    };
+
+    let on_blur = {
+        let open = open.clone();
+        Callback::from(move |_: FocusEvent| open.set(false))
+    };
+
+    let on_input = {
+        let query = query.clone();
+        let open = open.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+            open.set(true);
+        })
+    };
+
+    let on_keydown = {
+        let open = open.clone();
+        let go_to = go_to.clone();
+        let first = results.first().map(|asset| asset.id.clone());
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                if let Some(id) = first.clone() {
+                    e.prevent_default();
+                    go_to.emit(id);
+                }
+            }
+            "Escape" => open.set(false),
+            _ => {}
+        })
+    };
+
+    // Keeps focus in the input so the click lands before the dropdown closes
+    let keep_focus = Callback::from(|e: MouseEvent| e.prevent_default());
+
+    let option = |asset: &RecentAsset| {
+        let on_click = {
+            let go_to = go_to.clone();
+            let id = asset.id.clone();
+            Callback::from(move |_| go_to.emit(id.clone()))
+        };
+        html! {
+            <li key={asset.id.clone()}>
+                <button
+                    class="search-result w-full flex items-center space-x-2 px-3 py-2 text-left hover:bg-blue-50 transition"
+                    onmousedown={keep_focus.clone()}
+                    onclick={on_click}
+                >
+                    <span class="font-mono font-bold text-blue-700">{&asset.symbol}</span>
+                    <span class="text-gray-700">{&asset.name}</span>
+                </button>
+            </li>
+        }
+    };
+
+    let dropdown = if !*open {
+        html! {}
+    } else if query.trim().is_empty() {
+        if store.recently_viewed.is_empty() {
+            html! {}
+        } else {
+            let on_clear = Callback::from(move |_| dispatch.emit(StoreAction::ClearRecentlyViewed));
+            html! {
+                <div class="search-dropdown search-recent absolute right-0 mt-1 w-64 bg-white border rounded shadow-lg z-10">
+                    <div class="flex items-baseline justify-between px-3 pt-2 text-xs text-gray-500">
+                        <span class="font-semibold">{i18n.t("recent.title")}</span>
+                        <button class="recent-clear hover:text-blue-600 transition" onmousedown={keep_focus.clone()} onclick={on_clear}>
+                            {i18n.t("recent.clear")}
+                        </button>
+                    </div>
+                    <ul>{store.recently_viewed.iter().map(option).collect::<Html>()}</ul>
+                </div>
+            }
+        }
+    } else if results.is_empty() {
+        html! {
+            <div class="search-dropdown absolute right-0 mt-1 w-64 bg-white border rounded shadow-lg z-10 px-3 py-2 text-sm text-gray-500">
+                {i18n.t("search.no_results")}
+            </div>
+        }
+    } else {
+        html! {
+            <ul class="search-dropdown absolute right-0 mt-1 w-64 bg-white border rounded shadow-lg z-10">
+                {results.iter().map(option).collect::<Html>()}
+            </ul>
+        }
+    };
+
+    html! {
+        <div class="asset-search relative">
+            <input
+                type="search"
+                class="border rounded px-2 py-1 text-sm w-48 focus:outline-none focus:ring-2 focus:ring-blue-400"
+                placeholder={i18n.t("search.placeholder")}
+                aria-label={i18n.t("search.placeholder")}
+                value={(*query).clone()}
+                onfocus={on_focus}
+                onblur={on_blur}
+                oninput={on_input}
+                onkeydown={on_keydown}
+            />
+            {dropdown}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::sample_cryptocurrencies;
+
+    fn ids(found: Vec<&Cryptocurrency>) -> Vec<&str> {
+        found.into_iter().map(|crypto| crypto.id.as_str()).collect()
+    }
+
+    #[test]
+    fn matches_name_symbol_or_id_ignoring_case() {
+        let market = sample_cryptocurrencies();
+
+        assert_eq!(ids(search("BIT", &market)), ["bitcoin"]);
+        assert_eq!(ids(search(" sol ", &market)), ["solana"]);
+        assert_eq!(ids(search("polka", &market)), ["polkadot"]);
+        assert!(search("", &market).is_empty());
+        assert!(search("doge", &market).is_empty());
+    }
+
+    #[test]
+    fn prefix_matches_come_first() {
+        let market = sample_cryptocurrencies();
+
+        // Cardano only contains a "d"; Polkadot's symbol starts with one
+        assert_eq!(ids(search("d", &market)), ["polkadot", "cardano"]);
+    }
+}
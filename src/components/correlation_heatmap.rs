@@ -0,0 +1,95 @@
+// src/components/correlation_heatmap.rs
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+// Cell colours at a correlation of -1, 0 and +1
+const NEGATIVE: (f64, f64, f64) = (214.0, 48.0, 49.0);
+const NEUTRAL: (f64, f64, f64) = (255.0, 255.0, 255.0);
+const POSITIVE: (f64, f64, f64) = (108.0, 92.0, 231.0);
+const UNKNOWN: &str = "#f1f2f6";
+
+// Blends from white towards red or purple with the strength of the
+// correlation; grey when there is none to show
+fn cell_color(value: Option<f64>) -> String {
+    let Some(value) = value.filter(|value| value.is_finite()) else {
+        return UNKNOWN.to_string();
+    };
+    let value = value.clamp(-1.0, 1.0);
+    let target = if value < 0.0 { NEGATIVE } else { POSITIVE };
+    let mix = |from: f64, to: f64| (from + (to - from) * value.abs()).round() as u8;
+    format!(
+        "rgb({}, {}, {})",
+        mix(NEUTRAL.0, target.0),
+        mix(NEUTRAL.1, target.1),
+        mix(NEUTRAL.2, target.2)
+    )
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CorrelationHeatmapProps {
+    // Row and column headings, in the matrix's order
+    pub labels: Vec<String>,
+    pub matrix: Vec<Vec<Option<f64>>>,
+}
+
+// Square grid of pairwise correlations, coloured by strength and sign
+#[function_component(CorrelationHeatmap)]
+pub fn correlation_heatmap(props: &CorrelationHeatmapProps) -> Html {
+    let i18n = use_i18n();
+
+    let rows = props.labels.iter().zip(&props.matrix).map(|(label, row)| {
+        let cells = row.iter().map(|value| {
+            let strong = value.is_some_and(|value| value.abs() > 0.6);
+            html! {
+                <td
+                    class={classes!("heatmap-cell", strong.then_some("heatmap-strong"))}
+                    style={format!("background-color: {}", cell_color(*value))}
+                >
+                    {value.map(|value| i18n.number(value, 2)).unwrap_or_else(|| "–".to_string())}
+                </td>
+            }
+        });
+        html! {
+            <tr>
+                <th class="heatmap-label" scope="row">{label}</th>
+                {for cells}
+            </tr>
+        }
+    });
+
+    html! {
+        <div class="correlation-heatmap overflow-x-auto">
+            <table class="text-xs font-mono">
+                <thead>
+                    <tr>
+                        <th></th>
+                        {for props.labels.iter().map(|label| html! { <th class="heatmap-label" scope="col">{label}</th> })}
+                    </tr>
+                </thead>
+                <tbody>{for rows}</tbody>
+            </table>
+            <p class="heatmap-legend text-xs text-gray-500 mt-1">
+                <span class="heatmap-key" style={format!("background-color: {}", cell_color(Some(-1.0)))}>{"-1"}</span>
+                <span class="heatmap-key" style={format!("background-color: {}", cell_color(Some(0.0)))}>{"0"}</span>
+                <span class="heatmap-key" style={format!("background-color: {}", cell_color(Some(1.0)))}>{"+1"}</span>
+            </p>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_color_fades_to_white_at_zero() {
+        assert_eq!(cell_color(Some(0.0)), "rgb(255, 255, 255)");
+        assert_eq!(cell_color(Some(1.0)), "rgb(108, 92, 231)");
+        assert_eq!(cell_color(Some(-1.0)), "rgb(214, 48, 49)");
+        assert_eq!(cell_color(Some(-0.5)), "rgb(235, 152, 152)");
+        assert_eq!(cell_color(Some(3.0)), cell_color(Some(1.0)));
+        assert_eq!(cell_color(None), UNKNOWN);
+        assert_eq!(cell_color(Some(f64::NAN)), UNKNOWN);
+    }
+}
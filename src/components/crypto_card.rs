@@ -1,16 +1,17 @@
 // src/components/crypto_card.rs
 use yew::prelude::*;
 
-use crate::models::crypto;
+use crate::components::amount_input::AmountInput;
+use crate::i18n::use_i18n;
 use crate::models::crypto::Cryptocurrency;
 use crate::models::crypto::PortfolioItem;
-use web_sys::HtmlInputElement;
+use rust_decimal::Decimal;
 
 #[derive(Properties, PartialEq)]
 pub struct CryptoCardProps {
     pub cryptocurrency: Cryptocurrency,
     pub in_portfolio: bool,
-    pub amount: Option<f64>,
+    pub amount: Option<Decimal>,
     pub on_add_to_portfolio: Callback<PortfolioItem>,
     pub on_update_portfolio: Callback<PortfolioItem>,
     pub on_remove_from_portfolio: Callback<String>,
@@ -18,18 +19,15 @@ pub struct CryptoCardProps {
 
 #[function_component(CryptoCard)]
 pub fn crypto_card(props: &CryptoCardProps) -> Html {
-    let amount_input_ref = use_node_ref();
-    let amount = use_state(|| props.amount.unwrap_or(0.0));
+    let i18n = use_i18n();
+    // Latest valid quantity from the amount input, `None` while it is invalid
+    let amount = use_state(|| props.amount);
     let show_amount_input = use_state(|| false);
+    let holdings = props.amount.unwrap_or(Decimal::ZERO);
 
     let on_amount_change = {
         let amount = amount.clone();
-        Callback::from(move |e: Event| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            if let Ok(value) = input.value().parse::<f64>() {
-                amount.set(value);
-            }
-        })
+        Callback::from(move |value: Option<Decimal>| amount.set(value))
     };
 
     let on_add_click = {
@@ -40,11 +38,13 @@ pub fn crypto_card(props: &CryptoCardProps) -> Html {
 
         Callback::from(move |_| {
             if *show_input {
-                _on_add_to_portfolio.emit(PortfolioItem {
-                    crypto_id: crypto_id.clone(),
-                    amount: *amount,
-                });
-                show_input.set(false);
+                if let Some(amount) = *amount {
+                    _on_add_to_portfolio.emit(PortfolioItem {
+                        crypto_id: crypto_id.clone(),
+                        amount,
+                    });
+                    show_input.set(false);
+                }
             } else {
                 show_input.set(true);
             }
@@ -57,10 +57,12 @@ pub fn crypto_card(props: &CryptoCardProps) -> Html {
         let amount = *amount;
 
         Callback::from(move |_| {
-            _on_update_portfolio.emit(PortfolioItem {
-                crypto_id: crypto_id.clone(),
-                amount,
-            });
+            if let Some(amount) = amount {
+                _on_update_portfolio.emit(PortfolioItem {
+                    crypto_id: crypto_id.clone(),
+                    amount,
+                });
+            }
         })
     };
 
@@ -82,38 +84,36 @@ pub fn crypto_card(props: &CryptoCardProps) -> Html {
 
             <div class="card-body space-y-4">
                 <div class="price-info flex items-center space-x-4">
-                    <p class="price text-2xl font-bold text-gray-800">{format!("${:.2}", props.cryptocurrency.price)}</p>
+                    <p class="price text-2xl font-bold text-gray-800">{i18n.price(&props.cryptocurrency.price)}</p>
                     <p class={format!(
                         "text-sm font-medium {}",
                         if props.cryptocurrency.price_change_24h >= 0.0 { "text-green-600" } else { "text-red-600" }
                     )}>
-                        {format!("{:.2}%", props.cryptocurrency.price_change_24h)}
+                        {i18n.signed_percent(props.cryptocurrency.price_change_24h)}
                     </p>
                 </div>
 
                 <div class="market-info text-gray-600 space-y-1">
-                    <p>{"Market Cap: "}<span class="font-semibold text-gray-800">{format!("${:.2} B", props.cryptocurrency.market_cap / 1_000_000_000.0)}</span></p>
-                    <p>{"24h Volume: "}<span class="font-semibold text-gray-800">{format!("${:.2} B", props.cryptocurrency.volume_24h / 1_000_000_000.0)}</span></p>
+                    <p>{i18n.t("card.market_cap")}<span class="font-semibold text-gray-800">{i18n.compact_currency(props.cryptocurrency.market_cap)}</span></p>
+                    <p>{i18n.t("card.volume_24h")}<span class="font-semibold text-gray-800">{i18n.compact_currency(props.cryptocurrency.volume_24h)}</span></p>
                 </div>
 
                 {if *show_amount_input {
                     html! {
-                        <div class="amount-input flex items-center space-x-2">
-                            <input
-                                type="number"
-                                step="0.000001"
-                                min="0"
-                                placeholder="Enter amount"
-                                ref={amount_input_ref}
-                                value={amount.to_string()}
-                                onchange={on_amount_change}
-                                class="border rounded px-2 py-1 w-32 focus:outline-none focus:ring-2 focus:ring-blue-400"
+                        <div class="amount-input flex items-start space-x-2">
+                            <AmountInput
+                                initial={Decimal::ZERO}
+                                price={props.cryptocurrency.price}
+                                symbol={props.cryptocurrency.symbol.clone()}
+                                decimals={props.cryptocurrency.decimals}
+                                on_change={on_amount_change.clone()}
                             />
                             <button
-                                class="px-4 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition"
+                                class="px-4 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition disabled:opacity-50"
+                                disabled={amount.is_none()}
                                 onclick={on_add_click}
                             >
-                                {"Add"}
+                                {i18n.t("common.add")}
                             </button>
                         </div>
                     }
@@ -121,29 +121,29 @@ pub fn crypto_card(props: &CryptoCardProps) -> Html {
                     html! {
                         <div class="portfolio-actions space-y-2">
                             <div class="amount-display">
-                                <p class="text-gray-700">{"Your holdings: "}<span class="font-semibold">{format!("{:.6} {}", *amount, props.cryptocurrency.symbol)}</span></p>
-                                <p class="text-gray-700">{"Value: "}<span class="font-semibold text-green-600">{format!("${:.2}", *amount * props.cryptocurrency.price)}</span></p>
+                                <p class="text-gray-700">{i18n.t("card.holdings")}<span class="font-semibold">{format!("{} {}", i18n.quantity(&holdings), props.cryptocurrency.symbol)}</span></p>
+                                <p class="text-gray-700">{i18n.t("card.value")}<span class="font-semibold text-green-600">{i18n.currency(&props.cryptocurrency.value_of(holdings))}</span></p>
                             </div>
-                            <div class="portfolio-buttons flex items-center space-x-2">
-                                <input
-                                    type="number"
-                                    step="0.000001"
-                                    min="0"
-                                    value={amount.to_string()}
-                                    onchange={on_amount_change}
-                                    class="border rounded px-2 py-1 w-32 focus:outline-none focus:ring-2 focus:ring-blue-400"
+                            <div class="portfolio-buttons flex items-start space-x-2">
+                                <AmountInput
+                                    initial={holdings}
+                                    price={props.cryptocurrency.price}
+                                    symbol={props.cryptocurrency.symbol.clone()}
+                                    decimals={props.cryptocurrency.decimals}
+                                    on_change={on_amount_change}
                                 />
                                 <button
-                                    class="px-4 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition"
+                                    class="px-4 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition disabled:opacity-50"
+                                    disabled={amount.is_none()}
                                     onclick={on_update_click}
                                 >
-                                    {"Update"}
+                                    {i18n.t("common.update")}
                                 </button>
                                 <button
                                     class="remove px-4 py-1 bg-red-500 text-white rounded hover:bg-red-600 transition"
                                     onclick={on_remove_click}
                                 >
-                                    {"Remove"}
+                                    {i18n.t("common.remove")}
                                 </button>
                             </div>
                         </div>
@@ -155,7 +155,7 @@ pub fn crypto_card(props: &CryptoCardProps) -> Html {
                                 class="px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition"
                                 onclick={on_add_click}
                             >
-                                {"Add to Portfolio"}
+                                {i18n.t("card.add_to_portfolio")}
                             </button>
                         </div>
                     }
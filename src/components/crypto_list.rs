@@ -3,6 +3,7 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::app::Route;
+use crate::i18n::use_i18n;
 use crate::models::crypto::Cryptocurrency;
 
 #[derive(Properties, PartialEq)]
@@ -13,6 +14,7 @@ pub struct CryptoListProps {
 #[function_component(CryptoList)]
 pub fn crypto_list(props: &CryptoListProps) -> Html {
     let navigator = use_navigator().unwrap();
+    let i18n = use_i18n();
 
     let on_crypto_click = {
         let navigator = navigator.clone();
@@ -44,12 +46,12 @@ pub fn crypto_list(props: &CryptoListProps) -> Html {
                             <span class="name text-gray-700">{&crypto.name}</span>
                         </div>
                         <div class="crypto-price flex items-center space-x-4">
-                            <span class="price font-semibold text-gray-800">{format!("${:.2}", crypto.price)}</span>
+                            <span class="price font-semibold text-gray-800">{i18n.price(&crypto.price)}</span>
                             <span class={format!(
                                 "ml-2 text-sm font-medium {}",
                                 if crypto.price_change_24h >= 0.0 { "text-green-600" } else { "text-red-600" }
                             )}>
-                                {format!("{:.2}%", crypto.price_change_24h)}
+                                {i18n.signed_percent(crypto.price_change_24h)}
                             </span>
                         </div>
                     </div>
@@ -1,6 +1,8 @@
 // src/components/error.rs
 use yew::prelude::*;
 
+use crate::i18n::use_i18n;
+
 #[derive(Properties, PartialEq)]
 pub struct ErrorProps {
     pub message: String,
@@ -9,6 +11,7 @@ pub struct ErrorProps {
 
 #[function_component(Error)]
 pub fn error(props: &ErrorProps) -> Html {
+    let i18n = use_i18n();
     let on_retry = {
         let on_retry = props.on_retry.clone();
         Callback::from(move |_| {
@@ -24,7 +27,7 @@ pub fn error(props: &ErrorProps) -> Html {
             <p class="error-message text-red-700 font-semibold mb-2">{&props.message}</p>
             {
                 if props.on_retry.is_some() {
-                    html! { <button class="px-4 py-2 bg-red-600 text-white rounded hover:bg-red-700 transition" onclick={on_retry}>{"Retry"}</button> }
+                    html! { <button class="px-4 py-2 bg-red-600 text-white rounded hover:bg-red-700 transition" onclick={on_retry}>{i18n.t("common.retry")}</button> }
                 } else {
                     html! {}
                 }
@@ -0,0 +1,131 @@
+// src/components/exchange_prices.rs
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gloo::timers::callback::Interval;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use yew::prelude::*;
+
+use crate::components::error::Error;
+use crate::components::loading::Loading;
+use crate::config::use_config;
+use crate::i18n::{use_i18n, I18n};
+use crate::models::exchange::{aggregate, Aggregate, Source, SourceStatus};
+use crate::services::api;
+
+#[derive(Properties, PartialEq)]
+pub struct ExchangePricesProps {
+    pub id: String,
+}
+
+// Aggregate price of an asset and what each exchange quotes for it
+#[function_component(ExchangePrices)]
+pub fn exchange_prices(props: &ExchangePricesProps) -> Html {
+    let i18n = use_i18n();
+    let refresh_secs = use_config().refresh.prices_secs;
+    let prices = use_state(|| None::<Aggregate>);
+
+    {
+        let prices = prices.clone();
+        use_effect_with((props.id.clone(), refresh_secs), move |(id, secs)| {
+            prices.set(None);
+            let current = Rc::new(Cell::new(true));
+            let load = {
+                let (id, current) = (id.clone(), current.clone());
+                move || {
+                    let (id, current, prices) = (id.clone(), current.clone(), prices.clone());
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let quotes = api::get_exchange_quotes(&id).await;
+                        if current.get() {
+                            prices.set(Some(aggregate(quotes)));
+                        }
+                    });
+                }
+            };
+
+            load();
+            let refresh = (*secs > 0).then(|| Interval::new(secs.saturating_mul(1000), load));
+            move || {
+                current.set(false);
+                drop(refresh);
+            }
+        });
+    }
+
+    let Some(prices) = &*prices else {
+        return html! { <Loading /> };
+    };
+    let Some(price) = prices.price else {
+        return html! {
+            <Error message={i18n.t("exchanges.all_failed")} on_retry={None::<Callback<()>>} />
+        };
+    };
+    let used = prices.included().count();
+
+    html! {
+        <section class="exchange-prices mt-4">
+            <div class="flex items-baseline justify-between mb-3">
+                <div>
+                    <span class="text-sm text-gray-500">{i18n.t("exchanges.aggregate")}</span>
+                    <div class="exchange-aggregate text-2xl font-bold">{i18n.price(&price)}</div>
+                </div>
+                <span class="exchange-sources text-xs text-gray-500">
+                    {i18n.t_args("exchanges.sources", &[("used", used.to_string()), ("total", prices.sources.len().to_string())])}
+                </span>
+            </div>
+            <div class="overflow-x-auto">
+                <table class="w-full text-sm">
+                    <thead>
+                        <tr class="text-right text-xs text-gray-500 border-b">
+                            <th class="text-left py-1">{i18n.t("exchanges.exchange")}</th>
+                            <th>{i18n.t("exchanges.last")}</th>
+                            <th>{i18n.t("exchanges.bid")}</th>
+                            <th>{i18n.t("exchanges.ask")}</th>
+                            <th>{i18n.t("exchanges.spread")}</th>
+                            <th>{i18n.t("exchanges.volume")}</th>
+                            <th>{i18n.t("exchanges.deviation")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {for prices.sources.iter().map(|source| source_row(&i18n, source))}
+                    </tbody>
+                </table>
+            </div>
+        </section>
+    }
+}
+
+fn source_row(i18n: &I18n, source: &Source) -> Html {
+    let percent = |value: Decimal| value.to_f64().unwrap_or_default();
+    let (class, badge) = match &source.status {
+        SourceStatus::Included => ("exchange-included", None),
+        SourceStatus::Outlier => ("exchange-outlier", Some(html! {
+            <span class="exchange-badge" title={i18n.t("exchanges.outlier_hint")}>{i18n.t("exchanges.outlier")}</span>
+        })),
+        SourceStatus::Failed(error) => ("exchange-failed", Some(html! {
+            <span class="exchange-badge" title={error.clone()}>{i18n.t("exchanges.unavailable")}</span>
+        })),
+    };
+
+    let cells = match &source.ticker {
+        Some(ticker) => html! {
+            <>
+                <td>{i18n.price(&ticker.last)}</td>
+                <td>{i18n.price(&ticker.bid)}</td>
+                <td>{i18n.price(&ticker.ask)}</td>
+                <td>{ticker.spread_percent().map(|spread| i18n.percent(percent(spread))).unwrap_or_default()}</td>
+                <td>{i18n.compact_currency(ticker.volume_24h)}</td>
+                <td>{source.deviation_percent.map(|deviation| i18n.signed_percent(percent(deviation))).unwrap_or_default()}</td>
+            </>
+        },
+        None => html! { <td colspan="6" class="text-center text-gray-400">{"—"}</td> },
+    };
+
+    html! {
+        <tr key={source.exchange.clone()} class={classes!("exchange-row", class, "text-right", "font-mono", "border-b")}>
+            <td class="text-left font-sans py-1">{&source.name}{" "}{badge}</td>
+            {cells}
+        </tr>
+    }
+}
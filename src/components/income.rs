@@ -0,0 +1,395 @@
+// src/components/income.rs
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::components::amount_input::{parse_amount, validate_amount, AmountError, AmountMode};
+use crate::i18n::{use_i18n, I18n};
+use crate::models::crypto::MAX_AMOUNT;
+use crate::models::income::{estimate_apy, summarize, yearly_csv, IncomeEvent, IncomeKind, Period};
+use crate::models::wallet::WatchedWallet;
+use crate::services::api;
+use crate::store::{use_store, StoreAction};
+
+// The form as typed; checked on submit
+#[derive(Clone, Debug, PartialEq)]
+struct Draft {
+    crypto_id: String,
+    kind: IncomeKind,
+    amount: String,
+    // Left empty to look up the asset's closing price on `received_on`
+    value: String,
+    received_on: String,
+    // `WatchedWallet::key`, empty when paid in elsewhere
+    wallet: String,
+}
+
+fn period_label(i18n: &I18n, period: Period, start: NaiveDate) -> String {
+    let year = start.year().to_string();
+    match period {
+        Period::Month => i18n.t_args("income.period.month_label", &[("month", format!("{:02}", start.month())), ("year", year)]),
+        Period::Quarter => i18n.t_args("income.period.quarter_label", &[("quarter", (start.month0() / 3 + 1).to_string()), ("year", year)]),
+        Period::Year => year,
+    }
+}
+
+// `data:` URL to download `csv` from
+fn csv_href(csv: &str) -> String {
+    let mut href = String::from("data:text/csv;charset=utf-8,");
+    for byte in csv.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.,".contains(&byte) {
+            href.push(byte as char);
+        } else {
+            href.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    href
+}
+
+// Value of `amount` at the asset's close on `date`, or at today's price when
+// the history doesn't reach back that far
+async fn value_on(id: &str, date: NaiveDate, amount: Decimal, price: Decimal) -> Decimal {
+    let days = (Utc::now().date_naive() - date).num_days().max(0) as u32 + 1;
+    let close = match api::get_price_history(id, days).await {
+        Ok(candles) => candles
+            .iter()
+            .find(|candle| candle.timestamp.date_naive() == date)
+            .and_then(|candle| Decimal::from_f64(candle.close)),
+        Err(err) => {
+            log::warn!("Failed to load {} prices for {}: {}", id, date, err);
+            None
+        }
+    };
+    match close.unwrap_or(price).checked_mul(amount) {
+        Some(value) => value.round_dp(2),
+        None => {
+            log::warn!("Value of {} {} on {} is out of range", amount, id, date);
+            Decimal::ZERO
+        }
+    }
+}
+
+// Staking rewards, interest, airdrops and mining income: a form to record
+// them, totals per period and the yield they imply
+#[function_component(IncomeTracker)]
+pub fn income_tracker() -> Html {
+    let (store, dispatch) = use_store();
+    let i18n = use_i18n();
+    let today = Utc::now().date_naive();
+    let draft = use_state(|| Draft {
+        crypto_id: String::new(),
+        kind: IncomeKind::Staking,
+        amount: String::new(),
+        value: String::new(),
+        received_on: today.to_string(),
+        wallet: String::new(),
+    });
+    let period = use_state(Period::default);
+    let error = use_state(|| None::<String>);
+
+    let update = |change: fn(&mut Draft, String)| {
+        let draft = draft.clone();
+        move |value: String| {
+            let mut next = (*draft).clone();
+            change(&mut next, value);
+            draft.set(next);
+        }
+    };
+    let on_asset = {
+        let update = update(|draft, id| draft.crypto_id = id);
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+    let on_kind = {
+        let update = update(|draft, code| {
+            if let Some(kind) = IncomeKind::from_code(&code) {
+                draft.kind = kind;
+            }
+        });
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+    let on_amount = {
+        let update = update(|draft, amount| draft.amount = amount);
+        Callback::from(move |e: InputEvent| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_value = {
+        let update = update(|draft, value| draft.value = value);
+        Callback::from(move |e: InputEvent| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_wallet = {
+        let update = update(|draft, key| draft.wallet = key);
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+    let on_date = {
+        let update = update(|draft, date| draft.received_on = date);
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_period = {
+        let period = period.clone();
+        Callback::from(move |e: Event| {
+            if let Some(selected) = Period::from_code(&e.target_unchecked_into::<HtmlSelectElement>().value()) {
+                period.set(selected);
+            }
+        })
+    };
+
+    let on_submit = {
+        let (draft, error, dispatch) = (draft.clone(), error.clone(), dispatch.clone());
+        let cryptocurrencies = store.cryptocurrencies.clone();
+        let wallets = store.wallets.clone();
+        let i18n = i18n.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let separator = i18n.locale().number_symbols().decimal;
+            let Some(crypto) = cryptocurrencies.iter().find(|crypto| crypto.id == draft.crypto_id) else {
+                error.set(Some(i18n.t("income.error.asset")));
+                return;
+            };
+            let amount = match validate_amount(&draft.amount, AmountMode::Quantity, separator, crypto.price, crypto.decimals) {
+                Ok(amount) => amount,
+                Err(AmountError::Empty | AmountError::Zero) => {
+                    error.set(Some(i18n.t("income.error.amount")));
+                    return;
+                }
+                Err(err) => {
+                    error.set(Some(err.message(&i18n)));
+                    return;
+                }
+            };
+            let received_on = match NaiveDate::parse_from_str(&draft.received_on, "%Y-%m-%d") {
+                Ok(date) if date <= Utc::now().date_naive() => date,
+                _ => {
+                    error.set(Some(i18n.t("income.error.date")));
+                    return;
+                }
+            };
+            let value = match parse_amount(&draft.value, separator) {
+                Err(AmountError::Empty) => None,
+                Ok(value) if value > Decimal::from(MAX_AMOUNT) => {
+                    error.set(Some(AmountError::TooLarge.message(&i18n)));
+                    return;
+                }
+                Ok(value) => Some(value),
+                Err(_) => {
+                    error.set(Some(i18n.t("income.error.value")));
+                    return;
+                }
+            };
+
+            let event = IncomeEvent {
+                id: 0,
+                crypto_id: crypto.id.clone(),
+                kind: draft.kind,
+                amount,
+                value: value.unwrap_or_default(),
+                received_on,
+                wallet: wallets.iter().map(WatchedWallet::key).find(|key| *key == draft.wallet),
+            };
+            match value {
+                Some(_) => dispatch.emit(StoreAction::AddIncome(event)),
+                None => {
+                    let (dispatch, price) = (dispatch.clone(), crypto.price);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let value = value_on(&event.crypto_id, received_on, amount, price).await;
+                        dispatch.emit(StoreAction::AddIncome(IncomeEvent { value, ..event }));
+                    });
+                }
+            }
+            draft.set(Draft {
+                amount: String::new(),
+                value: String::new(),
+                ..(*draft).clone()
+            });
+            error.set(None);
+        })
+    };
+
+    let symbol = |id: &str| {
+        store
+            .cryptocurrencies
+            .iter()
+            .find(|crypto| crypto.id == id)
+            .map_or_else(|| id.to_string(), |crypto| crypto.symbol.clone())
+    };
+    let summaries = summarize(&store.income, *period);
+    let total: Decimal = store.income.iter().map(|event| event.value).sum();
+    let holdings = store.holdings();
+    let yields: Vec<(String, f64)> = holdings
+        .iter()
+        .filter_map(|holding| Some((holding.crypto_id.clone(), estimate_apy(&store.income, &holding.crypto_id, holding.amount())?)))
+        .collect();
+
+    let summary_rows = summaries.iter().map(|summary| {
+        let kinds = summary
+            .by_kind
+            .iter()
+            .map(|(kind, value)| format!("{} {}", i18n.t(&format!("income.kind.{}", kind.code())), i18n.currency(value)))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        html! {
+            <tr key={summary.start.to_string()} class="income-period-row text-right border-b">
+                <td class="income-period-label text-left py-1">{period_label(&i18n, *period, summary.start)}</td>
+                <td class="text-left text-xs text-gray-500">{kinds}</td>
+                <td class="text-gray-500">{summary.events}</td>
+                <td class="income-period-total font-mono">{i18n.currency(&summary.total)}</td>
+            </tr>
+        }
+    });
+    let events = store.income.iter().rev().map(|event| {
+        let on_remove = {
+            let dispatch = dispatch.clone();
+            let id = event.id;
+            Callback::from(move |_| dispatch.emit(StoreAction::RemoveIncome(id)))
+        };
+        html! {
+            <li key={event.id} class="income-event flex items-center justify-between border-b py-2 text-sm">
+                <div class="flex items-center space-x-2">
+                    <span class="text-gray-500">{i18n.day(&event.received_on)}</span>
+                    <span class="exchange-badge">{i18n.t(&format!("income.kind.{}", event.kind.code()))}</span>
+                    <span class="font-mono">{i18n.quantity(&event.amount)}{" "}{symbol(&event.crypto_id)}</span>
+                    if let Some(wallet) = event.wallet.as_ref().and_then(|key| store.wallets.iter().find(|wallet| wallet.key() == *key)) {
+                        <span class="income-wallet-name text-xs text-gray-500">{wallet.display_name()}</span>
+                    }
+                </div>
+                <div class="flex items-center space-x-3">
+                    <span class="income-value font-mono">{i18n.currency(&event.value)}</span>
+                    <button class="income-remove text-xs text-red-600 hover:underline" onclick={on_remove}>
+                        {i18n.t("common.remove")}
+                    </button>
+                </div>
+            </li>
+        }
+    });
+
+    html! {
+        <section class="income mt-8">
+            <h3 class="text-lg font-semibold text-blue-700">{i18n.t("income.title")}</h3>
+            <p class="text-sm text-gray-500 mb-3">{i18n.t("income.description")}</p>
+
+            <form class="income-form flex flex-wrap items-end gap-2" onsubmit={on_submit}>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("income.asset")}</span>
+                    <select class="income-asset border rounded px-2 py-1 text-sm text-gray-800" onchange={on_asset}>
+                        <option value="" selected={draft.crypto_id.is_empty()}>{i18n.t("income.choose_asset")}</option>
+                        {for store.cryptocurrencies.iter().map(|crypto| html! {
+                            <option value={crypto.id.clone()} selected={crypto.id == draft.crypto_id}>{&crypto.name}</option>
+                        })}
+                    </select>
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("income.kind")}</span>
+                    <select class="income-kind border rounded px-2 py-1 text-sm text-gray-800" onchange={on_kind}>
+                        {for IncomeKind::ALL.into_iter().map(|kind| html! {
+                            <option value={kind.code()} selected={kind == draft.kind}>{i18n.t(&format!("income.kind.{}", kind.code()))}</option>
+                        })}
+                    </select>
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("income.amount")}</span>
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        autocomplete="off"
+                        class="income-amount w-28 border rounded px-2 py-1 text-sm text-gray-800"
+                        value={draft.amount.clone()}
+                        oninput={on_amount}
+                    />
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("income.received_on")}</span>
+                    <input
+                        type="date"
+                        max={today.to_string()}
+                        class="income-date border rounded px-2 py-1 text-sm text-gray-800"
+                        value={draft.received_on.clone()}
+                        onchange={on_date}
+                    />
+                </label>
+                if !store.wallets.is_empty() {
+                    <label class="flex flex-col text-xs text-gray-500">
+                        <span>{i18n.t("income.wallet")}</span>
+                        <select class="income-wallet border rounded px-2 py-1 text-sm text-gray-800" onchange={on_wallet}>
+                            <option value="" selected={draft.wallet.is_empty()}>{i18n.t("income.wallet_elsewhere")}</option>
+                            {for store.wallets.iter().map(|wallet| {
+                                let key = wallet.key();
+                                html! {
+                                    <option value={key.clone()} selected={key == draft.wallet}>
+                                        {format!("{} · {}", wallet.chain.name(), wallet.display_name())}
+                                    </option>
+                                }
+                            })}
+                        </select>
+                    </label>
+                }
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("income.value")}</span>
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        autocomplete="off"
+                        class="income-value-input w-28 border rounded px-2 py-1 text-sm text-gray-800"
+                        placeholder={i18n.t("income.value_placeholder")}
+                        value={draft.value.clone()}
+                        oninput={on_value}
+                    />
+                </label>
+                <button type="submit" class="income-add px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition">
+                    {i18n.t("income.add")}
+                </button>
+            </form>
+            if let Some(message) = (*error).clone() {
+                <p class="income-error text-sm text-red-600 mt-2" role="alert">{message}</p>
+            }
+
+            if store.income.is_empty() {
+                <p class="income-empty text-sm text-gray-400 mt-3">{i18n.t("income.empty")}</p>
+            } else {
+                <div class="flex items-center justify-between mt-4">
+                    <p class="text-sm text-gray-600">
+                        {i18n.t("income.total")}{" "}
+                        <span class="income-total font-mono font-semibold text-green-600">{i18n.currency(&total)}</span>
+                    </p>
+                    <select class="income-period border rounded px-2 py-1 text-sm text-gray-800" aria-label={i18n.t("income.period")} onchange={on_period}>
+                        {for Period::ALL.into_iter().map(|option| html! {
+                            <option value={option.code()} selected={option == *period}>{i18n.t(&format!("income.period.{}", option.code()))}</option>
+                        })}
+                    </select>
+                </div>
+                <div class="overflow-x-auto">
+                    <table class="w-full text-sm mt-2">
+                        <thead>
+                            <tr class="text-right text-xs text-gray-500 border-b">
+                                <th class="text-left">{i18n.t("income.period_heading")}</th>
+                                <th class="text-left">{i18n.t("income.kinds")}</th>
+                                <th>{i18n.t("income.events")}</th>
+                                <th>{i18n.t("income.received")}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{for summary_rows}</tbody>
+                    </table>
+                </div>
+                <div class="flex items-center justify-between mt-2">
+                    <p class="income-tax-note text-xs text-gray-500">{i18n.t("income.tax_note")}</p>
+                    <a class="income-export text-sm text-blue-600 hover:underline" download="income-by-year.csv" href={csv_href(&yearly_csv(&store.income))}>
+                        {i18n.t("income.export")}
+                    </a>
+                </div>
+
+                if !yields.is_empty() {
+                    <h4 class="text-sm font-semibold text-gray-700 mt-4 mb-2">{i18n.t("income.apy_title")}</h4>
+                    <ul class="text-sm">
+                        {for yields.iter().map(|(id, apy)| html! {
+                            <li key={id.clone()} class="income-apy-row flex justify-between border-b py-1">
+                                <span class="font-mono font-bold text-blue-700">{symbol(id)}</span>
+                                <span class="income-apy font-mono">{i18n.percent(apy * 100.0)}</span>
+                            </li>
+                        })}
+                    </ul>
+                }
+
+                <ul class="income-events mt-4">{for events}</ul>
+            }
+        </section>
+    }
+}
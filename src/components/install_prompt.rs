@@ -0,0 +1,58 @@
+// src/components/install_prompt.rs
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::Event;
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+// Offers "Install app" once the browser reports the tracker as installable.
+// The `beforeinstallprompt` event is kept so its prompt can be shown from a click.
+#[function_component(InstallButton)]
+pub fn install_button() -> Html {
+    let i18n = use_i18n();
+    let deferred = use_state(|| None::<Event>);
+
+    {
+        let deferred = deferred.clone();
+        use_effect_with((), move |_| {
+            let window = gloo::utils::window();
+            let available = {
+                let deferred = deferred.clone();
+                EventListener::new(&window, "beforeinstallprompt", move |event| {
+                    // Keep the browser from showing its own banner
+                    event.prevent_default();
+                    deferred.set(Some(event.clone()));
+                })
+            };
+            let installed = EventListener::new(&window, "appinstalled", move |_| deferred.set(None));
+            move || drop((available, installed))
+        });
+    }
+
+    let on_install = {
+        let deferred = deferred.clone();
+        Callback::from(move |_| {
+            let Some(event) = (*deferred).clone() else {
+                return;
+            };
+            // A deferred prompt can only be shown once
+            deferred.set(None);
+            let prompt = js_sys::Reflect::get(&event, &"prompt".into())
+                .ok()
+                .and_then(|prompt| prompt.dyn_into::<js_sys::Function>().ok());
+            if let Some(prompt) = prompt {
+                let _ = prompt.call0(&event);
+            }
+        })
+    };
+
+    if deferred.is_none() {
+        return html! {};
+    }
+    html! {
+        <button class="install-button text-sm font-medium text-white bg-blue-600 hover:bg-blue-700 rounded px-3 py-1 transition" onclick={on_install}>
+            {i18n.t("install.button")}
+        </button>
+    }
+}
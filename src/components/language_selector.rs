@@ -0,0 +1,38 @@
+// src/components/language_selector.rs
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::i18n::{use_i18n, use_set_locale, Locale};
+
+#[function_component(LanguageSelector)]
+pub fn language_selector() -> Html {
+    let i18n = use_i18n();
+    let set_locale = use_set_locale();
+
+    let on_change = Callback::from(move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        if let Some(locale) = Locale::from_code(&select.value()) {
+            set_locale.emit(locale);
+        }
+    });
+
+    html! {
+        <label class="language-selector flex items-center space-x-2 text-sm text-gray-600">
+            <span>{i18n.t("language.label")}</span>
+            <select
+                class="border rounded px-2 py-1 focus:outline-none focus:ring-2 focus:ring-blue-400"
+                onchange={on_change}
+            >
+                {Locale::ALL.iter().map(|locale| html! {
+                    <option
+                        key={locale.code()}
+                        value={locale.code()}
+                        selected={*locale == i18n.locale()}
+                    >
+                        {locale.native_name()}
+                    </option>
+                }).collect::<Html>()}
+            </select>
+        </label>
+    }
+}
@@ -1,12 +1,16 @@
 // src/components/loading.rs
 use yew::prelude::*;
 
+use crate::i18n::use_i18n;
+
 #[function_component(Loading)]
 pub fn loading() -> Html {
+    let i18n = use_i18n();
+
     html! {
         <div class="loading-spinner flex flex-col items-center justify-center py-8">
             <div class="spinner w-12 h-12 border-4 border-blue-400 border-t-transparent rounded-full animate-spin mb-4"></div>
-            <p class="text-blue-700 font-semibold">{"Loading..."}</p>
+            <p class="text-blue-700 font-semibold">{i18n.t("common.loading")}</p>
         </div>
     }
 }
\ No newline at end of file
@@ -0,0 +1,134 @@
+// src/components/market_depth.rs
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gloo::timers::callback::Interval;
+use yew::prelude::*;
+
+use crate::components::error::Error;
+use crate::components::loading::Loading;
+use crate::components::order_book::{DepthChart, OrderBookLadder};
+use crate::components::recent_trades::RecentTrades;
+use crate::config::use_config;
+use crate::i18n::use_i18n;
+use crate::models::market::{OrderBook, Trade};
+use crate::services::api;
+
+// Levels a side requested with each snapshot; the depth chart shows them all
+const BOOK_DEPTH: usize = 50;
+const TRADE_LIMIT: usize = 20;
+
+// Brings `book` up to date: applies the updates since its sequence number,
+// or loads a fresh snapshot when there is none yet or updates were missed
+async fn refresh_book(id: &str, book: &RefCell<Option<OrderBook>>) -> anyhow::Result<()> {
+    let since = book.borrow().as_ref().map(|book| book.sequence);
+    if let Some(since) = since {
+        let updates = api::get_book_updates(id, since).await?;
+        let applied = match book.borrow_mut().as_mut() {
+            Some(book) => book.apply(&updates),
+            None => return Ok(()),
+        };
+        match applied {
+            Ok(()) => return Ok(()),
+            Err(gap) => log::warn!("Reloading the {} order book, {}", id, gap),
+        }
+    }
+    let snapshot = api::get_order_book(id, BOOK_DEPTH).await?;
+    *book.borrow_mut() = Some(snapshot);
+    Ok(())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MarketDepthProps {
+    pub id: String,
+}
+
+// Live order book, depth chart and recent trades of an asset
+#[function_component(MarketDepth)]
+pub fn market_depth(props: &MarketDepthProps) -> Html {
+    let i18n = use_i18n();
+    let refresh_secs = use_config().refresh.order_book_secs;
+    let book = use_state(|| None::<OrderBook>);
+    let trades = use_state(Vec::<Trade>::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let (book, trades, error) = (book.clone(), trades.clone(), error.clone());
+        use_effect_with((props.id.clone(), refresh_secs), move |(id, secs)| {
+            book.set(None);
+            trades.set(Vec::new());
+            error.set(None);
+
+            // Working copy the updates are applied to; a new one per asset so
+            // late responses for the previous asset can't touch it
+            let working = Rc::new(RefCell::new(None::<OrderBook>));
+            let current = Rc::new(Cell::new(true));
+            let busy = Rc::new(Cell::new(false));
+            let poll = {
+                let (id, current) = (id.clone(), current.clone());
+                move || {
+                    if busy.replace(true) {
+                        return;
+                    }
+                    let (id, working, current, busy) = (id.clone(), working.clone(), current.clone(), busy.clone());
+                    let (book, trades, error) = (book.clone(), trades.clone(), error.clone());
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let result = match refresh_book(&id, &working).await {
+                            Ok(()) => api::get_trades(&id, TRADE_LIMIT).await,
+                            Err(err) => Err(err),
+                        };
+                        busy.set(false);
+                        if !current.get() {
+                            return;
+                        }
+                        match result {
+                            Ok(latest) => {
+                                book.set(working.borrow().clone());
+                                trades.set(latest);
+                                error.set(None);
+                            }
+                            Err(err) => error.set(Some(err.to_string())),
+                        }
+                    });
+                }
+            };
+
+            poll();
+            let refresh = (*secs > 0).then(|| Interval::new(secs.saturating_mul(1000), poll));
+            move || {
+                current.set(false);
+                drop(refresh);
+            }
+        });
+    }
+
+    let body = match (&*book, &*error) {
+        (None, Some(error)) => html! { <Error message={error.clone()} on_retry={None::<Callback<()>>} /> },
+        (None, None) => html! { <Loading /> },
+        (Some(book), error) => html! {
+            <>
+                if let Some(error) = error {
+                    <p class="market-stale text-xs text-red-600 mb-2">{i18n.t_args("market.stale", &[("error", error.clone())])}</p>
+                }
+                <DepthChart book={book.clone()} />
+                <div class="grid md:grid-cols-2 gap-6 mt-4">
+                    <div>
+                        <h4 class="text-sm font-semibold text-gray-600 mb-1">{i18n.t("market.book")}</h4>
+                        <OrderBookLadder book={book.clone()} />
+                    </div>
+                    <div>
+                        <h4 class="text-sm font-semibold text-gray-600 mb-1">{i18n.t("market.trades")}</h4>
+                        <RecentTrades trades={(*trades).clone()} />
+                    </div>
+                </div>
+            </>
+        },
+    };
+
+    html! {
+        <section class="market-depth mt-4">
+            <h3 class="text-lg font-semibold text-gray-800 mb-2">{i18n.t("market.title")}</h3>
+            {body}
+        </section>
+    }
+}
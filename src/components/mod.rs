@@ -4,3 +4,22 @@ pub mod crypto_card;
 pub mod portfolio_item;
 pub mod loading;
 pub mod error;
+pub mod language_selector;
+pub mod amount_input;
+pub mod undo_toast;
+pub mod sync_status;
+pub mod require_auth;
+pub mod offline_banner;
+pub mod install_prompt;
+pub mod recently_viewed;
+pub mod asset_search;
+pub mod price_chart;
+pub mod order_book;
+pub mod recent_trades;
+pub mod market_depth;
+pub mod exchange_prices;
+pub mod wallets;
+pub mod rebalance;
+pub mod correlation_heatmap;
+pub mod risk;
+pub mod income;
@@ -0,0 +1,45 @@
+// src/components/offline_banner.rs
+use gloo::events::EventListener;
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+// Tracks the browser's connectivity through its online and offline events
+#[hook]
+pub fn use_online() -> bool {
+    // Assumed online until mounted, matching server-rendered pages
+    let online = use_state(|| true);
+
+    {
+        let online = online.clone();
+        use_effect_with((), move |_| {
+            let window = gloo::utils::window();
+            online.set(window.navigator().on_line());
+            let went_online = {
+                let online = online.clone();
+                EventListener::new(&window, "online", move |_| online.set(true))
+            };
+            let went_offline = EventListener::new(&window, "offline", move |_| online.set(false));
+            move || drop((went_online, went_offline))
+        });
+    }
+
+    *online
+}
+
+// Shown under the header while offline; the service worker then answers
+// market requests with the last data it saw
+#[function_component(OfflineBanner)]
+pub fn offline_banner() -> Html {
+    let online = use_online();
+    let i18n = use_i18n();
+
+    if online {
+        return html! {};
+    }
+    html! {
+        <div role="status" class="offline-banner bg-yellow-100 text-yellow-800 text-sm text-center px-4 py-2">
+            {i18n.t("offline.banner")}
+        </div>
+    }
+}
@@ -0,0 +1,149 @@
+// src/components/order_book.rs
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::models::market::{cumulative, BookLevel, OrderBook};
+
+// Levels shown on each side of the ladder
+pub const LADDER_LEVELS: usize = 10;
+
+// SVG user units of the depth chart
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 160.0;
+
+#[derive(Properties, PartialEq)]
+pub struct OrderBookProps {
+    pub book: OrderBook,
+}
+
+// Asks above bids, best prices meeting at the spread, each row shaded by the
+// running total up to it
+#[function_component(OrderBookLadder)]
+pub fn order_book_ladder(props: &OrderBookProps) -> Html {
+    let i18n = use_i18n();
+    let book = &props.book;
+    let bids = &book.bids[..book.bids.len().min(LADDER_LEVELS)];
+    let asks = &book.asks[..book.asks.len().min(LADDER_LEVELS)];
+    let bid_totals = cumulative(bids);
+    let ask_totals = cumulative(asks);
+    let largest = bid_totals
+        .last()
+        .into_iter()
+        .chain(ask_totals.last())
+        .map(|level| level.amount)
+        .max()
+        .unwrap_or_default();
+
+    let row = |level: &BookLevel, total: &BookLevel, class: &'static str| {
+        let share = if largest.is_zero() {
+            0.0
+        } else {
+            (total.amount / largest * Decimal::ONE_HUNDRED).to_f64().unwrap_or_default()
+        };
+        html! {
+            <div
+                key={level.price.to_string()}
+                class={classes!("book-row", class, "grid", "grid-cols-3", "text-right", "font-mono", "text-xs", "px-2", "py-0.5")}
+                style={format!("background-size: {:.1}% 100%", share)}
+            >
+                <span class="book-price">{i18n.price(&level.price)}</span>
+                <span>{i18n.quantity(&level.amount)}</span>
+                <span class="text-gray-500">{i18n.quantity(&total.amount)}</span>
+            </div>
+        }
+    };
+
+    let spread = match (book.spread(), book.mid_price(), book.spread_percent()) {
+        (Some(spread), Some(mid), Some(percent)) => html! {
+            <div class="book-spread flex justify-between px-2 py-1 my-1 text-xs bg-gray-50 border-y">
+                <span>{i18n.t("market.spread")}{": "}{i18n.price(&spread)}{" ("}{i18n.percent(percent.to_f64().unwrap_or_default())}{")"}</span>
+                <span>{i18n.t("market.mid")}{": "}{i18n.price(&mid)}</span>
+            </div>
+        },
+        _ => html! { <div class="book-spread border-y my-1"></div> },
+    };
+
+    html! {
+        <div class="order-book">
+            <div class="grid grid-cols-3 text-right text-xs font-semibold text-gray-500 px-2 pb-1">
+                <span>{i18n.t("market.price")}</span>
+                <span>{i18n.t("market.amount")}</span>
+                <span>{i18n.t("market.total")}</span>
+            </div>
+            {for asks.iter().zip(&ask_totals).rev().map(|(level, total)| row(level, total, "book-ask"))}
+            {spread}
+            {for bids.iter().zip(&bid_totals).map(|(level, total)| row(level, total, "book-bid"))}
+        </div>
+    }
+}
+
+// Closed, stepped outline of one side's running totals, from its best price
+// outwards, for filling as an area
+fn depth_path(totals: &[BookLevel], x: impl Fn(f64) -> f64, y: impl Fn(f64) -> f64) -> String {
+    let Some(first) = totals.first() else {
+        return String::new();
+    };
+    let value = |decimal: Decimal| decimal.to_f64().unwrap_or_default();
+
+    let mut path = format!("M{:.1},{:.1}", x(value(first.price)), y(0.0));
+    let mut previous = None;
+    for level in totals {
+        let at = x(value(level.price));
+        if let Some(previous) = previous {
+            path.push_str(&format!(" L{:.1},{:.1}", at, y(previous)));
+        }
+        path.push_str(&format!(" L{:.1},{:.1}", at, y(value(level.amount))));
+        previous = Some(value(level.amount));
+    }
+    let last = x(value(totals[totals.len() - 1].price));
+    path.push_str(&format!(" L{:.1},{:.1} Z", last, y(0.0)));
+    path
+}
+
+// Cumulative bids and asks either side of the mid price
+#[function_component(DepthChart)]
+pub fn depth_chart(props: &OrderBookProps) -> Html {
+    let bids = props.book.cumulative_bids();
+    let asks = props.book.cumulative_asks();
+    let (Some(low), Some(high)) = (bids.last(), asks.last()) else {
+        return html! {};
+    };
+
+    let low = low.price.to_f64().unwrap_or_default();
+    let high = high.price.to_f64().unwrap_or_default();
+    let deepest = bids
+        .last()
+        .into_iter()
+        .chain(asks.last())
+        .filter_map(|level| level.amount.to_f64())
+        .fold(0.0, f64::max);
+    let x = |price: f64| if high > low { (price - low) / (high - low) * WIDTH } else { WIDTH / 2.0 };
+    let y = |amount: f64| if deepest > 0.0 { HEIGHT - amount / deepest * HEIGHT } else { HEIGHT };
+
+    html! {
+        <svg class="depth-chart w-full h-40" viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} preserveAspectRatio="none">
+            <path class="depth-bids" d={depth_path(&bids, x, y)} />
+            <path class="depth-asks" d={depth_path(&asks, x, y)} />
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn depth_path_steps_out_from_the_best_price() {
+        let totals = [
+            BookLevel { price: dec!(10), amount: dec!(1) },
+            BookLevel { price: dec!(20), amount: dec!(3) },
+        ];
+        let path = depth_path(&totals, |price| price, |amount| 10.0 - amount);
+
+        assert_eq!(path, "M10.0,10.0 L10.0,9.0 L20.0,9.0 L20.0,7.0 L20.0,10.0 Z");
+        assert_eq!(depth_path(&[], |price| price, |amount| amount), "");
+    }
+}
@@ -1,10 +1,14 @@
 // src/components/portfolio_item.rs
 use yew::prelude::*;
 use yew_router::prelude::*;
-use web_sys::HtmlInputElement;
+use rust_decimal::Decimal;
 
 use crate::app::Route;
-use crate::models::crypto::{self, Cryptocurrency, PortfolioItem as PortfolioItemModel};
+use crate::components::amount_input::AmountInput;
+use crate::components::wallets::holding_sources;
+use crate::i18n::use_i18n;
+use crate::models::crypto::{Cryptocurrency, PortfolioItem as PortfolioItemModel};
+use crate::store::HoldingSource;
 
 #[derive(Properties, PartialEq)]
 pub struct PortfolioItemProps {
@@ -12,21 +16,22 @@ pub struct PortfolioItemProps {
     pub cryptocurrency: Option<Cryptocurrency>,
     pub on_update: Callback<PortfolioItemModel>,
     pub on_remove: Callback<String>,
+    // Where the holding comes from, when watched wallets add to the typed-in
+    // amount; the amount and value shown are then the total
+    #[prop_or_default]
+    pub sources: Vec<(HoldingSource, Decimal)>,
 }
 
 #[function_component(PortfolioItem)]
 pub fn portfolio_item(props: &PortfolioItemProps) -> Html {
     let navigator = use_navigator().unwrap();
-    let amount = use_state(|| props.item.amount);
+    let i18n = use_i18n();
+    // Latest valid quantity from the amount input, `None` while it is invalid
+    let amount = use_state(|| Some(props.item.amount));
     
     let on_amount_change = {
         let amount = amount.clone();
-        Callback::from(move |e: Event| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            if let Ok(value) = input.value().parse::<f64>() {
-                amount.set(value);
-            }
-        })
+        Callback::from(move |value: Option<Decimal>| amount.set(value))
     };
     
     let on_update_click = {
@@ -34,10 +39,12 @@ pub fn portfolio_item(props: &PortfolioItemProps) -> Html {
         let _on_update = props.on_update.clone();
         let amount = *amount;
         Callback::from(move |_| {
-            _on_update.emit(PortfolioItemModel {
-                crypto_id: crypto_id.clone(),
-                amount,
-            });
+            if let Some(amount) = amount {
+                _on_update.emit(PortfolioItemModel {
+                    crypto_id: crypto_id.clone(),
+                    amount,
+                });
+            }
         })
     };
     
@@ -58,7 +65,13 @@ pub fn portfolio_item(props: &PortfolioItemProps) -> Html {
     };
     
     if let Some(crypto) = &props.cryptocurrency {
-        let total_value = props.item.amount * crypto.price;
+        let merged = props.sources.iter().any(|(source, _)| *source != HoldingSource::Manual);
+        let held = if merged {
+            props.sources.iter().map(|(_, amount)| *amount).sum()
+        } else {
+            props.item.amount
+        };
+        let total_value = crypto.value_of(held);
         
         html! {
             <div class="portfolio-item flex items-center justify-between bg-blue-50 rounded p-4 shadow-sm">
@@ -68,31 +81,34 @@ pub fn portfolio_item(props: &PortfolioItemProps) -> Html {
                         <span class="name text-gray-700">{&crypto.name}</span>
                     </div>
                     <div class="holdings flex items-center space-x-4 mt-2">
-                        <span class="amount text-gray-800">{format!("{:.6}", props.item.amount)}</span>
-                        <span class="value font-semibold text-green-600">{format!("${:.2}", total_value)}</span>
+                        <span class="amount text-gray-800">{i18n.quantity(&held)}</span>
+                        <span class="value font-semibold text-green-600">{i18n.currency(&total_value)}</span>
                     </div>
+                    if merged {
+                        {holding_sources(&i18n, &props.sources)}
+                    }
                 </div>
                 
-                <div class="item-actions flex items-center space-x-2 ml-4">
-                    <input
-                        type="number"
-                        step="0.000001"
-                        min="0"
-                        value={amount.to_string()}
-                        onchange={on_amount_change}
-                        class="border rounded px-2 py-1 w-24 focus:outline-none focus:ring-2 focus:ring-blue-400"
+                <div class="item-actions flex items-start space-x-2 ml-4">
+                    <AmountInput
+                        initial={props.item.amount}
+                        price={crypto.price}
+                        symbol={crypto.symbol.clone()}
+                        decimals={crypto.decimals}
+                        on_change={on_amount_change}
                     />
                     <button
-                        class="px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition"
+                        class="px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition disabled:opacity-50"
+                        disabled={amount.is_none()}
                         onclick={on_update_click}
                     >
-                        {"Update"}
+                        {i18n.t("common.update")}
                     </button>
                     <button
                         class="remove px-3 py-1 bg-red-500 text-white rounded hover:bg-red-600 transition"
                         onclick={on_remove_click}
                     >
-                        {"Remove"}
+                        {i18n.t("common.remove")}
                     </button>
                 </div>
             </div>
@@ -102,7 +118,7 @@ pub fn portfolio_item(props: &PortfolioItemProps) -> Html {
             <div class="portfolio-item loading flex items-center justify-between bg-gray-100 rounded p-4 animate-pulse">
                 <div class="item-info">
                     <div class="crypto-name">
-                        <span class="symbol text-gray-400">{"Loading..."}</span>
+                        <span class="symbol text-gray-400">{i18n.t("common.loading")}</span>
                     </div>
                 </div>
             </div>
@@ -0,0 +1,412 @@
+// src/components/price_chart.rs
+use std::cell::Cell;
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::components::error::Error;
+use crate::components::loading::Loading;
+use crate::i18n::use_i18n;
+use crate::indicators::{self, use_indicator_settings, Indicator, IndicatorSettings, Series};
+use crate::models::history::Candle;
+use crate::services::api;
+
+// Ranges offered above the chart, in days
+const RANGES: [u32; 3] = [30, 90, 180];
+const DEFAULT_RANGE: u32 = 90;
+
+// SVG user units; the chart scales to the width of its container
+pub(crate) const WIDTH: f64 = 600.0;
+const PRICE_HEIGHT: f64 = 220.0;
+const PANE_HEIGHT: f64 = 90.0;
+
+// Maps bar indexes and values onto one pane of the chart
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Frame {
+    count: usize,
+    min: f64,
+    max: f64,
+    height: f64,
+}
+
+impl Frame {
+    // Fits every value with a little headroom; a flat series sits mid-pane
+    pub(crate) fn fit(count: usize, values: impl IntoIterator<Item = f64>, height: f64) -> Self {
+        let (min, max) = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+        let (min, max) = match (min, max) {
+            (min, max) if min > max => (0.0, 1.0),
+            (min, max) if min == max => (min - 1.0, max + 1.0),
+            (min, max) => {
+                let padding = (max - min) * 0.05;
+                (min - padding, max + padding)
+            }
+        };
+        Self::fixed(count, min, max, height)
+    }
+
+    fn fixed(count: usize, min: f64, max: f64, height: f64) -> Self {
+        Self { count, min, max, height }
+    }
+
+    fn x(&self, index: usize) -> f64 {
+        if self.count <= 1 {
+            WIDTH / 2.0
+        } else {
+            index as f64 * WIDTH / (self.count - 1) as f64
+        }
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        self.height - (value - self.min) / (self.max - self.min) * self.height
+    }
+}
+
+// SVG path through the defined points, broken wherever the series has gaps
+fn line_path(series: &[Option<f64>], frame: &Frame) -> String {
+    let mut path = String::new();
+    let mut drawing = false;
+    for (index, value) in series.iter().enumerate() {
+        match value {
+            Some(value) => {
+                let command = if drawing { 'L' } else { 'M' };
+                if !path.is_empty() {
+                    path.push(' ');
+                }
+                path.push_str(&format!("{}{:.1},{:.1}", command, frame.x(index), frame.y(*value)));
+                drawing = true;
+            }
+            None => drawing = false,
+        }
+    }
+    path
+}
+
+// Every value the given series define, for fitting a frame around them
+fn defined<'a>(series: impl IntoIterator<Item = &'a Series>) -> Vec<f64> {
+    series.into_iter().flat_map(|series| series.iter().flatten().copied()).collect()
+}
+
+pub(crate) fn line(series: &[Option<f64>], frame: &Frame, class: &'static str) -> Html {
+    html! { <path class={classes!("chart-line", class)} d={line_path(series, frame)} fill="none" vector-effect="non-scaling-stroke" /> }
+}
+
+fn horizontal(value: f64, frame: &Frame) -> Html {
+    let y = format!("{:.1}", frame.y(value));
+    html! { <line class="chart-guide" x1="0" x2={WIDTH.to_string()} y1={y.clone()} y2={y} vector-effect="non-scaling-stroke" /> }
+}
+
+// One input of an indicator's settings
+struct Parameter {
+    key: &'static str,
+    step: f64,
+    get: fn(&IndicatorSettings) -> f64,
+    set: fn(&mut IndicatorSettings, f64),
+}
+
+fn parameters(indicator: Indicator) -> Vec<Parameter> {
+    let period = |get: fn(&IndicatorSettings) -> f64, set: fn(&mut IndicatorSettings, f64)| Parameter {
+        key: "chart.param.period",
+        step: 1.0,
+        get,
+        set,
+    };
+    match indicator {
+        Indicator::Sma => vec![period(|s| s.sma_period as f64, |s, v| s.sma_period = v as usize)],
+        Indicator::Ema => vec![period(|s| s.ema_period as f64, |s, v| s.ema_period = v as usize)],
+        Indicator::Bollinger => vec![
+            period(|s| s.bollinger_period as f64, |s, v| s.bollinger_period = v as usize),
+            Parameter {
+                key: "chart.param.width",
+                step: 0.5,
+                get: |s| s.bollinger_width,
+                set: |s, v| s.bollinger_width = v,
+            },
+        ],
+        Indicator::Vwap => Vec::new(),
+        Indicator::Rsi => vec![period(|s| s.rsi_period as f64, |s, v| s.rsi_period = v as usize)],
+        Indicator::Macd => vec![
+            Parameter {
+                key: "chart.param.fast",
+                step: 1.0,
+                get: |s| s.macd_fast as f64,
+                set: |s, v| s.macd_fast = v as usize,
+            },
+            Parameter {
+                key: "chart.param.slow",
+                step: 1.0,
+                get: |s| s.macd_slow as f64,
+                set: |s, v| s.macd_slow = v as usize,
+            },
+            Parameter {
+                key: "chart.param.signal",
+                step: 1.0,
+                get: |s| s.macd_signal as f64,
+                set: |s, v| s.macd_signal = v as usize,
+            },
+        ],
+        Indicator::Atr => vec![period(|s| s.atr_period as f64, |s, v| s.atr_period = v as usize)],
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PriceChartProps {
+    pub id: String,
+}
+
+// Daily closes of an asset with the indicators chosen by the user, overlays
+// drawn over the price and oscillators in panes below it
+#[function_component(PriceChart)]
+pub fn price_chart(props: &PriceChartProps) -> Html {
+    let i18n = use_i18n();
+    let (settings, set_settings) = use_indicator_settings();
+    let days = use_state(|| DEFAULT_RANGE);
+    let history = use_state(|| None::<Result<Vec<Candle>, String>>);
+    // Bumped to fetch again after a failure
+    let attempt = use_state(|| 0_u32);
+
+    {
+        let history = history.clone();
+        use_effect_with((props.id.clone(), *days, *attempt), move |(id, days, _)| {
+            // Drops responses for an asset or range no longer shown
+            let current = Rc::new(Cell::new(true));
+            {
+                let current = current.clone();
+                let (id, days) = (id.clone(), *days);
+                history.set(None);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = api::get_price_history(&id, days).await.map_err(|err| err.to_string());
+                    if current.get() {
+                        history.set(Some(result));
+                    }
+                });
+            }
+            move || current.set(false)
+        });
+    }
+
+    let ranges = RANGES.iter().map(|range| {
+        let on_click = {
+            let days = days.clone();
+            let range = *range;
+            Callback::from(move |_| days.set(range))
+        };
+        let class = if *range == *days {
+            "chart-range px-2 py-1 rounded bg-blue-600 text-white"
+        } else {
+            "chart-range px-2 py-1 rounded text-gray-600 hover:bg-blue-50"
+        };
+        html! {
+            <button key={*range} class={class} onclick={on_click}>{i18n.plural("chart.days", f64::from(*range))}</button>
+        }
+    });
+
+    let controls = Indicator::ALL.iter().map(|indicator| {
+        let indicator = *indicator;
+        let enabled = settings.is_enabled(indicator);
+        let on_toggle = {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            Callback::from(move |_| set_settings.emit(settings.clone().toggled(indicator)))
+        };
+        let inputs = enabled.then(|| {
+            parameters(indicator)
+                .into_iter()
+                .map(|Parameter { key, step, get, set }| {
+                    let on_change = {
+                        let settings = settings.clone();
+                        let set_settings = set_settings.clone();
+                        Callback::from(move |e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            if let Ok(value) = input.value().trim().parse::<f64>() {
+                                let mut next = settings.clone();
+                                set(&mut next, value);
+                                set_settings.emit(next);
+                            }
+                        })
+                    };
+                    html! {
+                        <label class="flex items-center space-x-1 text-xs text-gray-500">
+                            <span>{i18n.t(key)}</span>
+                            <input
+                                type="number"
+                                class="chart-parameter w-14 border rounded px-1"
+                                step={step.to_string()}
+                                value={get(&settings).to_string()}
+                                onchange={on_change}
+                            />
+                        </label>
+                    }
+                })
+                .collect::<Html>()
+        });
+        html! {
+            <div key={indicator.code()} class="flex items-center space-x-2">
+                <label class="flex items-center space-x-1 text-sm text-gray-700">
+                    <input
+                        type="checkbox"
+                        class={format!("chart-toggle chart-toggle-{}", indicator.code())}
+                        checked={enabled}
+                        onchange={on_toggle}
+                    />
+                    <span>{i18n.t(&indicator.label_key())}</span>
+                </label>
+                {inputs}
+            </div>
+        }
+    });
+
+    let on_retry = Callback::from(move |_| attempt.set(*attempt + 1));
+
+    let body = match &*history {
+        None => html! { <Loading /> },
+        Some(Err(error)) => html! { <Error message={error.clone()} on_retry={Some(on_retry)} /> },
+        Some(Ok(candles)) if candles.is_empty() => html! { <p class="text-sm text-gray-500">{i18n.t("chart.empty")}</p> },
+        Some(Ok(candles)) => render_chart(candles, &settings, &i18n),
+    };
+
+    html! {
+        <section class="price-chart mt-4">
+            <div class="flex items-baseline justify-between mb-2">
+                <h3 class="text-lg font-semibold text-gray-800">{i18n.t("chart.title")}</h3>
+                <div class="flex space-x-1 text-sm">{for ranges}</div>
+            </div>
+            {body}
+            <fieldset class="chart-indicators mt-4 flex flex-wrap gap-x-6 gap-y-2">
+                <legend class="text-sm font-semibold text-gray-600 mb-1">{i18n.t("chart.indicators")}</legend>
+                {for controls}
+            </fieldset>
+        </section>
+    }
+}
+
+fn render_chart(candles: &[Candle], settings: &IndicatorSettings, i18n: &crate::i18n::I18n) -> Html {
+    let count = candles.len();
+    let closes = indicators::closes(candles);
+    let close_series: Series = closes.iter().copied().map(Some).collect();
+
+    // Overlays share the price pane, so they take part in its scale
+    let mut overlays: Vec<(Series, &'static str)> = Vec::new();
+    for indicator in &settings.enabled {
+        match indicator {
+            Indicator::Sma => overlays.push((indicators::sma(&closes, settings.sma_period), "chart-sma")),
+            Indicator::Ema => overlays.push((indicators::ema(&closes, settings.ema_period), "chart-ema")),
+            Indicator::Bollinger => {
+                let bands = indicators::bollinger(&closes, settings.bollinger_period, settings.bollinger_width);
+                overlays.push((bands.upper, "chart-bollinger"));
+                overlays.push((bands.middle, "chart-bollinger-middle"));
+                overlays.push((bands.lower, "chart-bollinger"));
+            }
+            Indicator::Vwap => overlays.push((indicators::vwap(candles), "chart-vwap")),
+            _ => {}
+        }
+    }
+    let frame = Frame::fit(
+        count,
+        defined(std::iter::once(&close_series).chain(overlays.iter().map(|(series, _)| series))),
+        PRICE_HEIGHT,
+    );
+
+    let panes = settings
+        .enabled
+        .iter()
+        .filter(|indicator| !indicator.is_overlay())
+        .map(|indicator| {
+            let content = match indicator {
+                Indicator::Rsi => {
+                    let rsi = indicators::rsi(&closes, settings.rsi_period);
+                    let frame = Frame::fixed(count, 0.0, 100.0, PANE_HEIGHT);
+                    html! { <>{horizontal(30.0, &frame)}{horizontal(70.0, &frame)}{line(&rsi, &frame, "chart-rsi")}</> }
+                }
+                Indicator::Macd => {
+                    let macd = indicators::macd(&closes, settings.macd_fast, settings.macd_slow, settings.macd_signal);
+                    let frame = Frame::fit(
+                        count,
+                        defined([&macd.line, &macd.signal, &macd.histogram]).into_iter().chain([0.0]),
+                        PANE_HEIGHT,
+                    );
+                    let zero = frame.y(0.0);
+                    let bar_width = (WIDTH / count as f64 * 0.6).max(0.5);
+                    let bars = macd.histogram.iter().enumerate().filter_map(|(index, value)| {
+                        let y = frame.y((*value)?);
+                        Some(html! {
+                            <rect
+                                class={if y < zero { "chart-histogram-up" } else { "chart-histogram-down" }}
+                                x={format!("{:.1}", frame.x(index) - bar_width / 2.0)}
+                                y={format!("{:.1}", y.min(zero))}
+                                width={format!("{:.1}", bar_width)}
+                                height={format!("{:.1}", (y - zero).abs())}
+                            />
+                        })
+                    });
+                    html! {
+                        <>
+                            {horizontal(0.0, &frame)}
+                            {for bars}
+                            {line(&macd.line, &frame, "chart-macd")}
+                            {line(&macd.signal, &frame, "chart-macd-signal")}
+                        </>
+                    }
+                }
+                Indicator::Atr => {
+                    let atr = indicators::atr(candles, settings.atr_period);
+                    let frame = Frame::fit(count, defined([&atr]), PANE_HEIGHT);
+                    line(&atr, &frame, "chart-atr")
+                }
+                _ => html! {},
+            };
+            html! {
+                <div key={indicator.code()} class={format!("chart-pane chart-pane-{} mt-2", indicator.code())}>
+                    <span class="text-xs text-gray-500">{i18n.t(&indicator.label_key())}</span>
+                    <svg class="w-full h-20" viewBox={format!("0 0 {} {}", WIDTH, PANE_HEIGHT)} preserveAspectRatio="none">
+                        {content}
+                    </svg>
+                </div>
+            }
+        });
+
+    let first = &candles[0];
+    let last = &candles[count - 1];
+
+    html! {
+        <div class="chart">
+            <svg class="chart-price w-full h-56" viewBox={format!("0 0 {} {}", WIDTH, PRICE_HEIGHT)} preserveAspectRatio="none">
+                {for overlays.iter().map(|(series, class)| line(series, &frame, class))}
+                {line(&close_series, &frame, "chart-close")}
+            </svg>
+            <div class="flex justify-between text-xs text-gray-500">
+                <span>{i18n.date(&first.timestamp)}</span>
+                <span>{i18n.date(&last.timestamp)}</span>
+            </div>
+            {for panes}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_path_breaks_at_gaps() {
+        let frame = Frame::fixed(5, 0.0, 10.0, 100.0);
+        let series = [None, Some(0.0), Some(10.0), None, Some(5.0)];
+
+        assert_eq!(line_path(&series, &frame), "M150.0,100.0 L300.0,0.0 M600.0,50.0");
+        assert_eq!(line_path(&[None, None], &frame), "");
+    }
+
+    #[test]
+    fn frame_pads_the_range_and_centers_flat_series() {
+        let frame = Frame::fit(2, [10.0, 20.0, f64::NAN], 100.0);
+        assert_eq!((frame.min, frame.max), (9.5, 20.5));
+
+        let flat = Frame::fit(1, [3.0], 100.0);
+        assert_eq!(flat.y(3.0), 50.0);
+        assert_eq!(flat.x(0), WIDTH / 2.0);
+        assert_eq!(Frame::fit(3, [], 100.0).y(0.0), 100.0);
+    }
+}
@@ -0,0 +1,262 @@
+// src/components/rebalance.rs
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::components::amount_input::{parse_amount, to_input_text, AmountError};
+use crate::i18n::{use_i18n, I18n};
+use crate::models::rebalance::{plan, Allocation, RebalanceError, RebalanceSettings};
+use crate::store::use_store;
+use crate::utils::settings::use_stored_settings;
+
+const SETTINGS_STORAGE_KEY: &str = "crypto_tracker.rebalance";
+
+struct Setting {
+    key: &'static str,
+    get: fn(&RebalanceSettings) -> Decimal,
+    set: fn(&mut RebalanceSettings, Decimal),
+}
+
+const SETTINGS: [Setting; 3] = [
+    Setting {
+        key: "rebalance.tolerance",
+        get: |settings| settings.tolerance_percent,
+        set: |settings, value| settings.tolerance_percent = value,
+    },
+    Setting {
+        key: "rebalance.min_trade",
+        get: |settings| settings.min_trade,
+        set: |settings, value| settings.min_trade = value,
+    },
+    Setting {
+        key: "rebalance.new_cash",
+        get: |settings| settings.new_cash,
+        set: |settings, value| settings.new_cash = value,
+    },
+];
+
+fn percent(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+// Target allocation per asset and the trades that reach it from the current
+// holdings, at current prices
+#[function_component(Rebalancer)]
+pub fn rebalancer() -> Html {
+    let (store, _) = use_store();
+    let i18n = use_i18n();
+    let separator = i18n.locale().number_symbols().decimal;
+    let (settings, set_settings) = use_stored_settings(SETTINGS_STORAGE_KEY, RebalanceSettings::normalized);
+    // Assets picked from the list that don't have a target yet
+    let added = use_state(Vec::<String>::new);
+
+    let amounts: Vec<(String, Decimal)> = store
+        .holdings()
+        .iter()
+        .map(|holding| (holding.crypto_id.clone(), holding.amount()))
+        .collect();
+    let held = |id: &str| amounts.iter().filter(|(held, _)| held == id).map(|(_, amount)| *amount).sum::<Decimal>();
+    let listed: Vec<_> = store
+        .cryptocurrencies
+        .iter()
+        .filter(|crypto| !held(&crypto.id).is_zero() || settings.targets.contains_key(&crypto.id) || added.contains(&crypto.id))
+        .collect();
+    let held_total: Decimal = listed.iter().map(|crypto| crypto.value_of(held(&crypto.id))).sum();
+    let result = plan(&store.cryptocurrencies, &amounts, &settings);
+
+    let inputs = SETTINGS.iter().map(|Setting { key, get, set }| {
+        let on_change = {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            let set = *set;
+            Callback::from(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                if let Ok(value) = parse_amount(&input.value(), separator) {
+                    let mut next = settings.clone();
+                    set(&mut next, value);
+                    set_settings.emit(next);
+                }
+            })
+        };
+        html! {
+            <label class="flex flex-col text-xs text-gray-500">
+                <span>{i18n.t(key)}</span>
+                <input
+                    type="text"
+                    inputmode="decimal"
+                    autocomplete="off"
+                    class="rebalance-setting w-28 border rounded px-2 py-1 text-sm text-gray-800"
+                    value={to_input_text(get(&settings), separator)}
+                    onchange={on_change}
+                />
+            </label>
+        }
+    });
+    let on_cash_only = {
+        let settings = settings.clone();
+        let set_settings = set_settings.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            set_settings.emit(RebalanceSettings {
+                cash_only: input.checked(),
+                ..settings.clone()
+            });
+        })
+    };
+    let on_add = {
+        let added = added.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let id = select.value();
+            if !id.is_empty() && !added.contains(&id) {
+                let mut next = (*added).clone();
+                next.push(id);
+                added.set(next);
+            }
+            select.set_value("");
+        })
+    };
+
+    let rows = listed.iter().map(|crypto| {
+        let on_target = {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            let id = crypto.id.clone();
+            Callback::from(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                let target = match parse_amount(&input.value(), separator) {
+                    Err(AmountError::Empty) => Ok(Decimal::ZERO),
+                    parsed => parsed,
+                };
+                if let Ok(target) = target {
+                    let mut next = settings.clone();
+                    next.targets.insert(id.clone(), target);
+                    set_settings.emit(next);
+                }
+            })
+        };
+        let allocation = result
+            .as_ref()
+            .ok()
+            .and_then(|plan| plan.allocations.iter().find(|allocation| allocation.crypto.id == crypto.id));
+        let before = match allocation {
+            Some(allocation) => allocation.before_percent,
+            None if held_total.is_zero() => Decimal::ZERO,
+            None => crypto.value_of(held(&crypto.id)) / held_total * Decimal::ONE_HUNDRED,
+        };
+        let target = settings.targets.get(&crypto.id).map(|target| to_input_text(*target, separator)).unwrap_or_default();
+
+        html! {
+            <tr key={crypto.id.clone()} class="rebalance-row text-right border-b">
+                <td class="text-left py-1">
+                    <span class="font-mono font-bold text-blue-700">{&crypto.symbol}</span>{" "}
+                    <span class="text-gray-500">{&crypto.name}</span>
+                </td>
+                <td class="rebalance-before font-mono">{i18n.percent(percent(before))}</td>
+                <td>
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        autocomplete="off"
+                        class="rebalance-target w-20 border rounded px-2 py-1 text-sm text-right"
+                        aria-label={i18n.t_args("rebalance.target_for", &[("asset", crypto.name.clone())])}
+                        placeholder="0"
+                        value={target}
+                        onchange={on_target}
+                    />
+                </td>
+                <td class="rebalance-after font-mono">
+                    {allocation.map(|allocation| i18n.percent(percent(allocation.after_percent))).unwrap_or_else(|| "–".to_string())}
+                </td>
+                <td class="rebalance-trade">{trade(&i18n, allocation)}</td>
+            </tr>
+        }
+    });
+    let unlisted = store.cryptocurrencies.iter().filter(|crypto| !listed.iter().any(|listed| listed.id == crypto.id));
+
+    let summary = match &result {
+        Ok(plan) => html! {
+            <p class="rebalance-summary text-sm text-gray-600 mt-3">
+                if plan.trades().next().is_none() {
+                    <span class="rebalance-balanced">{i18n.t("rebalance.balanced")}</span>{" "}
+                }
+                if !settings.new_cash.is_zero() || !plan.cash_left.is_zero() {
+                    <span class="rebalance-cash-left">
+                        {i18n.t_args("rebalance.cash_left", &[("amount", i18n.currency(&plan.cash_left))])}
+                    </span>
+                }
+            </p>
+        },
+        Err(RebalanceError::Targets(total)) => html! {
+            <p class="rebalance-error text-sm text-red-600 mt-3" role="alert">
+                {i18n.t_args("rebalance.error.targets", &[("total", i18n.percent(percent(*total)))])}
+            </p>
+        },
+        Err(RebalanceError::Empty) => html! {
+            <p class="rebalance-error text-sm text-gray-500 mt-3">{i18n.t("rebalance.error.empty")}</p>
+        },
+    };
+
+    html! {
+        <section class="rebalance mt-8">
+            <h3 class="text-lg font-semibold text-blue-700">{i18n.t("rebalance.title")}</h3>
+            <p class="text-sm text-gray-500 mb-3">{i18n.t("rebalance.description")}</p>
+            <div class="flex flex-wrap items-end gap-4 mb-4">
+                {for inputs}
+                <label class="flex items-center space-x-2 text-sm text-gray-600">
+                    <input type="checkbox" class="rebalance-cash-only" checked={settings.cash_only} onchange={on_cash_only} />
+                    <span>{i18n.t("rebalance.cash_only")}</span>
+                </label>
+            </div>
+            <div class="overflow-x-auto">
+                <table class="w-full text-sm">
+                    <thead>
+                        <tr class="text-right text-xs text-gray-500 border-b">
+                            <th class="text-left">{i18n.t("rebalance.asset")}</th>
+                            <th>{i18n.t("rebalance.current")}</th>
+                            <th>{i18n.t("rebalance.target")}</th>
+                            <th>{i18n.t("rebalance.after")}</th>
+                            <th>{i18n.t("rebalance.trade")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{for rows}</tbody>
+                    <tfoot>
+                        <tr class="text-right text-xs text-gray-500">
+                            <td class="text-left py-1">
+                                <select class="rebalance-add border rounded px-2 py-1 text-sm text-gray-800" onchange={on_add}>
+                                    <option value="" selected=true>{i18n.t("rebalance.add")}</option>
+                                    {for unlisted.map(|crypto| html! {
+                                        <option value={crypto.id.clone()}>{&crypto.name}</option>
+                                    })}
+                                </select>
+                            </td>
+                            <td></td>
+                            <td class="rebalance-target-total font-mono">{i18n.percent(percent(settings.target_total()))}</td>
+                            <td colspan="2"></td>
+                        </tr>
+                    </tfoot>
+                </table>
+            </div>
+            {summary}
+        </section>
+    }
+}
+
+// "Buy 0.5 ETH ($1,000.00)", "Sell …" or "Hold"
+fn trade(i18n: &I18n, allocation: Option<&Allocation>) -> Html {
+    let Some(allocation) = allocation.filter(|allocation| !allocation.trade.is_zero()) else {
+        return html! { <span class="text-gray-400">{i18n.t("rebalance.hold")}</span> };
+    };
+    let (class, key) = if allocation.trade.is_sign_positive() {
+        ("rebalance-buy text-green-600", "rebalance.buy")
+    } else {
+        ("rebalance-sell text-red-600", "rebalance.sell")
+    };
+    let args = [
+        ("amount", i18n.quantity(&allocation.trade.abs())),
+        ("symbol", allocation.crypto.symbol.clone()),
+        ("value", i18n.currency(&allocation.trade_value().abs())),
+    ];
+    html! { <span class={class}>{i18n.t_args(key, &args)}</span> }
+}
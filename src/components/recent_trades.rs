@@ -0,0 +1,38 @@
+// src/components/recent_trades.rs
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::models::market::{Side, Trade};
+
+#[derive(Properties, PartialEq)]
+pub struct RecentTradesProps {
+    pub trades: Vec<Trade>,
+}
+
+#[function_component(RecentTrades)]
+pub fn recent_trades(props: &RecentTradesProps) -> Html {
+    let i18n = use_i18n();
+
+    html! {
+        <div class="recent-trades">
+            <div class="grid grid-cols-3 text-right text-xs font-semibold text-gray-500 px-2 pb-1">
+                <span class="text-left">{i18n.t("market.time")}</span>
+                <span>{i18n.t("market.price")}</span>
+                <span>{i18n.t("market.amount")}</span>
+            </div>
+            {props.trades.iter().map(|trade| {
+                let (class, side) = match trade.side {
+                    Side::Buy => ("trade-buy text-green-600", i18n.t("market.buy")),
+                    Side::Sell => ("trade-sell text-red-600", i18n.t("market.sell")),
+                };
+                html! {
+                    <div key={trade.id} class="trade grid grid-cols-3 text-right font-mono text-xs px-2 py-0.5">
+                        <span class="text-left text-gray-500">{i18n.time(&trade.timestamp)}</span>
+                        <span class={class} title={side}>{i18n.price(&trade.price)}</span>
+                        <span>{i18n.quantity(&trade.amount)}</span>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </div>
+    }
+}
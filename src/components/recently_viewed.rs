@@ -0,0 +1,54 @@
+// src/components/recently_viewed.rs
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::i18n::use_i18n;
+use crate::store::{use_store, StoreAction};
+
+// Quick links back to the assets opened most recently; hidden until there are any
+#[function_component(RecentlyViewedStrip)]
+pub fn recently_viewed_strip() -> Html {
+    let i18n = use_i18n();
+    let (store, dispatch) = use_store();
+
+    if store.recently_viewed.is_empty() {
+        return html! {};
+    }
+
+    let on_clear = Callback::from(move |_| dispatch.emit(StoreAction::ClearRecentlyViewed));
+
+    html! {
+        <section class="recently-viewed mb-6">
+            <div class="flex items-baseline justify-between mb-2">
+                <h3 class="text-sm font-semibold text-gray-600">{i18n.t("recent.title")}</h3>
+                <button class="recent-clear text-xs text-gray-500 hover:text-blue-600 transition" onclick={on_clear}>
+                    {i18n.t("recent.clear")}
+                </button>
+            </div>
+            <div class="flex flex-wrap gap-2">
+                {store.recently_viewed.iter().map(|asset| {
+                    let price = store
+                        .cryptocurrencies
+                        .iter()
+                        .find(|crypto| crypto.id == asset.id)
+                        .map(|crypto| i18n.price(&crypto.price));
+
+                    html! {
+                        <Link<Route>
+                            key={asset.id.clone()}
+                            to={Route::Details { id: asset.id.clone() }}
+                            classes="recent-asset flex items-center space-x-2 border rounded-full px-3 py-1 text-sm hover:bg-blue-50 transition"
+                        >
+                            <span class="font-mono font-bold text-blue-700">{&asset.symbol}</span>
+                            <span class="text-gray-700">{&asset.name}</span>
+                            if let Some(price) = price {
+                                <span class="text-gray-500">{price}</span>
+                            }
+                        </Link<Route>>
+                    }
+                }).collect::<Html>()}
+            </div>
+        </section>
+    }
+}
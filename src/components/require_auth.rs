@@ -0,0 +1,40 @@
+// src/components/require_auth.rs
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::auth::use_auth;
+use crate::pages::login::LoginQuery;
+
+#[derive(Properties, PartialEq)]
+pub struct RequireAuthProps {
+    #[prop_or_default]
+    pub children: Html,
+}
+
+// Renders its children only when signed in; otherwise sends the visitor to
+// the login page, which returns them here afterwards
+#[function_component(RequireAuth)]
+pub fn require_auth(props: &RequireAuthProps) -> Html {
+    let auth = use_auth();
+    let navigator = use_navigator().unwrap();
+    let location = use_location().unwrap();
+
+    let authenticated = auth.is_authenticated();
+    use_effect_with(authenticated, move |authenticated| {
+        if !authenticated {
+            let query = LoginQuery {
+                redirect: Some(location.path().to_string()),
+            };
+            if let Err(err) = navigator.replace_with_query(&Route::Login, &query) {
+                log::error!("Failed to redirect to login: {}", err);
+            }
+        }
+    });
+
+    if authenticated {
+        props.children.clone()
+    } else {
+        html! {}
+    }
+}
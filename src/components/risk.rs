@@ -0,0 +1,189 @@
+// src/components/risk.rs
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures::future::join_all;
+use rust_decimal::prelude::ToPrimitive;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::components::correlation_heatmap::CorrelationHeatmap;
+use crate::components::loading::Loading;
+use crate::i18n::{use_i18n, I18n};
+use crate::models::history::Candle;
+use crate::risk::{analyze, RiskMetrics, BENCHMARK};
+use crate::services::api;
+use crate::store::use_store;
+
+// Days of history the metrics can be measured over
+const WINDOWS: [u32; 3] = [30, 90, 180];
+const DEFAULT_WINDOW: u32 = 90;
+const RISK_FREE_RATE: f64 = 0.0;
+const CONFIDENCE: f64 = 0.95;
+
+// Held assets' histories plus the benchmark's
+type Histories = (Vec<(String, Vec<Candle>)>, Vec<Candle>);
+
+// Volatility, risk-adjusted return, drawdown, beta and Value-at-Risk of every
+// holding and of the portfolio as a whole, with the correlations between them
+#[function_component(RiskPanel)]
+pub fn risk_panel() -> Html {
+    let (store, _) = use_store();
+    let i18n = use_i18n();
+    let window = use_state(|| DEFAULT_WINDOW);
+    let history = use_state(|| None::<Result<Histories, String>>);
+
+    // Held assets the market knows, with their current value
+    let held: Vec<(String, String, f64)> = store
+        .holdings()
+        .iter()
+        .filter_map(|holding| {
+            let crypto = store.cryptocurrencies.iter().find(|crypto| crypto.id == holding.crypto_id)?;
+            let value = crypto.value_of(holding.amount()).to_f64().unwrap_or_default();
+            Some((crypto.id.clone(), crypto.symbol.clone(), value))
+        })
+        .collect();
+    let ids: Vec<String> = held.iter().map(|(id, _, _)| id.clone()).collect();
+
+    {
+        let history = history.clone();
+        use_effect_with((ids.clone(), *window), move |(ids, days)| {
+            let current = Rc::new(Cell::new(true));
+            history.set(None);
+            let (ids, days) = (ids.clone(), *days);
+            {
+                let current = current.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let (assets, benchmark) = futures::join!(
+                        join_all(ids.iter().map(|id| api::get_price_history(id, days))),
+                        api::get_price_history(BENCHMARK, days)
+                    );
+                    if !current.get() {
+                        return;
+                    }
+                    let result = assets
+                        .into_iter()
+                        .collect::<Result<Vec<_>, _>>()
+                        .and_then(|assets| Ok((ids.into_iter().zip(assets).collect(), benchmark?)))
+                        .map_err(|err| err.to_string());
+                    history.set(Some(result));
+                });
+            }
+            move || current.set(false)
+        });
+    }
+
+    let on_window = {
+        let window = window.clone();
+        Callback::from(move |e: Event| {
+            if let Ok(days) = e.target_unchecked_into::<HtmlSelectElement>().value().parse() {
+                window.set(days);
+            }
+        })
+    };
+
+    let body = match &*history {
+        None => html! { <Loading /> },
+        Some(Err(err)) => html! {
+            <p class="risk-error text-sm text-red-600" role="alert">{i18n.t_args("risk.error", &[("error", err.clone())])}</p>
+        },
+        Some(Ok((histories, benchmark))) => {
+            // Only what is still held, should the holdings change mid-load
+            let histories: Vec<_> = histories.iter().filter(|(id, _)| ids.contains(id)).cloned().collect();
+            let weights: Vec<f64> = histories
+                .iter()
+                .map(|(id, _)| held.iter().find(|(held, _, _)| held == id).map_or(0.0, |(_, _, value)| *value))
+                .collect();
+            let report = analyze(&histories, &weights, Some(benchmark), RISK_FREE_RATE, CONFIDENCE);
+            let symbol = |id: &str| held.iter().find(|(held, _, _)| held == id).map_or_else(|| id.to_string(), |(_, symbol, _)| symbol.clone());
+            let labels: Vec<String> = report.assets.iter().map(|(id, _)| symbol(id)).collect();
+
+            if report.days < 2 {
+                html! { <p class="risk-error text-sm text-gray-500">{i18n.t("risk.not_enough_history")}</p> }
+            } else {
+                html! {
+                    <>
+                        <div class="overflow-x-auto">
+                            <table class="w-full text-sm">
+                                <thead>
+                                    <tr class="text-right text-xs text-gray-500 border-b">
+                                        <th class="text-left">{i18n.t("risk.asset")}</th>
+                                        <th>{i18n.t("risk.volatility")}</th>
+                                        <th>{i18n.t("risk.sharpe")}</th>
+                                        <th>{i18n.t("risk.sortino")}</th>
+                                        <th>{i18n.t("risk.max_drawdown")}</th>
+                                        <th>{i18n.t("risk.beta")}</th>
+                                        <th>{i18n.t("risk.var_historical")}</th>
+                                        <th>{i18n.t("risk.var_parametric")}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {for report.assets.iter().zip(&labels).map(|((id, metrics), label)| html! {
+                                        <tr key={id.clone()} class="risk-row text-right border-b">
+                                            <td class="text-left py-1 font-mono font-bold text-blue-700">{label}</td>
+                                            {cells(&i18n, metrics)}
+                                        </tr>
+                                    })}
+                                </tbody>
+                                <tfoot>
+                                    <tr class="risk-portfolio text-right font-semibold">
+                                        <td class="text-left py-1">{i18n.t("risk.portfolio")}</td>
+                                        {cells(&i18n, &report.portfolio)}
+                                    </tr>
+                                </tfoot>
+                            </table>
+                        </div>
+                        <p class="risk-note text-xs text-gray-500 mt-2">
+                            {i18n.t_args("risk.note", &[
+                                ("days", report.days.to_string()),
+                                ("confidence", i18n.percent(CONFIDENCE * 100.0)),
+                                ("rate", i18n.percent(RISK_FREE_RATE * 100.0)),
+                            ])}
+                        </p>
+                        if labels.len() > 1 {
+                            <h4 class="text-sm font-semibold text-gray-700 mt-4 mb-2">{i18n.t("risk.correlations")}</h4>
+                            <CorrelationHeatmap labels={labels.clone()} matrix={report.correlations.clone()} />
+                        }
+                    </>
+                }
+            }
+        }
+    };
+
+    html! {
+        <section class="risk-panel mt-8">
+            <div class="flex items-center justify-between">
+                <h3 class="text-lg font-semibold text-blue-700">{i18n.t("risk.title")}</h3>
+                <select class="risk-window border rounded px-2 py-1 text-sm text-gray-800" aria-label={i18n.t("risk.window")} onchange={on_window}>
+                    {for WINDOWS.iter().map(|days| html! {
+                        <option value={days.to_string()} selected={*days == *window}>
+                            {i18n.plural("risk.days", f64::from(*days))}
+                        </option>
+                    })}
+                </select>
+            </div>
+            <p class="text-sm text-gray-500 mb-3">{i18n.t("risk.description")}</p>
+            {body}
+        </section>
+    }
+}
+
+fn cells(i18n: &I18n, metrics: &RiskMetrics) -> Html {
+    let percent = |value: Option<f64>| value.map(|value| i18n.percent(value * 100.0));
+    let ratio = |value: Option<f64>| value.map(|value| i18n.number(value, 2));
+    let columns = [
+        ("risk-volatility", percent(metrics.volatility)),
+        ("risk-sharpe", ratio(metrics.sharpe)),
+        ("risk-sortino", ratio(metrics.sortino)),
+        ("risk-drawdown", percent(metrics.max_drawdown)),
+        ("risk-beta", ratio(metrics.beta)),
+        ("risk-var-historical", percent(metrics.var_historical)),
+        ("risk-var-parametric", percent(metrics.var_parametric)),
+    ];
+    columns
+        .into_iter()
+        .map(|(class, value)| html! {
+            <td class={classes!(class, "font-mono")}>{value.unwrap_or_else(|| "–".to_string())}</td>
+        })
+        .collect()
+}
@@ -0,0 +1,47 @@
+// src/components/sync_status.rs
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::store::{use_store, use_sync, StoreAction, SyncStatus};
+
+// Sync state of the portfolio, shown in the Portfolio page header
+#[function_component(SyncIndicator)]
+pub fn sync_indicator() -> Html {
+    let (store, dispatch) = use_store();
+    let sync = use_sync();
+    let i18n = use_i18n();
+
+    let pending = sync.pending_changes(&store.portfolio);
+    let pending_text = (pending > 0).then(|| i18n.plural("sync.pending", pending as f64));
+
+    let on_retry = Callback::from(move |_| dispatch.emit(StoreAction::SavePortfolio));
+
+    let (class, label, retry) = match &sync.status {
+        SyncStatus::Syncing => ("text-gray-500", i18n.t("sync.saving"), false),
+        SyncStatus::Offline => ("text-yellow-700", i18n.t("sync.offline"), false),
+        SyncStatus::Failed(_) => ("text-red-600", i18n.t("sync.failed"), true),
+        SyncStatus::Synced if pending == 0 => ("text-green-600", i18n.t("sync.saved"), false),
+        SyncStatus::Idle | SyncStatus::Synced => match pending_text.clone() {
+            Some(text) => return html! { <span class="sync-status text-sm text-gray-500">{text}</span> },
+            None => return html! {},
+        },
+    };
+    let title = match &sync.status {
+        SyncStatus::Failed(message) => Some(message.clone()),
+        _ => None,
+    };
+
+    html! {
+        <div role="status" class={classes!("sync-status", "flex", "items-center", "space-x-2", "text-sm", class)} title={title}>
+            <span>{label}</span>
+            if let Some(text) = pending_text.filter(|_| !sync.is_syncing()) {
+                <span class="text-gray-500">{"· "}{text}</span>
+            }
+            if retry {
+                <button class="font-semibold text-blue-600 hover:text-blue-800 transition" onclick={on_retry}>
+                    {i18n.t("sync.retry")}
+                </button>
+            }
+        </div>
+    }
+}
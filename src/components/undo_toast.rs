@@ -0,0 +1,120 @@
+// src/components/undo_toast.rs
+use gloo::events::EventListener;
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent};
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::store::{use_history, use_store, StoreAction, StoreContext};
+
+// How long the toast stays visible after a destructive edit
+const TOAST_DURATION_MS: u32 = 6_000;
+
+// Fields where Ctrl+Z should keep its native text-editing meaning
+fn is_text_field(target: Option<web_sys::EventTarget>) -> bool {
+    target
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .map(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+        .unwrap_or(false)
+}
+
+// Registers Ctrl+Z / Ctrl+Shift+Z (and Ctrl+Y) for portfolio undo and redo
+#[hook]
+fn use_undo_shortcuts() {
+    let store = use_context::<StoreContext>().expect("Store context not set");
+
+    use_effect_with((), move |_| {
+        let listener = EventListener::new(&gloo::utils::window(), "keydown", move |event| {
+            let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                return;
+            };
+            if !(event.ctrl_key() || event.meta_key()) || is_text_field(event.target()) {
+                return;
+            }
+
+            let action = match event.key().to_lowercase().as_str() {
+                "z" if event.shift_key() => StoreAction::Redo,
+                "z" => StoreAction::Undo,
+                "y" => StoreAction::Redo,
+                _ => return,
+            };
+            event.prevent_default();
+            // Like the toast's button, push the restored portfolio straight away
+            store.dispatch(action);
+            store.dispatch(StoreAction::SavePortfolio);
+        });
+        move || drop(listener)
+    });
+}
+
+#[function_component(UndoToast)]
+pub fn undo_toast() -> Html {
+    use_undo_shortcuts();
+
+    let i18n = use_i18n();
+    let (store, dispatch) = use_store();
+    let history = use_history();
+    let dismissed_revision = use_state(|| history.revision());
+
+    // Hide the toast a few seconds after each new edit
+    {
+        let dismissed_revision = dismissed_revision.clone();
+        use_effect_with(history.revision(), move |revision| {
+            let revision = *revision;
+            let timeout = Timeout::new(TOAST_DURATION_MS, move || {
+                dismissed_revision.set(revision);
+            });
+            move || drop(timeout)
+        });
+    }
+
+    let edit = match history.last_edit() {
+        Some(edit) if edit.is_destructive() && *dismissed_revision != history.revision() => edit,
+        _ => return html! {},
+    };
+
+    let name = store
+        .cryptocurrencies
+        .iter()
+        .chain(store.selected_cryptocurrency.iter())
+        .find(|crypto| crypto.id == edit.crypto_id())
+        .map(|crypto| crypto.name.clone())
+        .unwrap_or_else(|| edit.crypto_id().to_string());
+
+    let on_undo = {
+        let dispatch = dispatch.clone();
+        let dismissed_revision = dismissed_revision.clone();
+        let revision = history.revision();
+        Callback::from(move |_| {
+            dispatch.emit(StoreAction::Undo);
+            dispatch.emit(StoreAction::SavePortfolio);
+            dismissed_revision.set(revision);
+        })
+    };
+
+    let on_dismiss = {
+        let dismissed_revision = dismissed_revision.clone();
+        let revision = history.revision();
+        Callback::from(move |_| dismissed_revision.set(revision))
+    };
+
+    html! {
+        <div
+            role="status"
+            class="undo-toast fixed bottom-6 left-1/2 -translate-x-1/2 flex items-center space-x-4 bg-gray-800 text-white rounded shadow-lg px-4 py-3"
+        >
+            <span>{i18n.t_args("undo.removed", &[("name", name)])}</span>
+            <button class="font-semibold text-blue-300 hover:text-blue-200 transition" onclick={on_undo}>
+                {i18n.t("undo.undo")}
+            </button>
+            <button
+                class="text-gray-400 hover:text-gray-200 transition"
+                aria-label={i18n.t("undo.dismiss")}
+                onclick={on_dismiss}
+            >
+                {"✕"}
+            </button>
+        </div>
+    }
+}
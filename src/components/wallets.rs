@@ -0,0 +1,219 @@
+// src/components/wallets.rs
+use rust_decimal::Decimal;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::i18n::{use_i18n, I18n};
+use crate::models::crypto::Cryptocurrency;
+use crate::models::wallet::{short_address, validate_address, Chain, WalletBalance, WatchedWallet};
+use crate::store::{use_store, Holding, HoldingSource, StoreAction};
+
+// Watched addresses with their balances, and a form to add more
+#[function_component(WalletManager)]
+pub fn wallet_manager() -> Html {
+    let (store, dispatch) = use_store();
+    let i18n = use_i18n();
+    let chain = use_state(|| Chain::Bitcoin);
+    let address = use_state(String::new);
+    let label = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    let on_chain = {
+        let chain = chain.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(selected) = Chain::from_code(&select.value()) {
+                chain.set(selected);
+            }
+        })
+    };
+    let on_address = {
+        let address = address.clone();
+        Callback::from(move |e: InputEvent| address.set(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_label = {
+        let label = label.clone();
+        Callback::from(move |e: InputEvent| label.set(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+
+    let on_submit = {
+        let (chain, address, label, error) = (chain.clone(), address.clone(), label.clone(), error.clone());
+        let wallets = store.wallets.clone();
+        let dispatch = dispatch.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let wallet = match validate_address(*chain, &address) {
+                Ok(valid) => WatchedWallet {
+                    chain: *chain,
+                    address: valid,
+                    label: Some(label.trim().to_string()).filter(|label| !label.is_empty()),
+                },
+                Err(err) => {
+                    error.set(Some(format!("wallets.error.{}", err.code())));
+                    return;
+                }
+            };
+            if wallets.iter().any(|watched| watched.key() == wallet.key()) {
+                error.set(Some("wallets.error.duplicate".to_string()));
+                return;
+            }
+            dispatch.emit(StoreAction::AddWallet(wallet));
+            address.set(String::new());
+            label.set(String::new());
+            error.set(None);
+        })
+    };
+
+    let rows = store.wallets.iter().map(|wallet| {
+        let on_remove = {
+            let dispatch = dispatch.clone();
+            let key = wallet.key();
+            Callback::from(move |_| dispatch.emit(StoreAction::RemoveWallet(key.clone())))
+        };
+        let balance = match store.wallet_balances.get(&wallet.key()) {
+            None => html! { <span class="wallet-balance text-gray-400">{i18n.t("common.loading")}</span> },
+            Some(WalletBalance::Loaded(assets)) => html! {
+                <span class="wallet-balance font-mono">
+                    {for assets.iter().enumerate().map(|(index, asset)| html! {
+                        <>
+                            if index > 0 {{" · "}}
+                            <span class={classes!(asset.token.is_some().then_some("wallet-token"))} title={asset.token.clone()}>
+                                {i18n.quantity(&asset.amount)}{" "}{&asset.symbol}
+                            </span>
+                        </>
+                    })}
+                </span>
+            },
+            Some(WalletBalance::Failed(message)) => html! {
+                <span class="wallet-balance wallet-failed exchange-badge" title={message.clone()}>{i18n.t("wallets.unavailable")}</span>
+            },
+        };
+        html! {
+            <li key={wallet.key()} class="wallet-row flex items-center justify-between border-b py-2 text-sm">
+                <div class="flex items-center space-x-2 min-w-0">
+                    <span class={classes!("wallet-chain", format!("wallet-chain-{}", wallet.chain.code()))}>{wallet.chain.name()}</span>
+                    <span class="wallet-name font-semibold">{wallet.display_name()}</span>
+                    if wallet.label.is_some() {
+                        <span class="wallet-address font-mono text-xs text-gray-500" title={wallet.address.clone()}>
+                            {short_address(&wallet.address)}
+                        </span>
+                    }
+                </div>
+                <div class="flex items-center space-x-3">
+                    {balance}
+                    <button class="wallet-remove text-xs text-red-600 hover:underline" onclick={on_remove}>
+                        {i18n.t("common.remove")}
+                    </button>
+                </div>
+            </li>
+        }
+    });
+
+    html! {
+        <section class="wallets mt-8">
+            <h3 class="text-lg font-semibold text-blue-700">{i18n.t("wallets.title")}</h3>
+            <p class="text-sm text-gray-500 mb-3">{i18n.t("wallets.description")}</p>
+            if store.wallets.is_empty() {
+                <p class="wallets-empty text-sm text-gray-400 mb-3">{i18n.t("wallets.empty")}</p>
+            } else {
+                <ul class="wallet-list mb-4">{for rows}</ul>
+            }
+            <form class="wallet-form flex flex-wrap items-end gap-2" onsubmit={on_submit}>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("wallets.chain")}</span>
+                    <select class="wallet-chain-select border rounded px-2 py-1 text-sm text-gray-800" onchange={on_chain}>
+                        {for Chain::ALL.into_iter().map(|option| html! {
+                            <option value={option.code()} selected={option == *chain}>{option.name()}</option>
+                        })}
+                    </select>
+                </label>
+                <label class="flex flex-col flex-1 text-xs text-gray-500">
+                    <span>{i18n.t("wallets.address")}</span>
+                    <input
+                        class="wallet-address-input border rounded px-2 py-1 text-sm font-mono text-gray-800"
+                        spellcheck="false"
+                        autocomplete="off"
+                        value={(*address).clone()}
+                        oninput={on_address}
+                    />
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("wallets.label")}</span>
+                    <input
+                        class="wallet-label-input w-36 border rounded px-2 py-1 text-sm text-gray-800"
+                        placeholder={i18n.t("wallets.label_placeholder")}
+                        value={(*label).clone()}
+                        oninput={on_label}
+                    />
+                </label>
+                <button type="submit" class="px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition">
+                    {i18n.t("wallets.add")}
+                </button>
+            </form>
+            if let Some(key) = &*error {
+                <p class="wallet-error text-sm text-red-600 mt-2" role="alert">
+                    {i18n.t_args(key, &[("chain", chain.name().to_string())])}
+                </p>
+            }
+        </section>
+    }
+}
+
+// "Manual 0.5 · Cold storage 0.25", under holdings that include wallets or
+// income
+pub fn holding_sources(i18n: &I18n, sources: &[(HoldingSource, Decimal)]) -> Html {
+    let parts = sources.iter().enumerate().map(|(index, (source, amount))| {
+        let name = match source {
+            HoldingSource::Manual => i18n.t("wallets.source.manual"),
+            HoldingSource::Wallet { wallet, token: None } => format!("{} ({})", wallet.display_name(), wallet.chain.name()),
+            HoldingSource::Wallet { wallet, token: Some(symbol) } => {
+                format!("{} ({}, {})", wallet.display_name(), wallet.chain.name(), symbol)
+            }
+            HoldingSource::Income => i18n.t("wallets.source.income"),
+        };
+        html! {
+            <>
+                if index > 0 {{" · "}}
+                <span class="holding-source">{name}{" "}<span class="font-mono">{i18n.quantity(amount)}</span></span>
+            </>
+        }
+    });
+    html! { <div class="holding-sources text-xs text-gray-500 mt-1">{for parts}</div> }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct WalletHoldingItemProps {
+    pub holding: Holding,
+    pub cryptocurrency: Option<Cryptocurrency>,
+}
+
+// An asset held only in watched wallets or received as income; its amount
+// can't be edited here
+#[function_component(WalletHoldingItem)]
+pub fn wallet_holding_item(props: &WalletHoldingItemProps) -> Html {
+    let i18n = use_i18n();
+    let Some(crypto) = &props.cryptocurrency else {
+        return html! {
+            <div class="portfolio-item loading flex items-center justify-between bg-gray-100 rounded p-4 animate-pulse">
+                <span class="symbol text-gray-400">{i18n.t("common.loading")}</span>
+            </div>
+        };
+    };
+    let amount = props.holding.amount();
+
+    html! {
+        <div class="portfolio-item wallet-holding bg-blue-50 rounded p-4 shadow-sm">
+            <Link<Route> to={Route::Details { id: crypto.id.clone() }} classes="crypto-name flex items-center space-x-2">
+                <span class="symbol font-mono font-bold text-blue-700">{&crypto.symbol}</span>
+                <span class="name text-gray-700">{&crypto.name}</span>
+            </Link<Route>>
+            <div class="holdings flex items-center space-x-4 mt-2">
+                <span class="amount text-gray-800">{i18n.quantity(&amount)}</span>
+                <span class="value font-semibold text-green-600">{i18n.currency(&crypto.value_of(amount))}</span>
+            </div>
+            {holding_sources(&i18n, &props.holding.sources)}
+        </div>
+    }
+}
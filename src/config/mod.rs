@@ -0,0 +1,421 @@
+// src/config/mod.rs
+//
+// Settings read at runtime from `config.json`, served next to the bundle, so
+// a deployment can point at another API or rebrand without a rebuild.
+// Anything the file leaves out keeps the compiled default; a file that fails
+// validation is ignored as a whole.
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use reqwasm::http::Request;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::models::token::Token;
+use crate::models::wallet::{validate_address, Chain};
+use crate::utils::format::Currency;
+
+const CONFIG_URL: &str = "config.json";
+
+// Shortest refresh interval accepted, to keep clients from hammering the API
+pub const MIN_REFRESH_SECS: u32 = 5;
+pub const MAX_REFRESH_SECS: u32 = 24 * 60 * 60;
+// Most decimal places a `Decimal` can hold
+pub const MAX_TOKEN_DECIMALS: u32 = 28;
+
+// Where market data comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    // Bundled sample data; portfolios stay in local storage
+    Sample,
+    // The HTTP API at `api_base_url`
+    Api,
+}
+
+// Seconds between background refreshes, 0 to turn one off
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Refresh {
+    pub prices_secs: u32,
+    pub portfolio_secs: u32,
+    // Order book and trades on the details page
+    pub order_book_secs: u32,
+}
+
+impl Default for Refresh {
+    fn default() -> Self {
+        Self {
+            prices_secs: 30,
+            portfolio_secs: 60,
+            order_book_secs: 5,
+        }
+    }
+}
+
+// A venue whose prices go into the aggregate assets are priced at
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Exchange {
+    pub id: String,
+    pub name: String,
+    // Base URL of the venue's own ticker API; without one its prices come
+    // from the main API under `/exchanges/{id}`
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Exchange {
+    fn new(id: &str, name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            url: None,
+        }
+    }
+}
+
+fn default_exchanges() -> Vec<Exchange> {
+    vec![
+        Exchange::new("binance", "Binance"),
+        Exchange::new("coinbase", "Coinbase"),
+        Exchange::new("kraken", "Kraken"),
+        Exchange::new("bitstamp", "Bitstamp"),
+    ]
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Features {
+    pub install_prompt: bool,
+    pub language_selector: bool,
+    pub offline_banner: bool,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            install_prompt: true,
+            language_selector: true,
+            offline_banner: true,
+        }
+    }
+}
+
+// Overrides for the translated app name and footer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Branding {
+    pub app_name: Option<String>,
+    pub copyright: Option<String>,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            app_name: None,
+            copyright: option_env!("COPYRIGHT_TEXT").map(str::to_string),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub provider: Provider,
+    pub api_base_url: Option<String>,
+    pub refresh: Refresh,
+    pub exchanges: Vec<Exchange>,
+    // Node or block explorer per chain for watched wallets; chains left out
+    // go through the API server's `/rpc/{chain}`
+    pub rpc: BTreeMap<Chain, String>,
+    // Tokens counted in watched wallets on top of the built-in list, e.g.
+    // bridged versions of other listed assets
+    pub tokens: Vec<Token>,
+    // Token contracts or mints never counted, even when listed
+    pub token_denylist: Vec<String>,
+    // Currency the API quotes prices in, used to format fiat amounts
+    pub currency: Currency,
+    pub features: Features,
+    pub branding: Branding,
+}
+
+// Compiled defaults, taking `API_BASE_URL` and `COPYRIGHT_TEXT` from the
+// build environment when set
+impl Default for Config {
+    fn default() -> Self {
+        let api_base_url = option_env!("API_BASE_URL").map(str::to_string);
+        Self {
+            provider: if api_base_url.is_some() { Provider::Api } else { Provider::Sample },
+            api_base_url,
+            refresh: Refresh::default(),
+            exchanges: default_exchanges(),
+            rpc: BTreeMap::new(),
+            tokens: Vec::new(),
+            token_denylist: Vec::new(),
+            currency: Currency::default(),
+            features: Features::default(),
+            branding: Branding::default(),
+        }
+    }
+}
+
+impl Config {
+    // Reads a `config.json`, listing every problem found
+    pub fn parse(text: &str) -> Result<Self, Vec<String>> {
+        let config: Self = serde_json::from_str(text).map_err(|err| vec![err.to_string()])?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.provider == Provider::Api {
+            match self.api_base_url.as_deref().map(str::trim) {
+                None | Some("") => problems.push("provider \"api\" needs an api_base_url".to_string()),
+                Some(url) if !is_url(url) => {
+                    problems.push(format!("api_base_url must be an http(s) URL or an absolute path, got `{}`", url))
+                }
+                Some(_) => {}
+            }
+        }
+        for (index, exchange) in self.exchanges.iter().enumerate() {
+            if exchange.id.trim().is_empty() || exchange.name.trim().is_empty() {
+                problems.push(format!("exchanges[{}] needs an id and a name", index));
+            } else if self.exchanges[..index].iter().any(|earlier| earlier.id == exchange.id) {
+                problems.push(format!("exchanges[{}] repeats the id `{}`", index, exchange.id));
+            }
+            if let Some(url) = exchange.url.as_deref().map(str::trim).filter(|url| !is_url(url)) {
+                problems.push(format!("exchanges[{}].url must be an http(s) URL or an absolute path, got `{}`", index, url));
+            }
+        }
+        for (chain, url) in &self.rpc {
+            if !is_url(url.trim()) {
+                problems.push(format!("rpc.{} must be an http(s) URL or an absolute path, got `{}`", chain.code(), url));
+            }
+        }
+        for (index, token) in self.tokens.iter().enumerate() {
+            if token.chain == Chain::Bitcoin {
+                problems.push(format!("tokens[{}]: Bitcoin has no tokens", index));
+            } else if validate_address(token.chain, &token.contract).is_err() {
+                problems.push(format!("tokens[{}].contract `{}` isn't a {} address", index, token.contract, token.chain.name()));
+            }
+            if token.asset_id.trim().is_empty() || token.symbol.trim().is_empty() {
+                problems.push(format!("tokens[{}] needs a symbol and an asset_id", index));
+            }
+            if token.decimals > MAX_TOKEN_DECIMALS {
+                problems.push(format!("tokens[{}].decimals must be at most {}", index, MAX_TOKEN_DECIMALS));
+            }
+        }
+        for (name, secs) in [
+            ("refresh.prices_secs", self.refresh.prices_secs),
+            ("refresh.portfolio_secs", self.refresh.portfolio_secs),
+            ("refresh.order_book_secs", self.refresh.order_book_secs),
+        ] {
+            if secs != 0 && !(MIN_REFRESH_SECS..=MAX_REFRESH_SECS).contains(&secs) {
+                problems.push(format!(
+                    "{} must be 0 or between {} and {}, got {}",
+                    name, MIN_REFRESH_SECS, MAX_REFRESH_SECS, secs
+                ));
+            }
+        }
+        if self.branding.app_name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            problems.push("branding.app_name must not be empty".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    // Base URL requests go to, or `None` to use the sample data
+    pub fn api_base_url(&self) -> Option<String> {
+        match (self.provider, &self.api_base_url) {
+            (Provider::Api, Some(url)) => Some(url.trim().trim_end_matches('/').to_string()),
+            _ => None,
+        }
+    }
+}
+
+fn is_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://") || url.starts_with('/')
+}
+
+// Fetches `config.json`, falling back to the compiled defaults when it is
+// missing or invalid
+pub async fn load() -> Config {
+    let response = match Request::get(CONFIG_URL).send().await {
+        Ok(response) if response.ok() => response,
+        Ok(response) if response.status() == 404 => return Config::default(),
+        Ok(response) => {
+            log::warn!("Using built-in settings, {} answered {}", CONFIG_URL, response.status());
+            return Config::default();
+        }
+        Err(err) => {
+            log::warn!("Using built-in settings, cannot load {}: {}", CONFIG_URL, err);
+            return Config::default();
+        }
+    };
+
+    let text = response.text().await.unwrap_or_default();
+    Config::parse(&text).unwrap_or_else(|problems| {
+        for problem in problems {
+            log::error!("{}: {}", CONFIG_URL, problem);
+        }
+        log::warn!("Using built-in settings, {} is invalid", CONFIG_URL);
+        Config::default()
+    })
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<Config>>> = const { RefCell::new(None) };
+}
+
+// Config in effect, for code outside components such as `services::api`
+pub fn current() -> Rc<Config> {
+    CURRENT.with(|current| current.borrow_mut().get_or_insert_with(Default::default).clone())
+}
+
+// Called once at startup, before the app renders
+pub fn install(config: Config) {
+    CURRENT.with(|current| *current.borrow_mut() = Some(Rc::new(config)));
+}
+
+#[hook]
+pub fn use_config() -> Rc<Config> {
+    use_context::<Rc<Config>>().expect("Config context not set")
+}
+
+#[derive(Properties, Debug, PartialEq)]
+pub struct ConfigProviderProps {
+    #[prop_or_default]
+    pub children: Html,
+    // Overrides the installed config, e.g. for tests
+    #[prop_or_default]
+    pub config: Option<Config>,
+}
+
+#[function_component]
+pub fn ConfigProvider(props: &ConfigProviderProps) -> Html {
+    let config = props.config.clone().map(Rc::new).unwrap_or_else(current);
+
+    html! {
+        <ContextProvider<Rc<Config>> context={config}>
+            {props.children.clone()}
+        </ContextProvider<Rc<Config>>>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_keep_their_defaults() {
+        let config = Config::parse(r#"{ "refresh": { "prices_secs": 10 }, "branding": { "app_name": "Coin Desk" } }"#).unwrap();
+
+        assert_eq!(config.refresh.prices_secs, 10);
+        assert_eq!(config.refresh.portfolio_secs, Refresh::default().portfolio_secs);
+        assert_eq!(config.branding.app_name.as_deref(), Some("Coin Desk"));
+        assert_eq!(config.features, Features::default());
+        assert_eq!(config.provider, Config::default().provider);
+    }
+
+    #[test]
+    fn api_provider_uses_the_trimmed_base_url() {
+        let config = Config::parse(r#"{ "provider": "api", "api_base_url": "https://api.example.com/v1/", "currency": "EUR" }"#).unwrap();
+
+        assert_eq!(config.api_base_url().as_deref(), Some("https://api.example.com/v1"));
+        assert_eq!(config.currency, Currency::Eur);
+
+        let sample = Config {
+            provider: Provider::Sample,
+            ..config
+        };
+        assert_eq!(sample.api_base_url(), None);
+    }
+
+    #[test]
+    fn reports_every_invalid_setting() {
+        let problems = Config::parse(
+            r#"{ "provider": "api", "refresh": { "prices_secs": 1, "portfolio_secs": 0 }, "branding": { "app_name": " " } }"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("api_base_url"));
+        assert!(problems[1].contains("refresh.prices_secs"));
+        assert!(problems[2].contains("app_name"));
+        assert!(Config::parse(r#"{ "provider": "api", "api_base_url": "ftp://example.com" }"#).is_err());
+    }
+
+    #[test]
+    fn exchanges_need_unique_ids_and_valid_urls() {
+        let config = Config::parse(r#"{ "exchanges": [{ "id": "kraken", "name": "Kraken", "url": "https://kraken.example.com" }] }"#).unwrap();
+        assert_eq!(config.exchanges.len(), 1);
+        assert_eq!(Config::default().exchanges.len(), 4);
+
+        let problems = Config::parse(
+            r#"{ "exchanges": [{ "id": "a", "name": "A" }, { "id": "a", "name": "Again", "url": "ftp://a" }, { "id": "", "name": "B" }] }"#,
+        )
+        .unwrap_err();
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("repeats the id `a`"));
+        assert!(problems[1].contains("exchanges[1].url"));
+        assert!(problems[2].contains("exchanges[2] needs an id"));
+    }
+
+    #[test]
+    fn rpc_endpoints_need_known_chains_and_valid_urls() {
+        let config = Config::parse(r#"{ "rpc": { "bitcoin": "https://mempool.example.com/api", "base": "/rpc/base" } }"#).unwrap();
+        assert_eq!(config.rpc.len(), 2);
+        assert_eq!(config.rpc[&Chain::Base], "/rpc/base");
+
+        let problems = Config::parse(r#"{ "rpc": { "solana": "wss://solana.example.com" } }"#).unwrap_err();
+        assert_eq!(problems, ["rpc.solana must be an http(s) URL or an absolute path, got `wss://solana.example.com`"]);
+        assert!(Config::parse(r#"{ "rpc": { "dogecoin": "https://doge.example.com" } }"#).is_err());
+    }
+
+    #[test]
+    fn tokens_need_a_contract_of_their_chain() {
+        let config = Config::parse(
+            r#"{ "tokens": [{ "chain": "base", "contract": "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c", "symbol": "WBTC", "asset_id": "bitcoin", "decimals": 8 }],
+                 "token_denylist": ["0x4200000000000000000000000000000000000006"] }"#,
+        )
+        .unwrap();
+        assert_eq!(config.tokens[0].chain, Chain::Base);
+        assert_eq!(config.token_denylist.len(), 1);
+
+        let problems = Config::parse(
+            r#"{ "tokens": [
+                { "chain": "bitcoin", "contract": "x", "symbol": "X", "asset_id": "x", "decimals": 8 },
+                { "chain": "solana", "contract": "0x0555e30da8f98308edb960aa94c0db47230d2b9c", "symbol": "", "asset_id": "bitcoin", "decimals": 40 }
+            ] }"#,
+        )
+        .unwrap_err();
+        assert_eq!(problems.len(), 4);
+        assert!(problems[0].contains("Bitcoin has no tokens"));
+        assert!(problems[1].contains("isn't a Solana address"));
+        assert!(problems[2].contains("needs a symbol"));
+        assert!(problems[3].contains("decimals must be at most 28"));
+    }
+
+    #[test]
+    fn example_config_is_valid() {
+        let config = Config::parse(include_str!("../../config.example.json")).unwrap();
+
+        assert_eq!(config.api_base_url().as_deref(), Some("http://localhost:8787"));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_values() {
+        assert!(Config::parse(r#"{ "api_url": "https://example.com" }"#).is_err());
+        assert!(Config::parse(r#"{ "currency": "XYZ" }"#).is_err());
+        assert!(Config::parse(r#"{ "provider": "coingecko" }"#).is_err());
+        assert!(Config::parse("not json").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+// src/i18n/catalog.rs
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+// CLDR plural categories used by the bundled locales
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Other,
+}
+
+impl PluralCategory {
+    fn key(&self) -> &'static str {
+        match self {
+            PluralCategory::One => "one",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+// A catalog entry is either a plain string or a map of plural forms
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Message {
+    Text(String),
+    Plural(HashMap<String, String>),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Catalog {
+    messages: HashMap<String, Message>,
+}
+
+impl Catalog {
+    pub fn parse(source: &str) -> Result<Self, serde_json::Error> {
+        let messages = serde_json::from_str(source)?;
+        Ok(Self { messages })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        match self.messages.get(key)? {
+            Message::Text(text) => Some(text),
+            Message::Plural(forms) => forms.get("other").map(String::as_str),
+        }
+    }
+
+    pub fn get_plural(&self, key: &str, category: PluralCategory) -> Option<&str> {
+        match self.messages.get(key)? {
+            Message::Text(text) => Some(text),
+            Message::Plural(forms) => forms
+                .get(category.key())
+                .or_else(|| forms.get("other"))
+                .map(String::as_str),
+        }
+    }
+}
+
+// Replace `{name}` placeholders with the matching argument
+pub fn interpolate(template: &str, args: &[(&str, String)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+    result
+}
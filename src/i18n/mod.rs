@@ -0,0 +1,340 @@
+// src/i18n/mod.rs
+use std::rc::Rc;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use gloo::storage::{LocalStorage, Storage};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+pub mod catalog;
+
+use crate::config::use_config;
+use crate::utils::format::{self, Currency, NumberSymbols};
+use catalog::{interpolate, Catalog, PluralCategory};
+
+const LOCALE_STORAGE_KEY: &str = "crypto_tracker.locale";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::De, Locale::Fr];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+        }
+    }
+
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+            Locale::Fr => "Français",
+        }
+    }
+
+    // Accepts both bare language codes and tags such as "de-AT"
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    fn catalog_source(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("locales/en.json"),
+            Locale::De => include_str!("locales/de.json"),
+            Locale::Fr => include_str!("locales/fr.json"),
+        }
+    }
+
+    pub fn plural_category(&self, count: f64) -> PluralCategory {
+        match self {
+            Locale::En | Locale::De => {
+                if count == 1.0 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            // French treats zero and one as singular
+            Locale::Fr => {
+                if (0.0..2.0).contains(&count) {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+        }
+    }
+
+    pub fn number_symbols(&self) -> NumberSymbols {
+        match self {
+            Locale::En => NumberSymbols::default(),
+            Locale::De => NumberSymbols {
+                decimal: ',',
+                group: ".",
+                percent: "\u{a0}%",
+                currency_prefix: false,
+                compact: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+            },
+            Locale::Fr => NumberSymbols {
+                decimal: ',',
+                group: "\u{202f}",
+                percent: "\u{a0}%",
+                currency_prefix: false,
+                compact: ["\u{a0}k", "\u{a0}M", "\u{a0}Md", "\u{a0}Bn"],
+            },
+        }
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self {
+            Locale::En => "%m/%d/%Y",
+            Locale::De => "%d.%m.%Y",
+            Locale::Fr => "%d/%m/%Y",
+        }
+    }
+
+    fn time_pattern(&self) -> &'static str {
+        match self {
+            Locale::En => "%-I:%M %p",
+            Locale::De | Locale::Fr => "%H:%M",
+        }
+    }
+}
+
+// Translator and formatter bound to a single locale
+#[derive(Clone, Debug, PartialEq)]
+pub struct I18n {
+    locale: Locale,
+    currency: Currency,
+    catalog: Rc<Catalog>,
+    fallback: Rc<Catalog>,
+}
+
+impl I18n {
+    pub fn new(locale: Locale) -> Self {
+        let fallback = Rc::new(load_catalog(Locale::En));
+        let catalog = if locale == Locale::En {
+            fallback.clone()
+        } else {
+            Rc::new(load_catalog(locale))
+        };
+
+        Self {
+            locale,
+            currency: Currency::default(),
+            catalog,
+            fallback,
+        }
+    }
+
+    pub fn with_currency(self, currency: Currency) -> Self {
+        Self { currency, ..self }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn t(&self, key: &str) -> String {
+        self.catalog
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    pub fn t_args(&self, key: &str, args: &[(&str, String)]) -> String {
+        interpolate(&self.t(key), args)
+    }
+
+    // Select the plural form for `count` and substitute it as `{count}`
+    pub fn plural(&self, key: &str, count: f64) -> String {
+        let category = self.locale.plural_category(count);
+        let template = self
+            .catalog
+            .get_plural(key, category)
+            .or_else(|| self.fallback.get_plural(key, category))
+            .unwrap_or(key);
+        let decimals = if count.fract() == 0.0 { 0 } else { 2 };
+        interpolate(template, &[("count", self.number(count, decimals))])
+    }
+
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        format::format_decimal(value, decimals, &self.locale.number_symbols())
+    }
+
+    pub fn quantity(&self, value: &Decimal) -> String {
+        format::format_quantity(value, &self.locale.number_symbols())
+    }
+
+    pub fn percent(&self, value: f64) -> String {
+        format::format_percent(value, 2, &self.locale.number_symbols())
+    }
+
+    pub fn signed_percent(&self, value: f64) -> String {
+        format::format_signed_percent(value, 2, &self.locale.number_symbols())
+    }
+
+    pub fn compact(&self, value: f64) -> String {
+        format::format_compact(value, &self.locale.number_symbols())
+    }
+
+    // Unit price of an asset, keeping precision for sub-cent tokens
+    pub fn price(&self, value: &Decimal) -> String {
+        let symbols = self.locale.number_symbols();
+        let value = value.to_f64().unwrap_or_default();
+        format::format_currency(format::format_price(value, &symbols), self.currency, &symbols)
+    }
+
+    // Fiat amount such as a holding value or portfolio total
+    pub fn currency(&self, value: &Decimal) -> String {
+        let symbols = self.locale.number_symbols();
+        format::format_currency(format::format_money(value, 2, &symbols), self.currency, &symbols)
+    }
+
+    pub fn compact_currency(&self, value: f64) -> String {
+        let symbols = self.locale.number_symbols();
+        format::format_currency(format::format_compact(value, &symbols), self.currency, &symbols)
+    }
+
+    pub fn date(&self, timestamp: &DateTime<Utc>) -> String {
+        timestamp
+            .with_timezone(&Local)
+            .format(self.locale.date_pattern())
+            .to_string()
+    }
+
+    // A calendar day, shown as is in every time zone
+    pub fn day(&self, date: &NaiveDate) -> String {
+        date.format(self.locale.date_pattern()).to_string()
+    }
+
+    pub fn time(&self, timestamp: &DateTime<Utc>) -> String {
+        timestamp
+            .with_timezone(&Local)
+            .format(self.locale.time_pattern())
+            .to_string()
+    }
+
+    pub fn datetime(&self, timestamp: &DateTime<Utc>) -> String {
+        let local = timestamp.with_timezone(&Local);
+        format!(
+            "{} {}",
+            local.format(self.locale.date_pattern()),
+            local.format(self.locale.time_pattern())
+        )
+    }
+}
+
+fn load_catalog(locale: Locale) -> Catalog {
+    Catalog::parse(locale.catalog_source()).unwrap_or_else(|err| {
+        log::error!("Failed to parse {} catalog: {}", locale.code(), err);
+        Catalog::default()
+    })
+}
+
+// Stored preference first, then the browser language, then English
+fn initial_locale() -> Locale {
+    if let Ok(code) = LocalStorage::get::<String>(LOCALE_STORAGE_KEY) {
+        if let Some(locale) = Locale::from_code(&code) {
+            return locale;
+        }
+    }
+
+    gloo::utils::window()
+        .navigator()
+        .language()
+        .and_then(|language| Locale::from_code(&language))
+        .unwrap_or_default()
+}
+
+#[derive(Clone, PartialEq)]
+pub struct I18nContext {
+    pub i18n: I18n,
+    pub set_locale: Callback<Locale>,
+}
+
+#[hook]
+pub fn use_i18n() -> I18n {
+    use_context::<I18nContext>()
+        .expect("I18n context not set")
+        .i18n
+}
+
+#[hook]
+pub fn use_set_locale() -> Callback<Locale> {
+    use_context::<I18nContext>()
+        .expect("I18n context not set")
+        .set_locale
+}
+
+#[derive(Properties, Debug, PartialEq)]
+pub struct I18nProviderProps {
+    #[prop_or_default]
+    pub children: Html,
+    // Overrides the stored/browser locale, e.g. for tests
+    #[prop_or_default]
+    pub locale: Option<Locale>,
+    // Start in `locale` as the server did, then switch to the stored/browser
+    // locale once mounted
+    #[prop_or_default]
+    pub hydrate: bool,
+}
+
+#[function_component]
+pub fn I18nProvider(props: &I18nProviderProps) -> Html {
+    let locale = props.locale;
+    let hydrate = props.hydrate;
+    let currency = use_config().currency;
+    let i18n = use_state(move || match locale {
+        Some(locale) => I18n::new(locale),
+        None if hydrate => I18n::new(Locale::default()),
+        None => I18n::new(initial_locale()),
+    });
+
+    {
+        let i18n = i18n.clone();
+        use_effect_with((), move |_| {
+            if hydrate {
+                let preferred = initial_locale();
+                if preferred != i18n.locale() {
+                    i18n.set(I18n::new(preferred));
+                }
+            }
+        });
+    }
+
+    let set_locale = {
+        let i18n = i18n.clone();
+        Callback::from(move |locale: Locale| {
+            if let Err(err) = LocalStorage::set(LOCALE_STORAGE_KEY, locale.code()) {
+                log::warn!("Failed to persist locale: {}", err);
+            }
+            i18n.set(I18n::new(locale));
+        })
+    };
+
+    let context = I18nContext {
+        i18n: (*i18n).clone().with_currency(currency),
+        set_locale,
+    };
+
+    html! {
+        <ContextProvider<I18nContext> context={context}>
+            {props.children.clone()}
+        </ContextProvider<I18nContext>>
+    }
+}
@@ -0,0 +1,202 @@
+// src/indicators/mod.rs
+//
+// Technical indicators over price history. Every series has one entry per
+// input bar, `None` until enough bars have been seen, so results line up with
+// the candles they were computed from when charted.
+use crate::models::history::Candle;
+
+pub mod settings;
+
+#[cfg(test)]
+mod tests;
+
+pub use settings::{use_indicator_settings, Indicator, IndicatorSettings};
+
+pub type Series = Vec<Option<f64>>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Macd {
+    // Fast EMA minus slow EMA
+    pub line: Series,
+    // EMA of `line`
+    pub signal: Series,
+    pub histogram: Series,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bollinger {
+    pub upper: Series,
+    // Simple moving average
+    pub middle: Series,
+    pub lower: Series,
+}
+
+pub fn closes(candles: &[Candle]) -> Vec<f64> {
+    candles.iter().map(|candle| candle.close).collect()
+}
+
+// Simple moving average
+pub fn sma(values: &[f64], period: usize) -> Series {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return out;
+    }
+
+    let mut sum: f64 = values[..period].iter().sum();
+    out[period - 1] = Some(sum / period as f64);
+    for index in period..values.len() {
+        sum += values[index] - values[index - period];
+        out[index] = Some(sum / period as f64);
+    }
+    out
+}
+
+// Exponential moving average, seeded with the SMA of the first `period` values
+pub fn ema(values: &[f64], period: usize) -> Series {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return out;
+    }
+
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut average = values[..period].iter().sum::<f64>() / period as f64;
+    out[period - 1] = Some(average);
+    for index in period..values.len() {
+        average += (values[index] - average) * k;
+        out[index] = Some(average);
+    }
+    out
+}
+
+// Relative strength index with Wilder's smoothing, from 0 to 100
+pub fn rsi(values: &[f64], period: usize) -> Series {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return out;
+    }
+
+    let change = |index: usize| values[index] - values[index - 1];
+    let (mut gain, mut loss) = (1..=period).fold((0.0, 0.0), |(gain, loss), index| {
+        let change = change(index);
+        (gain + change.max(0.0), loss + (-change).max(0.0))
+    });
+    gain /= period as f64;
+    loss /= period as f64;
+    out[period] = Some(relative_strength(gain, loss));
+
+    for (index, value) in out.iter_mut().enumerate().skip(period + 1) {
+        let change = change(index);
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        *value = Some(relative_strength(gain, loss));
+    }
+    out
+}
+
+// A flat stretch counts as neutral rather than overbought
+fn relative_strength(gain: f64, loss: f64) -> f64 {
+    if loss == 0.0 {
+        if gain == 0.0 { 50.0 } else { 100.0 }
+    } else {
+        100.0 - 100.0 / (1.0 + gain / loss)
+    }
+}
+
+// Moving average convergence/divergence
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> Macd {
+    let line: Series = ema(values, fast)
+        .into_iter()
+        .zip(ema(values, slow))
+        .map(|(fast, slow)| Some(fast? - slow?))
+        .collect();
+
+    // The signal line starts once it has `signal` values of the MACD line
+    let start = line.iter().position(Option::is_some).unwrap_or(line.len());
+    let defined: Vec<f64> = line[start..].iter().flatten().copied().collect();
+    let mut signal_line = vec![None; start];
+    signal_line.extend(ema(&defined, signal));
+
+    let histogram = line
+        .iter()
+        .zip(&signal_line)
+        .map(|(line, signal)| Some((*line)? - (*signal)?))
+        .collect();
+
+    Macd {
+        line,
+        signal: signal_line,
+        histogram,
+    }
+}
+
+// SMA with bands `width` (population) standard deviations above and below
+pub fn bollinger(values: &[f64], period: usize, width: f64) -> Bollinger {
+    let middle = sma(values, period);
+    let deviation: Series = middle
+        .iter()
+        .enumerate()
+        .map(|(index, mean)| {
+            let mean = (*mean)?;
+            let window = &values[index + 1 - period..=index];
+            let variance = window.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / period as f64;
+            Some(variance.sqrt())
+        })
+        .collect();
+
+    let band = |sign: f64| -> Series {
+        middle
+            .iter()
+            .zip(&deviation)
+            .map(|(mean, deviation)| Some((*mean)? + sign * width * (*deviation)?))
+            .collect()
+    };
+
+    Bollinger {
+        upper: band(1.0),
+        lower: band(-1.0),
+        middle,
+    }
+}
+
+// Volume-weighted average of the typical price, anchored at the first candle
+pub fn vwap(candles: &[Candle]) -> Series {
+    let mut weighted = 0.0;
+    let mut volume = 0.0;
+    candles
+        .iter()
+        .map(|candle| {
+            let typical = (candle.high + candle.low + candle.close) / 3.0;
+            weighted += typical * candle.volume;
+            volume += candle.volume;
+            (volume > 0.0).then(|| weighted / volume)
+        })
+        .collect()
+}
+
+// Average true range with Wilder's smoothing
+pub fn atr(candles: &[Candle], period: usize) -> Series {
+    let mut out = vec![None; candles.len()];
+    if period == 0 || candles.len() < period {
+        return out;
+    }
+
+    let true_range: Vec<f64> = candles
+        .iter()
+        .enumerate()
+        .map(|(index, candle)| {
+            let range = candle.high - candle.low;
+            match index.checked_sub(1).map(|previous| candles[previous].close) {
+                Some(close) => range.max((candle.high - close).abs()).max((candle.low - close).abs()),
+                None => range,
+            }
+        })
+        .collect();
+
+    let mut average = true_range[..period].iter().sum::<f64>() / period as f64;
+    out[period - 1] = Some(average);
+    for index in period..candles.len() {
+        average = (average * (period - 1) as f64 + true_range[index]) / period as f64;
+        out[index] = Some(average);
+    }
+    out
+}
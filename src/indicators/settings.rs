@@ -0,0 +1,177 @@
+// src/indicators/settings.rs
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::utils::settings::use_stored_settings;
+
+const SETTINGS_STORAGE_KEY: &str = "crypto_tracker.indicators";
+
+// Periods outside this range are clamped
+pub const MIN_PERIOD: usize = 2;
+pub const MAX_PERIOD: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Indicator {
+    Sma,
+    Ema,
+    Bollinger,
+    Vwap,
+    Rsi,
+    Macd,
+    Atr,
+}
+
+impl Indicator {
+    pub const ALL: [Indicator; 7] = [
+        Indicator::Sma,
+        Indicator::Ema,
+        Indicator::Bollinger,
+        Indicator::Vwap,
+        Indicator::Rsi,
+        Indicator::Macd,
+        Indicator::Atr,
+    ];
+
+    // Drawn over the price rather than in a pane of its own
+    pub fn is_overlay(&self) -> bool {
+        matches!(self, Indicator::Sma | Indicator::Ema | Indicator::Bollinger | Indicator::Vwap)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Indicator::Sma => "sma",
+            Indicator::Ema => "ema",
+            Indicator::Bollinger => "bollinger",
+            Indicator::Vwap => "vwap",
+            Indicator::Rsi => "rsi",
+            Indicator::Macd => "macd",
+            Indicator::Atr => "atr",
+        }
+    }
+
+    // Catalog key of the indicator's name
+    pub fn label_key(&self) -> String {
+        format!("indicator.{}", self.code())
+    }
+}
+
+// Chart indicators the user has switched on and their parameters, kept in
+// local storage
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndicatorSettings {
+    pub enabled: Vec<Indicator>,
+    pub sma_period: usize,
+    pub ema_period: usize,
+    pub bollinger_period: usize,
+    // Standard deviations between the middle band and the outer ones
+    pub bollinger_width: f64,
+    pub rsi_period: usize,
+    pub macd_fast: usize,
+    pub macd_slow: usize,
+    pub macd_signal: usize,
+    pub atr_period: usize,
+}
+
+impl Default for IndicatorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: vec![Indicator::Sma],
+            sma_period: 20,
+            ema_period: 12,
+            bollinger_period: 20,
+            bollinger_width: 2.0,
+            rsi_period: 14,
+            macd_fast: 12,
+            macd_slow: 26,
+            macd_signal: 9,
+            atr_period: 14,
+        }
+    }
+}
+
+impl IndicatorSettings {
+    pub fn is_enabled(&self, indicator: Indicator) -> bool {
+        self.enabled.contains(&indicator)
+    }
+
+    pub fn toggled(mut self, indicator: Indicator) -> Self {
+        if self.is_enabled(indicator) {
+            self.enabled.retain(|enabled| *enabled != indicator);
+        } else {
+            self.enabled.push(indicator);
+        }
+        self
+    }
+
+    // Brings hand-edited or stale values back into range; the MACD's fast
+    // average must stay faster than its slow one
+    pub fn normalized(mut self) -> Self {
+        let clamp = |period: usize| period.clamp(MIN_PERIOD, MAX_PERIOD);
+        self.sma_period = clamp(self.sma_period);
+        self.ema_period = clamp(self.ema_period);
+        self.bollinger_period = clamp(self.bollinger_period);
+        self.rsi_period = clamp(self.rsi_period);
+        self.macd_slow = clamp(self.macd_slow).max(MIN_PERIOD + 1);
+        self.macd_fast = clamp(self.macd_fast).min(self.macd_slow - 1);
+        self.macd_signal = clamp(self.macd_signal);
+        self.atr_period = clamp(self.atr_period);
+        self.bollinger_width = if self.bollinger_width.is_finite() {
+            self.bollinger_width.clamp(0.5, 5.0)
+        } else {
+            Self::default().bollinger_width
+        };
+
+        let mut enabled = Vec::new();
+        for indicator in self.enabled {
+            if !enabled.contains(&indicator) {
+                enabled.push(indicator);
+            }
+        }
+        self.enabled = enabled;
+        self
+    }
+}
+
+// Settings start at the defaults and switch to the stored ones once mounted
+#[hook]
+pub fn use_indicator_settings() -> (IndicatorSettings, Callback<IndicatorSettings>) {
+    use_stored_settings(SETTINGS_STORAGE_KEY, IndicatorSettings::normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_clamps_periods_and_keeps_macd_ordered() {
+        let settings = IndicatorSettings {
+            enabled: vec![Indicator::Rsi, Indicator::Sma, Indicator::Rsi],
+            sma_period: 0,
+            rsi_period: 1000,
+            macd_fast: 30,
+            macd_slow: 26,
+            bollinger_width: f64::NAN,
+            ..IndicatorSettings::default()
+        }
+        .normalized();
+
+        assert_eq!(settings.enabled, [Indicator::Rsi, Indicator::Sma]);
+        assert_eq!(settings.sma_period, MIN_PERIOD);
+        assert_eq!(settings.rsi_period, MAX_PERIOD);
+        assert_eq!((settings.macd_fast, settings.macd_slow), (25, 26));
+        assert_eq!(settings.bollinger_width, 2.0);
+    }
+
+    #[test]
+    fn stored_settings_fill_in_missing_fields() {
+        let settings: IndicatorSettings = serde_json::from_str(r#"{ "enabled": ["macd", "vwap"], "rsi_period": 7 }"#).unwrap();
+
+        assert!(settings.is_enabled(Indicator::Macd));
+        assert!(!settings.is_enabled(Indicator::Sma));
+        assert_eq!(settings.rsi_period, 7);
+        assert_eq!(settings.macd_slow, IndicatorSettings::default().macd_slow);
+        assert_eq!(settings.toggled(Indicator::Vwap).enabled, [Indicator::Macd]);
+    }
+}
@@ -0,0 +1,111 @@
+// src/indicators/tests.rs
+use chrono::{Duration, TimeZone, Utc};
+
+use super::*;
+
+fn candle(day: i64, high: f64, low: f64, close: f64, volume: f64) -> Candle {
+    Candle {
+        timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(day),
+        open: close,
+        high,
+        low,
+        close,
+        volume,
+    }
+}
+
+fn candles() -> Vec<Candle> {
+    vec![
+        candle(0, 3.0, 1.0, 2.0, 1.0),
+        candle(1, 6.0, 2.0, 4.0, 3.0),
+        candle(2, 5.0, 4.0, 4.5, 0.0),
+    ]
+}
+
+// Compares series up to floating-point noise
+fn assert_series(actual: &Series, expected: &[Option<f64>]) {
+    assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+    for (actual, expected) in actual.iter().zip(expected) {
+        match (actual, expected) {
+            (Some(actual), Some(expected)) => assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected),
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+    }
+}
+
+// Moving averages
+
+#[test]
+fn sma_averages_each_window() {
+    assert_series(&sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3), &[None, None, Some(2.0), Some(3.0), Some(4.0)]);
+}
+
+#[test]
+fn ema_starts_from_the_sma_and_weights_recent_values() {
+    assert_series(&ema(&[2.0, 4.0, 6.0, 8.0, 12.0], 3), &[None, None, Some(4.0), Some(6.0), Some(9.0)]);
+}
+
+#[test]
+fn short_input_or_zero_period_yields_no_values() {
+    let values = [1.0, 2.0];
+
+    for series in [sma(&values, 3), ema(&values, 3), rsi(&values, 2), sma(&values, 0), atr(&candles(), 4)] {
+        assert!(series.iter().all(Option::is_none));
+    }
+    assert_eq!(sma(&values, 3).len(), 2);
+    assert!(sma(&[], 3).is_empty());
+}
+
+// Oscillators
+
+#[test]
+fn rsi_uses_wilder_smoothing() {
+    let values = [1.0, 2.0, 3.0, 2.0, 4.0];
+
+    assert_series(&rsi(&values, 2), &[None, None, Some(100.0), Some(50.0), Some(100.0 - 100.0 / 6.0)]);
+}
+
+#[test]
+fn rsi_of_a_flat_series_is_neutral() {
+    assert_series(&rsi(&[5.0; 4], 2), &[None, None, Some(50.0), Some(50.0)]);
+}
+
+#[test]
+fn macd_signal_starts_after_the_line() {
+    let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let macd = macd(&values, 2, 3, 2);
+
+    assert_series(&macd.line, &[None, None, Some(0.5), Some(0.5), Some(0.5), Some(0.5)]);
+    assert_series(&macd.signal, &[None, None, None, Some(0.5), Some(0.5), Some(0.5)]);
+    assert_series(&macd.histogram, &[None, None, None, Some(0.0), Some(0.0), Some(0.0)]);
+}
+
+// Bands and volume
+
+#[test]
+fn bollinger_bands_are_standard_deviations_from_the_sma() {
+    let bands = bollinger(&[1.0, 3.0, 5.0], 2, 2.0);
+
+    assert_series(&bands.middle, &[None, Some(2.0), Some(4.0)]);
+    assert_series(&bands.upper, &[None, Some(4.0), Some(6.0)]);
+    assert_series(&bands.lower, &[None, Some(0.0), Some(2.0)]);
+}
+
+#[test]
+fn vwap_weights_typical_prices_by_volume() {
+    let mut candles = candles();
+    assert_series(&vwap(&candles), &[Some(2.0), Some(3.5), Some(3.5)]);
+
+    candles[0].volume = 0.0;
+    assert_eq!(vwap(&candles)[0], None);
+}
+
+#[test]
+fn atr_includes_gaps_from_the_previous_close() {
+    assert_series(&atr(&candles(), 2), &[None, Some(3.0), Some(2.0)]);
+}
+
+#[test]
+fn closes_follow_the_candles() {
+    assert_eq!(closes(&candles()), [2.0, 4.0, 4.5]);
+}
@@ -0,0 +1,14 @@
+// src/lib.rs
+pub mod app;
+pub mod auth;
+pub mod components;
+pub mod config;
+pub mod i18n;
+pub mod indicators;
+pub mod models;
+pub mod pages;
+pub mod risk;
+pub mod services;
+pub mod ssr;
+pub mod store;
+pub mod utils;
@@ -1,17 +1,26 @@
 // src/main.rs
-use yew::prelude::*;
-use yew_router::prelude::*;
-
-mod app;
-mod components;
-mod models;
-mod pages;
-mod services;
-mod store;
-
-use app::App;
+use yew_crypto_tracker::app::{App, AppProps};
+use yew_crypto_tracker::config;
+use yew_crypto_tracker::ssr::InitialState;
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
-    yew::Renderer::<App>::new().render();
+
+    // Pages prerendered by `ssr-server` carry their state and are hydrated
+    match InitialState::from_document() {
+        Some(initial) if initial.is_for_current_page() => {
+            config::install(initial.config.clone());
+            yew::Renderer::<App>::with_props(AppProps { initial: Some(initial) }).hydrate();
+        }
+        stale => {
+            if stale.is_some() {
+                // Rendered for another route; start over on an empty page
+                gloo::utils::body().set_inner_html("");
+            }
+            wasm_bindgen_futures::spawn_local(async {
+                config::install(config::load().await);
+                yew::Renderer::<App>::new().render();
+            });
+        }
+    }
 }
@@ -0,0 +1,287 @@
+// src/models/arbitrage.rs
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::crypto::{Cryptocurrency, MAX_AMOUNT};
+use crate::models::exchange::{Quote, Ticker};
+
+// Costs and thresholds the scanner works with, kept in local storage
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArbitrageSettings {
+    // Charged on both the buy and the sell, in percent
+    pub taker_fee_percent: Decimal,
+    // Flat cost of moving the asset from one exchange to the other, in the
+    // quote currency
+    pub withdrawal_cost: Decimal,
+    // Quote currency spent on the buy
+    pub trade_size: Decimal,
+    // Opportunities returning no more than this after costs are hidden, in percent
+    pub min_profit_percent: Decimal,
+}
+
+impl Default for ArbitrageSettings {
+    fn default() -> Self {
+        Self {
+            taker_fee_percent: Decimal::new(1, 1),
+            withdrawal_cost: Decimal::from(5),
+            trade_size: Decimal::from(10_000),
+            min_profit_percent: Decimal::ZERO,
+        }
+    }
+}
+
+impl ArbitrageSettings {
+    // Brings hand-edited or stale values back into a sensible range
+    pub fn normalized(mut self) -> Self {
+        self.taker_fee_percent = self.taker_fee_percent.clamp(Decimal::ZERO, Decimal::from(5));
+        self.withdrawal_cost = self.withdrawal_cost.clamp(Decimal::ZERO, Decimal::from(MAX_AMOUNT));
+        self.trade_size = self.trade_size.clamp(Decimal::ONE, Decimal::from(MAX_AMOUNT));
+        self.min_profit_percent = self.min_profit_percent.clamp(Decimal::ZERO, Decimal::ONE_HUNDRED);
+        self
+    }
+}
+
+// Buying an asset on one exchange and selling it on another
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opportunity {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub buy_exchange: String,
+    // Best ask on the buying exchange
+    pub buy_price: Decimal,
+    pub sell_exchange: String,
+    // Best bid on the selling exchange
+    pub sell_price: Decimal,
+    // Difference between the two prices before costs, in percent of the buy price
+    pub gross_percent: Decimal,
+    // Left of `trade_size` after fees and the withdrawal, in the quote currency
+    pub profit: Decimal,
+    // `profit` in percent of `trade_size`
+    pub net_percent: Decimal,
+}
+
+// The most profitable pair of exchanges for `crypto`, if any exchange bids
+// more than another asks. Quotes that failed are ignored.
+pub fn best_opportunity(crypto: &Cryptocurrency, quotes: &[Quote], settings: &ArbitrageSettings) -> Option<Opportunity> {
+    let tickers: Vec<(&str, &Ticker)> = quotes
+        .iter()
+        .filter_map(|quote| Some((quote.name.as_str(), quote.ticker.as_ref().ok()?)))
+        .collect();
+    let fee = settings.taker_fee_percent / Decimal::ONE_HUNDRED;
+
+    let mut best: Option<Opportunity> = None;
+    for (buy_index, (buy_exchange, buy)) in tickers.iter().enumerate() {
+        for (sell_index, (sell_exchange, sell)) in tickers.iter().enumerate() {
+            if buy_index == sell_index || sell.bid <= buy.ask || buy.ask.is_zero() {
+                continue;
+            }
+            // Pairs whose prices are too far apart to compute are skipped
+            let Some((gross_percent, profit, net_percent)) = outcome(buy, sell, fee, settings) else {
+                continue;
+            };
+            if best.as_ref().is_some_and(|best| best.profit >= profit) {
+                continue;
+            }
+            best = Some(Opportunity {
+                id: crypto.id.clone(),
+                name: crypto.name.clone(),
+                symbol: crypto.symbol.clone(),
+                buy_exchange: buy_exchange.to_string(),
+                buy_price: buy.ask,
+                sell_exchange: sell_exchange.to_string(),
+                sell_price: sell.bid,
+                gross_percent,
+                profit,
+                net_percent,
+            });
+        }
+    }
+    best
+}
+
+// Spread, profit and net return of buying at `buy` and selling at `sell`;
+// `None` if any of them overflows
+fn outcome(buy: &Ticker, sell: &Ticker, fee: Decimal, settings: &ArbitrageSettings) -> Option<(Decimal, Decimal, Decimal)> {
+    let amount = settings.trade_size.checked_div(buy.ask.checked_mul(Decimal::ONE + fee)?)?;
+    let proceeds = amount.checked_mul(sell.bid)?.checked_mul(Decimal::ONE - fee)?;
+    let profit = proceeds.checked_sub(settings.trade_size)?.checked_sub(settings.withdrawal_cost)?;
+    let gross_percent = (sell.bid - buy.ask).checked_div(buy.ask)?.checked_mul(Decimal::ONE_HUNDRED)?;
+    let net_percent = profit.checked_div(settings.trade_size)?.checked_mul(Decimal::ONE_HUNDRED)?;
+    Some((gross_percent, profit, net_percent))
+}
+
+// Assets worth trading across exchanges given `settings`, best first
+pub fn scan(markets: &[(Cryptocurrency, Vec<Quote>)], settings: &ArbitrageSettings) -> Vec<Opportunity> {
+    let mut opportunities: Vec<Opportunity> = markets
+        .iter()
+        .filter_map(|(crypto, quotes)| best_opportunity(crypto, quotes, settings))
+        .filter(|opportunity| opportunity.net_percent > settings.min_profit_percent)
+        .collect();
+    sort(&mut opportunities, SortKey::Profit, true);
+    opportunities
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Asset,
+    Spread,
+    Profit,
+}
+
+pub fn sort(opportunities: &mut [Opportunity], key: SortKey, descending: bool) {
+    opportunities.sort_by(|a, b| {
+        let order = match key {
+            SortKey::Asset => a.name.cmp(&b.name),
+            SortKey::Spread => a.gross_percent.cmp(&b.gross_percent),
+            SortKey::Profit => a.profit.cmp(&b.profit),
+        };
+        let order = if descending { order.reverse() } else { order };
+        order.then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn crypto(id: &str) -> Cryptocurrency {
+        Cryptocurrency {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            symbol: id[..3].to_uppercase(),
+            price: dec!(100),
+            market_cap: 0.0,
+            volume_24h: 0.0,
+            price_change_24h: 0.0,
+            decimals: 8,
+        }
+    }
+
+    fn quote(exchange: &str, bid: Decimal, ask: Decimal) -> Quote {
+        Quote {
+            exchange: exchange.to_lowercase(),
+            name: exchange.to_string(),
+            ticker: Ok(Ticker {
+                bid,
+                ask,
+                last: bid,
+                volume_24h: 1.0,
+            }),
+        }
+    }
+
+    fn free() -> ArbitrageSettings {
+        ArbitrageSettings {
+            taker_fee_percent: Decimal::ZERO,
+            withdrawal_cost: Decimal::ZERO,
+            trade_size: dec!(1000),
+            min_profit_percent: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn buys_at_the_lowest_ask_and_sells_at_the_highest_bid() {
+        let quotes = [
+            quote("A", dec!(99), dec!(100)),
+            quote("B", dec!(104), dec!(105)),
+            quote("C", dec!(102), dec!(103)),
+        ];
+        let opportunity = best_opportunity(&crypto("bitcoin"), &quotes, &free()).unwrap();
+
+        assert_eq!((opportunity.buy_exchange.as_str(), opportunity.sell_exchange.as_str()), ("A", "B"));
+        assert_eq!(opportunity.gross_percent, dec!(4));
+        assert_eq!(opportunity.profit, dec!(40));
+        assert_eq!(opportunity.net_percent, dec!(4));
+    }
+
+    #[test]
+    fn fees_and_withdrawal_come_off_the_profit() {
+        let quotes = [quote("A", dec!(99), dec!(100)), quote("B", dec!(102), dec!(103))];
+        let settings = ArbitrageSettings {
+            taker_fee_percent: dec!(0.5),
+            withdrawal_cost: dec!(5),
+            ..free()
+        };
+        let opportunity = best_opportunity(&crypto("bitcoin"), &quotes, &settings).unwrap();
+
+        // 1000 / 100.5 units sold at 101.49 each, less the withdrawal
+        let expected = dec!(1000) / dec!(100.5) * dec!(101.49) - dec!(1005);
+        assert!((opportunity.profit - expected).abs() < dec!(0.000001));
+        assert!(opportunity.net_percent < opportunity.gross_percent);
+    }
+
+    #[test]
+    fn crossing_is_needed_and_failed_quotes_are_ignored() {
+        let mut quotes = vec![quote("A", dec!(99), dec!(101)), quote("B", dec!(100), dec!(102))];
+        assert!(best_opportunity(&crypto("bitcoin"), &quotes, &free()).is_none());
+
+        quotes.push(Quote {
+            exchange: "c".to_string(),
+            name: "C".to_string(),
+            ticker: Err("Request failed: 503".to_string()),
+        });
+        assert!(best_opportunity(&crypto("bitcoin"), &quotes, &free()).is_none());
+    }
+
+    #[test]
+    fn scan_keeps_opportunities_above_the_threshold_best_first() {
+        let markets = vec![
+            (crypto("bitcoin"), vec![quote("A", dec!(99), dec!(100)), quote("B", dec!(101), dec!(102))]),
+            (crypto("ethereum"), vec![quote("A", dec!(99), dec!(100)), quote("B", dec!(103), dec!(104))]),
+            (crypto("solana"), vec![quote("A", dec!(99), dec!(100)), quote("B", dec!(100.5), dec!(101))]),
+        ];
+        let settings = ArbitrageSettings {
+            min_profit_percent: dec!(0.5),
+            ..free()
+        };
+
+        let ids: Vec<String> = scan(&markets, &settings).into_iter().map(|opportunity| opportunity.id).collect();
+        assert_eq!(ids, ["ethereum", "bitcoin"]);
+
+        let mut opportunities = scan(&markets, &free());
+        sort(&mut opportunities, SortKey::Asset, false);
+        assert_eq!(opportunities[0].id, "bitcoin");
+        assert_eq!(opportunities[2].id, "solana");
+    }
+
+    #[test]
+    fn normalized_keeps_costs_in_range() {
+        let settings = ArbitrageSettings {
+            taker_fee_percent: dec!(-1),
+            withdrawal_cost: dec!(-3),
+            trade_size: Decimal::ZERO,
+            min_profit_percent: dec!(500),
+        }
+        .normalized();
+
+        assert_eq!(settings.taker_fee_percent, Decimal::ZERO);
+        assert_eq!(settings.withdrawal_cost, Decimal::ZERO);
+        assert_eq!(settings.trade_size, Decimal::ONE);
+        assert_eq!(settings.min_profit_percent, Decimal::ONE_HUNDRED);
+
+        let settings = ArbitrageSettings {
+            withdrawal_cost: Decimal::MAX,
+            trade_size: Decimal::MAX,
+            ..free()
+        }
+        .normalized();
+        assert_eq!(settings.withdrawal_cost, Decimal::from(MAX_AMOUNT));
+        assert_eq!(settings.trade_size, Decimal::from(MAX_AMOUNT));
+    }
+
+    #[test]
+    fn pairs_that_would_overflow_are_skipped() {
+        let quotes = [
+            quote("A", dec!(0.0000000000000000000001), dec!(0.0000000000000000000002)),
+            quote("B", dec!(1000000000), dec!(1000000001)),
+        ];
+        let settings = ArbitrageSettings {
+            trade_size: Decimal::from(MAX_AMOUNT),
+            ..free()
+        };
+        assert!(best_opportunity(&crypto("bitcoin"), &quotes, &settings).is_none());
+    }
+}
@@ -0,0 +1,94 @@
+// src/models/auth.rs
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// Access tokens are refreshed this long before they actually expire
+const REFRESH_MARGIN_SECS: i64 = 30;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+}
+
+// Body of `/auth/login` and `/auth/refresh` responses
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    // Lifetime of the access token in seconds
+    pub expires_in: i64,
+    pub user: User,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+    pub user: User,
+}
+
+impl Session {
+    // `None` when `expires_in` is too far off to be a real lifetime
+    pub fn from_tokens(tokens: TokenResponse, now: DateTime<Utc>) -> Option<Self> {
+        let expires_at = now.checked_add_signed(Duration::try_seconds(tokens.expires_in)?)?;
+        Some(Self {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_at,
+            user: tokens.user,
+        })
+    }
+
+    pub fn expires_soon(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now < Duration::seconds(REFRESH_MARGIN_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn tokens(expires_in: i64) -> TokenResponse {
+        TokenResponse {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_in,
+            user: User {
+                id: "demo".to_string(),
+                name: "Demo User".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn session_expiry_is_relative_to_issue_time() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let session = Session::from_tokens(tokens(900), now).unwrap();
+
+        assert_eq!(session.expires_at, Utc.with_ymd_and_hms(2025, 1, 1, 12, 15, 0).unwrap());
+        assert!(!session.expires_soon(now));
+    }
+
+    #[test]
+    fn sessions_are_refreshed_before_expiry() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let session = Session::from_tokens(tokens(900), now).unwrap();
+
+        assert!(!session.expires_soon(now + Duration::seconds(869)));
+        assert!(session.expires_soon(now + Duration::seconds(871)));
+        assert!(session.expires_soon(now + Duration::seconds(1000)));
+    }
+
+    #[test]
+    fn out_of_range_lifetimes_are_rejected() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(Session::from_tokens(tokens(i64::MAX), now), None);
+        assert_eq!(Session::from_tokens(tokens(i64::MIN), now), None);
+        // Within `Duration`'s range but past the last representable date
+        assert_eq!(Session::from_tokens(tokens(i64::MAX / 1000), now), None);
+    }
+}
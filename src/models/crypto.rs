@@ -1,20 +1,111 @@
 
 // src/models/crypto.rs
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+// Precision used for assets that don't report their own
+pub const DEFAULT_ASSET_DECIMALS: u32 = 8;
+// Upper bound for an entered quantity or fiat amount, well below where
+// multiplying it by a price could overflow a `Decimal`
+pub const MAX_AMOUNT: i64 = 1_000_000_000_000_000;
+
+// Monetary and quantity fields are `Decimal`s serialized as strings. Payloads
+// persisted before the switch stored plain JSON numbers; those still
+// deserialize and are rounded to the asset's precision when they enter the
+// store, or once the asset's market data is loaded if it isn't yet.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Cryptocurrency {
     pub id: String,
     pub name: String,
     pub symbol: String,
-    pub price: f64,
+    pub price: Decimal,
     pub market_cap: f64,
     pub volume_24h: f64,
     pub price_change_24h: f64,
+    // Number of fractional digits a quantity of this asset can hold
+    #[serde(default = "default_asset_decimals")]
+    pub decimals: u32,
+}
+
+impl Cryptocurrency {
+    pub fn normalize_amount(&self, amount: Decimal) -> Decimal {
+        amount.round_dp(self.decimals).normalize()
+    }
+
+    pub fn value_of(&self, amount: Decimal) -> Decimal {
+        self.price * amount
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PortfolioItem {
     pub crypto_id: String,
-    pub amount: f64,
+    pub amount: Decimal,
+}
+
+// The portfolio as stored on the server. `revision` goes up with every
+// accepted write; clients send the revision they started from so the server
+// can reject writes based on stale data.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioDocument {
+    pub revision: u64,
+    pub items: Vec<PortfolioItem>,
+}
+
+fn default_asset_decimals() -> u32 {
+    DEFAULT_ASSET_DECIMALS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn amounts_serialize_as_strings() {
+        let item = PortfolioItem {
+            crypto_id: "bitcoin".to_string(),
+            amount: dec!(0.00000001),
+        };
+
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(json, r#"{"crypto_id":"bitcoin","amount":"0.00000001"}"#);
+        assert_eq!(serde_json::from_str::<PortfolioItem>(&json).unwrap(), item);
+    }
+
+    #[test]
+    fn legacy_numeric_amounts_deserialize() {
+        let item: PortfolioItem =
+            serde_json::from_str(r#"{"crypto_id":"ethereum","amount":0.1}"#).unwrap();
+        assert_eq!(item.amount, dec!(0.1));
+    }
+
+    #[test]
+    fn legacy_cryptocurrency_defaults_precision() {
+        let crypto: Cryptocurrency = serde_json::from_str(
+            r#"{"id":"bitcoin","name":"Bitcoin","symbol":"BTC","price":63542.87,
+                "market_cap":1.0,"volume_24h":1.0,"price_change_24h":0.0}"#,
+        )
+        .unwrap();
+        assert_eq!(crypto.price, dec!(63542.87));
+        assert_eq!(crypto.decimals, DEFAULT_ASSET_DECIMALS);
+    }
+
+    #[test]
+    fn normalize_amount_rounds_to_asset_precision() {
+        let crypto = Cryptocurrency {
+            id: "cardano".to_string(),
+            name: "Cardano".to_string(),
+            symbol: "ADA".to_string(),
+            price: dec!(0.93),
+            market_cap: 0.0,
+            volume_24h: 0.0,
+            price_change_24h: 0.0,
+            decimals: 6,
+        };
+
+        assert_eq!(crypto.normalize_amount(dec!(1.23456789)), dec!(1.234568));
+        assert_eq!(crypto.normalize_amount(dec!(2.500000)), dec!(2.5));
+        assert_eq!(crypto.value_of(dec!(10)), dec!(9.30));
+    }
 }
@@ -0,0 +1,232 @@
+// src/models/exchange.rs
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Sources further than this from the median price are left out of the
+// aggregate, in percent
+pub const OUTLIER_PERCENT: Decimal = Decimal::TWO;
+// With fewer sources there is no telling which one is off
+const MIN_SOURCES_FOR_OUTLIERS: usize = 3;
+
+// Top of the book and daily volume on one exchange
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ticker {
+    pub bid: Decimal,
+    pub ask: Decimal,
+    pub last: Decimal,
+    // In the quote currency, like `Cryptocurrency::volume_24h`
+    pub volume_24h: f64,
+}
+
+impl Ticker {
+    pub fn spread(&self) -> Decimal {
+        self.ask - self.bid
+    }
+
+    // Spread as a percentage of the mid price
+    pub fn spread_percent(&self) -> Option<Decimal> {
+        let mid = (self.ask + self.bid) / Decimal::TWO;
+        (!mid.is_zero()).then(|| self.spread() / mid * Decimal::ONE_HUNDRED)
+    }
+}
+
+// What one exchange answered, or why it didn't
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub exchange: String,
+    pub name: String,
+    pub ticker: Result<Ticker, String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SourceStatus {
+    Included,
+    // Too far from the other exchanges to be trusted
+    Outlier,
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Source {
+    pub exchange: String,
+    pub name: String,
+    pub ticker: Option<Ticker>,
+    // Distance of the last price from the median of all sources, in percent
+    pub deviation_percent: Option<Decimal>,
+    pub status: SourceStatus,
+}
+
+// Volume-weighted price across exchanges and how each one contributed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aggregate {
+    // `None` when no exchange answered
+    pub price: Option<Decimal>,
+    pub sources: Vec<Source>,
+}
+
+impl Aggregate {
+    pub fn included(&self) -> impl Iterator<Item = &Source> {
+        self.sources.iter().filter(|source| source.status == SourceStatus::Included)
+    }
+}
+
+// Weighs each exchange's last price by its volume, leaving out failed
+// sources and, given enough of them, ones far from the median price.
+// Exchanges reporting no volume at all are averaged evenly.
+pub fn aggregate(quotes: Vec<Quote>) -> Aggregate {
+    let mut lasts: Vec<Decimal> = quotes
+        .iter()
+        .filter_map(|quote| quote.ticker.as_ref().ok())
+        .map(|ticker| ticker.last)
+        .collect();
+    lasts.sort();
+    let median = match lasts.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(lasts[len / 2]),
+        len => Some((lasts[len / 2 - 1] + lasts[len / 2]) / Decimal::TWO),
+    };
+    let flag_outliers = lasts.len() >= MIN_SOURCES_FOR_OUTLIERS;
+
+    let sources: Vec<Source> = quotes
+        .into_iter()
+        .map(|quote| match quote.ticker {
+            Ok(ticker) => {
+                let deviation_percent = median
+                    .filter(|median| !median.is_zero())
+                    .map(|median| (ticker.last - median) / median * Decimal::ONE_HUNDRED);
+                let outlier = flag_outliers && deviation_percent.is_some_and(|deviation| deviation.abs() > OUTLIER_PERCENT);
+                Source {
+                    exchange: quote.exchange,
+                    name: quote.name,
+                    ticker: Some(ticker),
+                    deviation_percent,
+                    status: if outlier { SourceStatus::Outlier } else { SourceStatus::Included },
+                }
+            }
+            Err(error) => Source {
+                exchange: quote.exchange,
+                name: quote.name,
+                ticker: None,
+                deviation_percent: None,
+                status: SourceStatus::Failed(error),
+            },
+        })
+        .collect();
+
+    let included: Vec<&Ticker> = sources
+        .iter()
+        .filter(|source| source.status == SourceStatus::Included)
+        .filter_map(|source| source.ticker.as_ref())
+        .collect();
+    let price = if included.is_empty() {
+        None
+    } else {
+        volume_weighted(&included).or_else(|| {
+            let sum = included.iter().try_fold(Decimal::ZERO, |sum, ticker| sum.checked_add(ticker.last))?;
+            Some(sum / Decimal::from(included.len()))
+        })
+    };
+
+    Aggregate { price, sources }
+}
+
+// `None` without any volume, or when the sums don't fit a `Decimal`
+fn volume_weighted(tickers: &[&Ticker]) -> Option<Decimal> {
+    let mut volume = Decimal::ZERO;
+    let mut weighted = Decimal::ZERO;
+    for ticker in tickers {
+        let weight = Decimal::from_f64(ticker.volume_24h.max(0.0)).unwrap_or_default();
+        volume = volume.checked_add(weight)?;
+        weighted = weighted.checked_add(ticker.last.checked_mul(weight)?)?;
+    }
+    weighted.checked_div(volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn quote(exchange: &str, last: Decimal, volume_24h: f64) -> Quote {
+        Quote {
+            exchange: exchange.to_string(),
+            name: exchange.to_uppercase(),
+            ticker: Ok(Ticker {
+                bid: last - dec!(1),
+                ask: last + dec!(1),
+                last,
+                volume_24h,
+            }),
+        }
+    }
+
+    fn failed(exchange: &str) -> Quote {
+        Quote {
+            exchange: exchange.to_string(),
+            name: exchange.to_uppercase(),
+            ticker: Err("Request failed: 503".to_string()),
+        }
+    }
+
+    #[test]
+    fn price_is_weighted_by_volume() {
+        let aggregate = aggregate(vec![quote("a", dec!(100), 3.0), quote("b", dec!(104), 1.0)]);
+
+        assert_eq!(aggregate.price, Some(dec!(101)));
+        assert_eq!(aggregate.included().count(), 2);
+        // Measured from the median, halfway between the two
+        let deviations: Vec<_> = aggregate.sources.iter().map(|source| source.deviation_percent.unwrap()).collect();
+        assert_eq!(deviations[0], -deviations[1]);
+        assert!(deviations[1] > Decimal::ZERO);
+    }
+
+    #[test]
+    fn weighting_keeps_every_digit_of_the_price() {
+        // More digits than an `f64` holds
+        let price = dec!(12345.678901234567891);
+        let aggregate = aggregate(vec![quote("a", price, 0.3), quote("b", price, 0.7)]);
+
+        assert_eq!(aggregate.price, Some(price));
+    }
+
+    #[test]
+    fn outliers_are_flagged_and_left_out() {
+        let aggregate = aggregate(vec![
+            quote("a", dec!(100), 1.0),
+            quote("b", dec!(101), 1.0),
+            quote("c", dec!(110), 50.0),
+        ]);
+
+        assert_eq!(aggregate.sources[2].status, SourceStatus::Outlier);
+        assert_eq!(aggregate.price, Some(dec!(100.5)));
+    }
+
+    #[test]
+    fn two_disagreeing_sources_are_both_kept() {
+        let aggregate = aggregate(vec![quote("a", dec!(100), 1.0), quote("b", dec!(120), 1.0)]);
+
+        assert_eq!(aggregate.included().count(), 2);
+        assert_eq!(aggregate.price, Some(dec!(110)));
+    }
+
+    #[test]
+    fn failed_sources_degrade_to_the_rest() {
+        let aggregate = aggregate(vec![failed("a"), quote("b", dec!(50), 0.0), quote("c", dec!(52), 0.0)]);
+
+        assert_eq!(aggregate.sources[0].status, SourceStatus::Failed("Request failed: 503".to_string()));
+        assert_eq!(aggregate.price, Some(dec!(51)));
+
+        let none = super::aggregate(vec![failed("a")]);
+        assert_eq!(none.price, None);
+        assert_eq!(none.included().count(), 0);
+    }
+
+    #[test]
+    fn ticker_spread_is_relative_to_the_mid_price() {
+        let ticker = quote("a", dec!(100), 1.0).ticker.unwrap();
+
+        assert_eq!(ticker.spread(), dec!(2));
+        assert_eq!(ticker.spread_percent(), Some(dec!(2)));
+    }
+}
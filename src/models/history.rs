@@ -0,0 +1,15 @@
+// src/models/history.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// One OHLCV bar of an asset's price history. Prices here feed charts and
+// statistics rather than balances, so they stay `f64`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub timestamp: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
@@ -0,0 +1,280 @@
+// src/models/income.rs
+//
+// Rewards received for holding an asset rather than bought. Each event keeps
+// its fiat value on the day it arrived, which is what counts as ordinary
+// income; later price moves only change the value of the holding.
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncomeKind {
+    Staking,
+    Interest,
+    Airdrop,
+    Mining,
+}
+
+impl IncomeKind {
+    pub const ALL: [IncomeKind; 4] = [IncomeKind::Staking, IncomeKind::Interest, IncomeKind::Airdrop, IncomeKind::Mining];
+
+    // Used in storage, translation keys and element values
+    pub fn code(self) -> &'static str {
+        match self {
+            IncomeKind::Staking => "staking",
+            IncomeKind::Interest => "interest",
+            IncomeKind::Airdrop => "airdrop",
+            IncomeKind::Mining => "mining",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+
+    // Paid in proportion to what is held, so it says something about yield.
+    // Airdrops don't.
+    pub fn is_yield(self) -> bool {
+        self != IncomeKind::Airdrop
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IncomeEvent {
+    // Assigned by the store, unique among the recorded events
+    #[serde(default)]
+    pub id: u64,
+    pub crypto_id: String,
+    pub kind: IncomeKind,
+    pub amount: Decimal,
+    // Fiat value when received
+    pub value: Decimal,
+    pub received_on: NaiveDate,
+    // `WatchedWallet::key` of the wallet it was paid into, whose balance
+    // then already includes it
+    #[serde(default)]
+    pub wallet: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    #[default]
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Period {
+    pub const ALL: [Period; 3] = [Period::Month, Period::Quarter, Period::Year];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Period::Month => "month",
+            Period::Quarter => "quarter",
+            Period::Year => "year",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|period| period.code() == code)
+    }
+
+    // First day of the period `date` falls in
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        let month = match self {
+            Period::Month => date.month(),
+            Period::Quarter => (date.month0() / 3) * 3 + 1,
+            Period::Year => 1,
+        };
+        NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+    }
+}
+
+// Income received within one period
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodIncome {
+    pub start: NaiveDate,
+    pub total: Decimal,
+    // Kinds with income this period, in `IncomeKind::ALL` order
+    pub by_kind: Vec<(IncomeKind, Decimal)>,
+    pub events: usize,
+}
+
+// Totals per period, newest first; periods without income are left out
+pub fn summarize(events: &[IncomeEvent], period: Period) -> Vec<PeriodIncome> {
+    let mut periods: Vec<PeriodIncome> = Vec::new();
+    for event in events {
+        let start = period.start(event.received_on);
+        let index = match periods.iter().position(|summary| summary.start == start) {
+            Some(index) => index,
+            None => {
+                periods.push(PeriodIncome {
+                    start,
+                    total: Decimal::ZERO,
+                    by_kind: Vec::new(),
+                    events: 0,
+                });
+                periods.len() - 1
+            }
+        };
+        let summary = &mut periods[index];
+        summary.total += event.value;
+        summary.events += 1;
+        match summary.by_kind.iter_mut().find(|(kind, _)| *kind == event.kind) {
+            Some((_, value)) => *value += event.value,
+            None => {
+                summary.by_kind.push((event.kind, event.value));
+                summary.by_kind.sort_by_key(|(kind, _)| *kind);
+            }
+        }
+    }
+    periods.sort_by_key(|summary| std::cmp::Reverse(summary.start));
+    periods
+}
+
+// Yearly totals by kind and overall as CSV, oldest year first, valued when
+// received: the figures to report as income
+pub fn yearly_csv(events: &[IncomeEvent]) -> String {
+    let mut csv = String::from("year,kind,value\n");
+    for year in summarize(events, Period::Year).iter().rev() {
+        let label = year.start.year();
+        for (kind, value) in &year.by_kind {
+            csv.push_str(&format!("{},{},{:.2}\n", label, kind.code(), value));
+        }
+        csv.push_str(&format!("{},total,{:.2}\n", label, year.total));
+    }
+    csv
+}
+
+// Total amount of an asset received as income of any kind
+pub fn received(events: &[IncomeEvent], crypto_id: &str) -> Decimal {
+    events.iter().filter(|event| event.crypto_id == crypto_id).map(|event| event.amount).sum()
+}
+
+// Annual percentage yield implied by an asset's rewards, compounded: how much
+// the rewards after the first grew the position between the first and the
+// last, scaled to a year. The first reward only marks where the window starts,
+// as it was earned before it. `held` is the current amount, rewards included.
+// `None` with fewer than two rewards on different days, or when the rewards
+// are all there is.
+pub fn estimate_apy(events: &[IncomeEvent], crypto_id: &str, held: Decimal) -> Option<f64> {
+    let mut rewards: Vec<&IncomeEvent> = events
+        .iter()
+        .filter(|event| event.crypto_id == crypto_id && event.kind.is_yield())
+        .collect();
+    rewards.sort_by_key(|event| event.received_on);
+    let (first, last) = (rewards.first()?, rewards.last()?);
+    let days = (last.received_on - first.received_on).num_days();
+    if days <= 0 {
+        return None;
+    }
+    let earned: Decimal = rewards[1..].iter().map(|event| event.amount).sum();
+    let before = held - earned;
+    if before <= Decimal::ZERO {
+        return None;
+    }
+    let growth = (held / before).to_f64()?;
+    Some(growth.powf(365.0 / days as f64) - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn event(crypto_id: &str, kind: IncomeKind, amount: Decimal, value: Decimal, received_on: &str) -> IncomeEvent {
+        IncomeEvent {
+            id: 0,
+            crypto_id: crypto_id.to_string(),
+            kind,
+            amount,
+            value,
+            received_on: received_on.parse().unwrap(),
+            wallet: None,
+        }
+    }
+
+    #[test]
+    fn periods_start_on_their_first_day() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 17).unwrap();
+        assert_eq!(Period::Month.start(date), NaiveDate::from_ymd_opt(2024, 8, 1).unwrap());
+        assert_eq!(Period::Quarter.start(date), NaiveDate::from_ymd_opt(2024, 7, 1).unwrap());
+        assert_eq!(Period::Year.start(date), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert_eq!(Period::from_code("quarter"), Some(Period::Quarter));
+        assert_eq!(IncomeKind::from_code("mining"), Some(IncomeKind::Mining));
+    }
+
+    #[test]
+    fn summaries_total_fiat_value_at_receipt_per_period() {
+        let events = [
+            event("ethereum", IncomeKind::Staking, dec!(0.01), dec!(25), "2024-01-31"),
+            event("cardano", IncomeKind::Airdrop, dec!(100), dec!(40), "2024-02-02"),
+            event("ethereum", IncomeKind::Staking, dec!(0.01), dec!(30), "2024-02-29"),
+            event("bitcoin", IncomeKind::Interest, dec!(0.0001), dec!(6), "2023-12-31"),
+        ];
+
+        let months = summarize(&events, Period::Month);
+        assert_eq!(months.iter().map(|summary| summary.start.to_string()).collect::<Vec<_>>(), [
+            "2024-02-01",
+            "2024-01-01",
+            "2023-12-01"
+        ]);
+        assert_eq!(months[0].total, dec!(70));
+        assert_eq!(months[0].events, 2);
+        assert_eq!(months[0].by_kind, [(IncomeKind::Staking, dec!(30)), (IncomeKind::Airdrop, dec!(40))]);
+
+        let years = summarize(&events, Period::Year);
+        assert_eq!(years.len(), 2);
+        assert_eq!(years[0].total, dec!(95));
+        assert_eq!(years[1].total, dec!(6));
+        assert!(summarize(&[], Period::Year).is_empty());
+    }
+
+    #[test]
+    fn yearly_csv_lists_each_kind_and_the_total_per_year() {
+        let events = [
+            event("ethereum", IncomeKind::Staking, dec!(0.01), dec!(25), "2024-01-31"),
+            event("cardano", IncomeKind::Airdrop, dec!(100), dec!(40.5), "2024-02-02"),
+            event("bitcoin", IncomeKind::Interest, dec!(0.0001), dec!(6), "2023-12-31"),
+        ];
+        assert_eq!(
+            yearly_csv(&events),
+            "year,kind,value\n2023,interest,6.00\n2023,total,6.00\n2024,staking,25.00\n2024,airdrop,40.50\n2024,total,65.50\n"
+        );
+        assert_eq!(yearly_csv(&[]), "year,kind,value\n");
+    }
+
+    #[test]
+    fn apy_compounds_the_growth_between_the_first_and_last_reward() {
+        // 1% a month for two months on 100 held before
+        let events = [
+            event("ethereum", IncomeKind::Staking, dec!(1), dec!(2000), "2024-01-01"),
+            event("ethereum", IncomeKind::Staking, dec!(1), dec!(2000), "2024-01-31"),
+            event("ethereum", IncomeKind::Staking, dec!(1.01), dec!(2000), "2024-03-01"),
+            event("ethereum", IncomeKind::Airdrop, dec!(50), dec!(2000), "2024-02-01"),
+            event("bitcoin", IncomeKind::Staking, dec!(1), dec!(2000), "2024-02-01"),
+        ];
+        let held = dec!(102.01);
+        let apy = estimate_apy(&events, "ethereum", held).unwrap();
+        let expected = 1.0201_f64.powf(365.0 / 60.0) - 1.0;
+        assert!((apy - expected).abs() < 1e-12, "{}", apy);
+
+        assert_eq!(estimate_apy(&events, "bitcoin", dec!(10)), None);
+        assert_eq!(estimate_apy(&events, "ethereum", dec!(2)), None);
+        assert_eq!(estimate_apy(&events, "cardano", dec!(10)), None);
+        assert_eq!(received(&events, "ethereum"), dec!(53.01));
+    }
+
+    #[test]
+    fn events_round_trip_through_storage() {
+        let stored = event("ethereum", IncomeKind::Staking, dec!(0.015), dec!(30.5), "2024-02-29");
+        let json = serde_json::to_string(&stored).unwrap();
+
+        assert!(json.contains(r#""kind":"staking""#));
+        assert!(json.contains(r#""amount":"0.015""#));
+        assert_eq!(serde_json::from_str::<IncomeEvent>(&json).unwrap(), stored);
+    }
+}
@@ -0,0 +1,239 @@
+// src/models/market.rs
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: Decimal,
+    pub amount: Decimal,
+}
+
+// Level-2 order book snapshot. Bids are sorted best (highest) first, asks
+// best (lowest) first. `sequence` is the last update the snapshot includes.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub sequence: u64,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+// Changed levels since the previous update; an amount of zero removes the level
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BookUpdate {
+    pub sequence: u64,
+    #[serde(default)]
+    pub bids: Vec<BookLevel>,
+    #[serde(default)]
+    pub asks: Vec<BookLevel>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub id: u64,
+    pub price: Decimal,
+    pub amount: Decimal,
+    // Side of the order that took liquidity
+    pub side: Side,
+    pub timestamp: DateTime<Utc>,
+}
+
+// An update arrived out of order, so some were missed and the book has to be
+// reloaded from a snapshot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub expected: u64,
+    pub received: u64,
+}
+
+impl fmt::Display for SequenceGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected book update {}, got {}", self.expected, self.received)
+    }
+}
+
+impl std::error::Error for SequenceGap {}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<&BookLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&BookLevel> {
+        self.asks.first()
+    }
+
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    pub fn mid_price(&self) -> Option<Decimal> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / Decimal::TWO)
+    }
+
+    // Spread as a percentage of the mid price
+    pub fn spread_percent(&self) -> Option<Decimal> {
+        let mid = self.mid_price().filter(|mid| !mid.is_zero())?;
+        Some(self.spread()? / mid * Decimal::ONE_HUNDRED)
+    }
+
+    // Applies updates in order. Ones the book already includes are skipped;
+    // a missing sequence number stops at the gap and leaves the rest unapplied.
+    pub fn apply(&mut self, updates: &[BookUpdate]) -> Result<(), SequenceGap> {
+        for update in updates {
+            if update.sequence <= self.sequence {
+                continue;
+            }
+            if update.sequence != self.sequence + 1 {
+                return Err(SequenceGap {
+                    expected: self.sequence + 1,
+                    received: update.sequence,
+                });
+            }
+            for level in &update.bids {
+                upsert(&mut self.bids, level, |a, b| a > b);
+            }
+            for level in &update.asks {
+                upsert(&mut self.asks, level, |a, b| a < b);
+            }
+            self.sequence = update.sequence;
+        }
+        Ok(())
+    }
+
+    // Running totals from the best price outwards, as drawn by a depth chart
+    pub fn cumulative_bids(&self) -> Vec<BookLevel> {
+        cumulative(&self.bids)
+    }
+
+    pub fn cumulative_asks(&self) -> Vec<BookLevel> {
+        cumulative(&self.asks)
+    }
+}
+
+// Replaces, inserts or (with a zero amount) removes the level at `level.price`,
+// keeping `levels` sorted best first by `better`
+fn upsert(levels: &mut Vec<BookLevel>, level: &BookLevel, better: fn(&Decimal, &Decimal) -> bool) {
+    let position = levels.iter().position(|existing| !better(&existing.price, &level.price));
+    match position {
+        Some(index) if levels[index].price == level.price => {
+            if level.amount.is_zero() {
+                levels.remove(index);
+            } else {
+                levels[index].amount = level.amount;
+            }
+        }
+        _ if level.amount.is_zero() => {}
+        Some(index) => levels.insert(index, level.clone()),
+        None => levels.push(level.clone()),
+    }
+}
+
+// Running totals of `levels`, which are sorted best first
+pub fn cumulative(levels: &[BookLevel]) -> Vec<BookLevel> {
+    let mut total = Decimal::ZERO;
+    levels
+        .iter()
+        .map(|level| {
+            total += level.amount;
+            BookLevel {
+                price: level.price,
+                amount: total,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, amount: Decimal) -> BookLevel {
+        BookLevel { price, amount }
+    }
+
+    fn book() -> OrderBook {
+        OrderBook {
+            sequence: 10,
+            bids: vec![level(dec!(99), dec!(1)), level(dec!(98), dec!(2))],
+            asks: vec![level(dec!(101), dec!(1.5)), level(dec!(103), dec!(0.5))],
+        }
+    }
+
+    fn update(sequence: u64, bids: Vec<BookLevel>, asks: Vec<BookLevel>) -> BookUpdate {
+        BookUpdate { sequence, bids, asks }
+    }
+
+    #[test]
+    fn spread_and_mid_price_come_from_the_best_levels() {
+        let book = book();
+
+        assert_eq!(book.spread(), Some(dec!(2)));
+        assert_eq!(book.mid_price(), Some(dec!(100)));
+        assert_eq!(book.spread_percent(), Some(dec!(2)));
+        assert_eq!(OrderBook::default().spread(), None);
+    }
+
+    #[test]
+    fn updates_insert_replace_and_remove_levels() {
+        let mut book = book();
+        let updates = [
+            update(11, vec![level(dec!(99.5), dec!(3)), level(dec!(98), dec!(0))], vec![level(dec!(102), dec!(4))]),
+            update(12, vec![level(dec!(97), dec!(1))], vec![level(dec!(101), dec!(0)), level(dec!(103), dec!(2))]),
+        ];
+
+        book.apply(&updates).unwrap();
+
+        assert_eq!(book.sequence, 12);
+        assert_eq!(book.bids, [level(dec!(99.5), dec!(3)), level(dec!(99), dec!(1)), level(dec!(97), dec!(1))]);
+        assert_eq!(book.asks, [level(dec!(102), dec!(4)), level(dec!(103), dec!(2))]);
+    }
+
+    #[test]
+    fn stale_updates_are_skipped_and_gaps_reported() {
+        let mut book = book();
+        let removal = update(9, vec![level(dec!(99), dec!(0))], Vec::new());
+        book.apply(&[removal]).unwrap();
+        assert_eq!(book, self::book());
+
+        let gap = book.apply(&[update(11, Vec::new(), Vec::new()), update(13, Vec::new(), Vec::new())]);
+        assert_eq!(gap, Err(SequenceGap { expected: 12, received: 13 }));
+        assert_eq!(book.sequence, 11);
+    }
+
+    #[test]
+    fn removing_a_missing_level_changes_nothing() {
+        let mut book = book();
+        book.apply(&[update(11, vec![level(dec!(50), dec!(0))], vec![level(dec!(200), dec!(0))])]).unwrap();
+
+        assert_eq!(book.bids, self::book().bids);
+        assert_eq!(book.asks, self::book().asks);
+    }
+
+    #[test]
+    fn cumulative_levels_add_up_from_the_best_price() {
+        let book = book();
+
+        assert_eq!(book.cumulative_bids(), [level(dec!(99), dec!(1)), level(dec!(98), dec!(3))]);
+        assert_eq!(book.cumulative_asks(), [level(dec!(101), dec!(1.5)), level(dec!(103), dec!(2))]);
+    }
+
+    #[test]
+    fn trades_use_lowercase_sides() {
+        let json = r#"{"id":7,"price":"100.5","amount":"0.25","side":"sell","timestamp":"2024-01-01T00:00:00Z"}"#;
+        let trade: Trade = serde_json::from_str(json).unwrap();
+
+        assert_eq!(trade.side, Side::Sell);
+        assert_eq!(trade.price, dec!(100.5));
+    }
+}
@@ -1,2 +1,12 @@
 // src/models/mod.rs
-pub mod crypto;
\ No newline at end of file
+pub mod arbitrage;
+pub mod auth;
+pub mod crypto;
+pub mod exchange;
+pub mod history;
+pub mod income;
+pub mod market;
+pub mod rebalance;
+pub mod simulation;
+pub mod token;
+pub mod wallet;
@@ -0,0 +1,336 @@
+// src/models/rebalance.rs
+use std::collections::BTreeMap;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::models::crypto::{Cryptocurrency, MAX_AMOUNT};
+
+// Target allocation and trading constraints, kept in local storage
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RebalanceSettings {
+    // Share of the portfolio by asset id, in percent. Held assets without a
+    // target are sold off.
+    pub targets: BTreeMap<String, Decimal>,
+    // Assets within this many percentage points of their target are left
+    // alone
+    pub tolerance_percent: Decimal,
+    // Trades worth less than this, in the quote currency, are skipped
+    pub min_trade: Decimal,
+    // Cash added to the portfolio, in the quote currency
+    pub new_cash: Decimal,
+    // Only buy with `new_cash`, never sell
+    pub cash_only: bool,
+}
+
+impl RebalanceSettings {
+    // Brings hand-edited or stale values back into a sensible range
+    pub fn normalized(mut self) -> Self {
+        self.targets.retain(|_, percent| !percent.is_zero());
+        for percent in self.targets.values_mut() {
+            *percent = (*percent).clamp(Decimal::ZERO, Decimal::ONE_HUNDRED);
+        }
+        self.tolerance_percent = self.tolerance_percent.clamp(Decimal::ZERO, Decimal::ONE_HUNDRED);
+        self.min_trade = self.min_trade.clamp(Decimal::ZERO, Decimal::from(MAX_AMOUNT));
+        self.new_cash = self.new_cash.clamp(Decimal::ZERO, Decimal::from(MAX_AMOUNT));
+        self
+    }
+
+    pub fn target_total(&self) -> Decimal {
+        self.targets.values().sum()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RebalanceError {
+    // Targets have to add up to 100%; carries their sum, zero when none are set
+    Targets(Decimal),
+    // Nothing held and no cash to invest
+    Empty,
+}
+
+// One asset before and after the suggested trade
+#[derive(Clone, Debug, PartialEq)]
+pub struct Allocation {
+    pub crypto: Cryptocurrency,
+    pub target_percent: Decimal,
+    pub before_amount: Decimal,
+    pub before_percent: Decimal,
+    // Quantity to buy, negative to sell, zero to leave as is
+    pub trade: Decimal,
+    pub after_percent: Decimal,
+}
+
+impl Allocation {
+    pub fn trade_value(&self) -> Decimal {
+        self.crypto.value_of(self.trade)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RebalancePlan {
+    // Held or targeted assets, largest target first
+    pub allocations: Vec<Allocation>,
+    // Holdings plus new cash
+    pub total: Decimal,
+    // New cash and sale proceeds not spent on buys
+    pub cash_left: Decimal,
+}
+
+impl RebalancePlan {
+    pub fn trades(&self) -> impl Iterator<Item = &Allocation> {
+        self.allocations.iter().filter(|allocation| !allocation.trade.is_zero())
+    }
+}
+
+fn percent_of(value: Decimal, total: Decimal) -> Decimal {
+    if total.is_zero() {
+        Decimal::ZERO
+    } else {
+        value / total * Decimal::ONE_HUNDRED
+    }
+}
+
+// Trades that bring `holdings` to the target allocation. Allocations are
+// measured against holdings plus new cash. Assets drifting no more than the
+// tolerance are left alone, quantities are rounded down to the asset's
+// precision and trades under the minimum size are dropped. When sales and
+// new cash don't cover every buy, all buys are scaled down alike.
+pub fn plan(
+    market: &[Cryptocurrency],
+    holdings: &[(String, Decimal)],
+    settings: &RebalanceSettings,
+) -> Result<RebalancePlan, RebalanceError> {
+    let target_total = settings.target_total();
+    if target_total != Decimal::ONE_HUNDRED {
+        return Err(RebalanceError::Targets(target_total));
+    }
+
+    // Held or targeted assets that have a price; anything else can't be traded
+    let assets: Vec<(Cryptocurrency, Decimal)> = market
+        .iter()
+        .filter(|crypto| !crypto.price.is_zero())
+        .filter_map(|crypto| {
+            let amount: Decimal = holdings.iter().filter(|(id, _)| *id == crypto.id).map(|(_, amount)| *amount).sum();
+            (!amount.is_zero() || settings.targets.contains_key(&crypto.id)).then(|| (crypto.clone(), amount))
+        })
+        .collect();
+
+    let held: Decimal = assets.iter().map(|(crypto, amount)| crypto.value_of(*amount)).sum();
+    let total = held + settings.new_cash;
+    if total.is_zero() {
+        return Err(RebalanceError::Empty);
+    }
+
+    // Wanted change in value per asset, before funding
+    let target_of = |id: &str| settings.targets.get(id).copied().unwrap_or_default();
+    let wanted: Vec<Decimal> = assets
+        .iter()
+        .map(|(crypto, amount)| {
+            let value = crypto.value_of(*amount);
+            let drift = percent_of(value, total) - target_of(&crypto.id);
+            if drift.abs() <= settings.tolerance_percent {
+                return Decimal::ZERO;
+            }
+            let delta = total * target_of(&crypto.id) / Decimal::ONE_HUNDRED - value;
+            if settings.cash_only && delta.is_sign_negative() {
+                Decimal::ZERO
+            } else {
+                delta
+            }
+        })
+        .collect();
+
+    let quantity = |crypto: &Cryptocurrency, value: Decimal| {
+        let amount = (value / crypto.price).round_dp_with_strategy(crypto.decimals, RoundingStrategy::ToZero);
+        if crypto.value_of(amount).abs() < settings.min_trade {
+            Decimal::ZERO
+        } else {
+            amount.normalize()
+        }
+    };
+
+    let mut trades: Vec<Decimal> = assets
+        .iter()
+        .zip(&wanted)
+        .map(|((crypto, _), delta)| if delta.is_sign_negative() { quantity(crypto, *delta) } else { Decimal::ZERO })
+        .collect();
+    let sold: Decimal = assets.iter().zip(&trades).map(|((crypto, _), amount)| -crypto.value_of(*amount)).sum();
+    let available = settings.new_cash + sold;
+    let buying: Decimal = wanted.iter().filter(|delta| delta.is_sign_positive()).sum();
+    let scale = if buying > available && !buying.is_zero() { available / buying } else { Decimal::ONE };
+    for (index, ((crypto, _), delta)) in assets.iter().zip(&wanted).enumerate() {
+        if delta.is_sign_positive() && !delta.is_zero() {
+            trades[index] = quantity(crypto, *delta * scale);
+        }
+    }
+
+    let spent: Decimal = assets.iter().zip(&trades).map(|((crypto, _), amount)| crypto.value_of(*amount)).sum();
+    let mut allocations: Vec<Allocation> = assets
+        .into_iter()
+        .zip(trades)
+        .map(|((crypto, amount), trade)| Allocation {
+            target_percent: target_of(&crypto.id),
+            before_percent: percent_of(crypto.value_of(amount), total),
+            after_percent: percent_of(crypto.value_of(amount + trade), total),
+            before_amount: amount,
+            trade,
+            crypto,
+        })
+        .collect();
+    allocations.sort_by(|a, b| {
+        b.target_percent
+            .cmp(&a.target_percent)
+            .then_with(|| b.before_percent.cmp(&a.before_percent))
+            .then_with(|| a.crypto.id.cmp(&b.crypto.id))
+    });
+
+    Ok(RebalancePlan {
+        allocations,
+        total,
+        cash_left: settings.new_cash - spent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn crypto(id: &str, price: Decimal) -> Cryptocurrency {
+        Cryptocurrency {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            symbol: id[..3].to_uppercase(),
+            price,
+            market_cap: 0.0,
+            volume_24h: 0.0,
+            price_change_24h: 0.0,
+            decimals: 8,
+        }
+    }
+
+    fn settings(targets: &[(&str, Decimal)]) -> RebalanceSettings {
+        RebalanceSettings {
+            targets: targets.iter().map(|(id, percent)| (id.to_string(), *percent)).collect(),
+            ..RebalanceSettings::default()
+        }
+    }
+
+    fn market() -> Vec<Cryptocurrency> {
+        vec![crypto("bitcoin", dec!(40000)), crypto("ethereum", dec!(2000)), crypto("cardano", dec!(0.5)), crypto("unpriced", Decimal::ZERO)]
+    }
+
+    fn trade(plan: &RebalancePlan, id: &str) -> Decimal {
+        plan.allocations.iter().find(|allocation| allocation.crypto.id == id).unwrap().trade
+    }
+
+    // 8000 in bitcoin, 2000 in ethereum
+    fn holdings() -> Vec<(String, Decimal)> {
+        vec![("bitcoin".to_string(), dec!(0.2)), ("ethereum".to_string(), dec!(1)), ("unpriced".to_string(), dec!(5))]
+    }
+
+    #[test]
+    fn sells_overweight_assets_to_buy_underweight_ones() {
+        let plan = plan(&market(), &holdings(), &settings(&[("bitcoin", dec!(50)), ("ethereum", dec!(50))])).unwrap();
+
+        assert_eq!(trade(&plan, "bitcoin"), dec!(-0.075));
+        assert_eq!(trade(&plan, "ethereum"), dec!(1.5));
+        assert_eq!(plan.cash_left, Decimal::ZERO);
+        assert!(plan.allocations.iter().all(|allocation| allocation.after_percent == dec!(50)));
+        assert_eq!(plan.allocations[0].before_percent, dec!(80));
+    }
+
+    #[test]
+    fn untargeted_holdings_are_sold_and_new_targets_bought() {
+        let plan = plan(&market(), &holdings(), &settings(&[("bitcoin", dec!(80)), ("cardano", dec!(20))])).unwrap();
+
+        assert_eq!(trade(&plan, "bitcoin"), Decimal::ZERO);
+        assert_eq!(trade(&plan, "ethereum"), dec!(-1));
+        assert_eq!(trade(&plan, "cardano"), dec!(4000));
+        assert_eq!(plan.allocations[0].crypto.id, "bitcoin");
+        assert_eq!(plan.allocations.len(), 3);
+    }
+
+    #[test]
+    fn the_tolerance_band_and_minimum_trade_size_skip_small_moves() {
+        let targets = settings(&[("bitcoin", dec!(75)), ("ethereum", dec!(25))]);
+        // 5 points of drift
+        let within = RebalanceSettings {
+            tolerance_percent: dec!(5),
+            ..targets.clone()
+        };
+        assert_eq!(plan(&market(), &holdings(), &within).unwrap().trades().count(), 0);
+
+        let small = RebalanceSettings {
+            min_trade: dec!(501),
+            ..targets.clone()
+        };
+        assert_eq!(plan(&market(), &holdings(), &small).unwrap().trades().count(), 0);
+        assert_eq!(plan(&market(), &holdings(), &targets).unwrap().trades().count(), 2);
+    }
+
+    #[test]
+    fn cash_only_buys_underweight_assets_without_selling() {
+        let settings = RebalanceSettings {
+            new_cash: dec!(1000),
+            cash_only: true,
+            ..settings(&[("bitcoin", dec!(50)), ("ethereum", dec!(50))])
+        };
+        let plan = plan(&market(), &holdings(), &settings).unwrap();
+
+        // Ethereum is 4500 short of half of 11000, so it gets all the cash
+        assert_eq!(plan.total, dec!(11000));
+        assert_eq!(trade(&plan, "bitcoin"), Decimal::ZERO);
+        assert_eq!(trade(&plan, "ethereum"), dec!(0.5));
+        assert_eq!(plan.cash_left, Decimal::ZERO);
+    }
+
+    #[test]
+    fn buys_are_scaled_to_the_cash_available() {
+        let market = [crypto("bitcoin", dec!(40000)), crypto("ethereum", dec!(3))];
+        let settings = RebalanceSettings {
+            new_cash: dec!(100),
+            cash_only: true,
+            ..settings(&[("bitcoin", dec!(50)), ("ethereum", dec!(50))])
+        };
+        let plan = plan(&market, &[("bitcoin".to_string(), dec!(0.25))], &settings).unwrap();
+
+        // 5050 wanted, 100 available; rounding down leaves a little cash over
+        assert_eq!(trade(&plan, "ethereum"), dec!(33.33333333));
+        assert!(plan.cash_left >= Decimal::ZERO && plan.cash_left < dec!(0.0001));
+    }
+
+    #[test]
+    fn targets_must_add_up_to_a_hundred() {
+        let result = plan(&market(), &holdings(), &settings(&[("bitcoin", dec!(60)), ("ethereum", dec!(30))]));
+        assert_eq!(result, Err(RebalanceError::Targets(dec!(90))));
+        assert_eq!(plan(&market(), &holdings(), &RebalanceSettings::default()), Err(RebalanceError::Targets(Decimal::ZERO)));
+        assert_eq!(plan(&market(), &[], &settings(&[("bitcoin", dec!(100))])), Err(RebalanceError::Empty));
+    }
+
+    #[test]
+    fn normalized_bounds_cash_and_trade_size() {
+        let normalized = RebalanceSettings {
+            new_cash: Decimal::MAX,
+            min_trade: Decimal::MAX,
+            tolerance_percent: dec!(-1),
+            ..settings(&[("bitcoin", dec!(150)), ("ethereum", Decimal::ZERO)])
+        }
+        .normalized();
+
+        assert_eq!(normalized.new_cash, Decimal::from(MAX_AMOUNT));
+        assert_eq!(normalized.min_trade, Decimal::from(MAX_AMOUNT));
+        assert_eq!(normalized.tolerance_percent, Decimal::ZERO);
+        assert_eq!(normalized.targets.into_iter().collect::<Vec<_>>(), [("bitcoin".to_string(), Decimal::ONE_HUNDRED)]);
+
+        // The largest cash allowed still plans without overflowing
+        let cash = RebalanceSettings {
+            new_cash: Decimal::from(MAX_AMOUNT),
+            ..settings(&[("cardano", dec!(100))])
+        };
+        let plan = plan(&market(), &holdings(), &cash).unwrap();
+        assert!(trade(&plan, "cardano") > Decimal::ZERO);
+    }
+}
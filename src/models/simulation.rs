@@ -0,0 +1,404 @@
+// src/models/simulation.rs
+//
+// Replays daily price history to compare buying a fixed amount on a schedule
+// (dollar-cost averaging) with investing the same total on the first day.
+// Like the candles it reads, everything here is `f64`: the results are
+// statistics, never balances.
+use std::collections::BTreeMap;
+
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::models::history::Candle;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Schedule {
+    Daily,
+    #[default]
+    Weekly,
+    Monthly,
+}
+
+impl Schedule {
+    pub const ALL: [Schedule; 3] = [Schedule::Daily, Schedule::Weekly, Schedule::Monthly];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Schedule::Daily => "daily",
+            Schedule::Weekly => "weekly",
+            Schedule::Monthly => "monthly",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|schedule| schedule.code() == code)
+    }
+
+    // The `index`th buy after `start`; monthly buys on the 31st fall on the
+    // last day of shorter months
+    fn date(self, start: NaiveDate, index: u32) -> Option<NaiveDate> {
+        match self {
+            Schedule::Daily => start.checked_add_days(Days::new(u64::from(index))),
+            Schedule::Weekly => start.checked_add_days(Days::new(7 * u64::from(index))),
+            Schedule::Monthly => start.checked_add_months(Months::new(index)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationSettings {
+    // Asset ids; each buy is split evenly between them
+    pub assets: Vec<String>,
+    // Spent per scheduled buy, in the quote currency
+    pub amount: f64,
+    pub schedule: Schedule,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulationError {
+    NoAssets,
+    // Amount not positive, or the range ends before it starts
+    InvalidInput,
+    // The history doesn't cover any of the range for every asset
+    NoPrices,
+}
+
+// What one strategy ends up with
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Outcome {
+    pub invested: f64,
+    pub value: f64,
+    // Gain over `invested`, in percent
+    pub roi_percent: f64,
+    // Largest fall of value per unit invested from an earlier high, in percent
+    pub max_drawdown_percent: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetOutcome {
+    pub id: String,
+    pub quantity: f64,
+    pub invested: f64,
+    // Quote currency paid per unit
+    pub average_cost: f64,
+    pub value: f64,
+}
+
+// The state of both strategies at the end of a day
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+    pub date: NaiveDate,
+    // Spent by dollar-cost averaging so far
+    pub invested: f64,
+    pub dca_value: f64,
+    pub lump_sum_value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    // First day every asset has a price and last day any has one, within the
+    // range asked for
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub buys: u32,
+    pub dca: Outcome,
+    pub lump_sum: Outcome,
+    // Dollar-cost averaging per asset, in `settings.assets` order
+    pub assets: Vec<AssetOutcome>,
+    pub points: Vec<Point>,
+}
+
+// Closing prices by day
+struct Prices(BTreeMap<NaiveDate, f64>);
+
+impl Prices {
+    fn new(candles: &[Candle]) -> Self {
+        Self(
+            candles
+                .iter()
+                .filter(|candle| candle.close > 0.0)
+                .map(|candle| (candle.timestamp.date_naive(), candle.close))
+                .collect(),
+        )
+    }
+
+    // The latest close on or before `date`, as on a day the market was closed
+    fn on(&self, date: NaiveDate) -> Option<f64> {
+        self.0.range(..=date).next_back().map(|(_, close)| *close)
+    }
+
+    fn first(&self) -> Option<NaiveDate> {
+        self.0.keys().next().copied()
+    }
+
+    fn last(&self) -> Option<NaiveDate> {
+        self.0.keys().next_back().copied()
+    }
+}
+
+// Dollar-cost averaging against a lump sum over `history`, daily candles by
+// asset id. Buys happen at the day's close, starting on the first day every
+// asset has a price; the lump sum invests what the schedule ends up spending
+// in total, all on that first day.
+pub fn simulate(settings: &SimulationSettings, history: &[(String, Vec<Candle>)]) -> Result<Simulation, SimulationError> {
+    if settings.assets.is_empty() {
+        return Err(SimulationError::NoAssets);
+    }
+    if !settings.amount.is_finite() || settings.amount <= 0.0 || settings.end < settings.start {
+        return Err(SimulationError::InvalidInput);
+    }
+
+    let prices: Vec<Prices> = settings
+        .assets
+        .iter()
+        .map(|id| {
+            let candles = history.iter().find(|(asset, _)| asset == id).map(|(_, candles)| candles.as_slice());
+            Prices::new(candles.unwrap_or_default())
+        })
+        .collect();
+    let first = prices.iter().map(Prices::first).collect::<Option<Vec<_>>>().and_then(|firsts| firsts.into_iter().max());
+    // Days past the last close would only repeat it, however far off the
+    // typed end date is
+    let last = prices.iter().filter_map(Prices::last).max();
+    let (start, end) = match (first, last) {
+        (Some(first), Some(last)) if first.max(settings.start) <= settings.end.min(last) => {
+            (first.max(settings.start), settings.end.min(last))
+        }
+        _ => return Err(SimulationError::NoPrices),
+    };
+
+    let share = settings.amount / settings.assets.len() as f64;
+    let buy_dates: Vec<NaiveDate> = (0..)
+        .map_while(|index| settings.schedule.date(start, index))
+        .take_while(|date| *date <= end)
+        .collect();
+    let buys = buy_dates.len() as u32;
+    let total = settings.amount * f64::from(buys);
+
+    // Priced on every day from `start`, since `start` is priced for all
+    let price = |asset: usize, date: NaiveDate| prices[asset].on(date).unwrap_or_default();
+    let lump_quantities: Vec<f64> =
+        (0..prices.len()).map(|asset| total / settings.assets.len() as f64 / price(asset, start)).collect();
+
+    let mut quantities = vec![0.0; prices.len()];
+    let mut invested = 0.0;
+    let mut next_buy = buy_dates.iter().peekable();
+    let mut points = Vec::new();
+    let mut date = start;
+    while date <= end {
+        if next_buy.next_if(|buy| **buy == date).is_some() {
+            for (asset, quantity) in quantities.iter_mut().enumerate() {
+                *quantity += share / price(asset, date);
+            }
+            invested += settings.amount;
+        }
+        let value_of = |quantities: &[f64]| -> f64 {
+            quantities.iter().enumerate().map(|(asset, quantity)| quantity * price(asset, date)).sum()
+        };
+        points.push(Point {
+            date,
+            invested,
+            dca_value: value_of(&quantities),
+            lump_sum_value: value_of(&lump_quantities),
+        });
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+
+    let last = points.last().cloned().expect("the range holds at least its first day");
+    let dca = outcome(
+        last.invested,
+        last.dca_value,
+        points.iter().map(|point| point.dca_value / point.invested),
+    );
+    let lump_sum = outcome(total, last.lump_sum_value, points.iter().map(|point| point.lump_sum_value / total));
+    let assets = settings
+        .assets
+        .iter()
+        .zip(&quantities)
+        .enumerate()
+        .map(|(asset, (id, quantity))| AssetOutcome {
+            id: id.clone(),
+            quantity: *quantity,
+            invested: share * f64::from(buys),
+            average_cost: share * f64::from(buys) / quantity,
+            value: quantity * price(asset, end),
+        })
+        .collect();
+
+    Ok(Simulation {
+        start,
+        end,
+        buys,
+        dca,
+        lump_sum,
+        assets,
+        points,
+    })
+}
+
+// `growth` is value per unit invested over time, so new money coming in
+// isn't mistaken for gains
+fn outcome(invested: f64, value: f64, growth: impl Iterator<Item = f64>) -> Outcome {
+    let mut peak = f64::MIN;
+    let mut max_drawdown = 0.0_f64;
+    for ratio in growth.filter(|ratio| ratio.is_finite()) {
+        peak = peak.max(ratio);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - ratio) / peak);
+        }
+    }
+    Outcome {
+        invested,
+        value,
+        roi_percent: if invested > 0.0 { (value - invested) / invested * 100.0 } else { 0.0 },
+        max_drawdown_percent: max_drawdown * 100.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    // Daily closes from January 1st
+    fn series(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| Candle {
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(day as i64),
+                open: *close,
+                high: *close,
+                low: *close,
+                close: *close,
+                volume: 0.0,
+            })
+            .collect()
+    }
+
+    fn settings(assets: &[&str], schedule: Schedule, start: NaiveDate, end: NaiveDate) -> SimulationSettings {
+        SimulationSettings {
+            assets: assets.iter().map(|id| id.to_string()).collect(),
+            amount: 100.0,
+            schedule,
+            start,
+            end,
+        }
+    }
+
+    fn close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn averaging_down_beats_the_lump_sum_in_a_dip() {
+        let history = [("bitcoin".to_string(), series(&[100.0, 50.0, 100.0]))];
+        let result = simulate(&settings(&["bitcoin"], Schedule::Daily, date(1), date(3)), &history).unwrap();
+
+        // 1 + 2 + 1 coins for 300
+        assert_eq!(result.buys, 3);
+        close(result.assets[0].quantity, 4.0);
+        close(result.assets[0].average_cost, 75.0);
+        close(result.dca.value, 400.0);
+        close(result.dca.roi_percent, 100.0 / 3.0);
+        // 300 at 100 on day one
+        close(result.lump_sum.invested, 300.0);
+        close(result.lump_sum.value, 300.0);
+        close(result.lump_sum.max_drawdown_percent, 50.0);
+        // 3 coins worth 150 for 200 invested on day two, after breaking even
+        close(result.dca.max_drawdown_percent, 25.0);
+        assert_eq!(result.points.len(), 3);
+        close(result.points[1].invested, 200.0);
+    }
+
+    #[test]
+    fn schedules_step_by_day_week_and_month() {
+        let history = [("bitcoin".to_string(), series(&[10.0; 62]))];
+        let count = |schedule| simulate(&settings(&["bitcoin"], schedule, date(1), date(31)), &history).unwrap().buys;
+        assert_eq!(count(Schedule::Daily), 31);
+        assert_eq!(count(Schedule::Weekly), 5);
+        assert_eq!(count(Schedule::Monthly), 1);
+
+        // Monthly from the 31st keeps to the end of February
+        let dates: Vec<NaiveDate> = (0..2).map(|index| Schedule::Monthly.date(date(31), index).unwrap()).collect();
+        assert_eq!(dates[1], NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    }
+
+    #[test]
+    fn buys_split_between_assets_and_start_once_all_are_priced() {
+        let mut late = series(&[0.0, 0.0, 10.0, 20.0]);
+        late.drain(..2);
+        let history = [
+            ("bitcoin".to_string(), series(&[100.0, 100.0, 100.0, 200.0])),
+            ("ethereum".to_string(), late),
+        ];
+        let result = simulate(&settings(&["bitcoin", "ethereum"], Schedule::Daily, date(1), date(4)), &history).unwrap();
+
+        assert_eq!(result.start, date(3));
+        assert_eq!(result.buys, 2);
+        close(result.assets[0].quantity, 0.5 + 0.25);
+        close(result.assets[1].quantity, 5.0 + 2.5);
+        close(result.assets[1].invested, 100.0);
+        // Both doubled in the end, so the lump sum from day three doubled too
+        close(result.lump_sum.value, 400.0);
+    }
+
+    #[test]
+    fn missing_days_use_the_previous_close() {
+        let mut history = series(&[100.0, 100.0, 100.0]);
+        history.remove(1);
+        let result = simulate(&settings(&["bitcoin"], Schedule::Daily, date(1), date(3)), &[("bitcoin".to_string(), history)]).unwrap();
+        close(result.assets[0].quantity, 3.0);
+    }
+
+    #[test]
+    fn rejects_empty_or_unpriced_input() {
+        let history = [("bitcoin".to_string(), series(&[100.0]))];
+        let simulation = |settings: SimulationSettings| simulate(&settings, &history).map(|_| ());
+
+        assert_eq!(simulation(settings(&[], Schedule::Daily, date(1), date(2))), Err(SimulationError::NoAssets));
+        assert_eq!(simulation(settings(&["bitcoin"], Schedule::Daily, date(2), date(1))), Err(SimulationError::InvalidInput));
+        assert_eq!(
+            simulation(SimulationSettings { amount: 0.0, ..settings(&["bitcoin"], Schedule::Daily, date(1), date(2)) }),
+            Err(SimulationError::InvalidInput)
+        );
+        assert_eq!(simulation(settings(&["solana"], Schedule::Daily, date(1), date(2))), Err(SimulationError::NoPrices));
+        assert_eq!(simulation(settings(&["bitcoin"], Schedule::Daily, date(2), date(3))), Err(SimulationError::NoPrices));
+    }
+
+    #[test]
+    fn the_range_ends_at_the_last_close() {
+        let history = [("bitcoin".to_string(), series(&[100.0, 200.0]))];
+        let far = NaiveDate::from_ymd_opt(262_000, 1, 1).unwrap();
+        let result = simulate(&settings(&["bitcoin"], Schedule::Daily, date(1), far), &history).unwrap();
+
+        assert_eq!(result.end, date(2));
+        assert_eq!(result.buys, 2);
+        assert_eq!(result.points.len(), 2);
+    }
+
+    #[test]
+    fn replays_the_mock_server_fixture() {
+        let candles: Vec<Candle> = serde_json::from_str(include_str!("../../mock-server/fixtures/history/bitcoin.json")).unwrap();
+        let first = candles[0].timestamp.date_naive();
+        let last = candles.last().unwrap().timestamp.date_naive();
+        let result = simulate(&settings(&["bitcoin"], Schedule::Weekly, first, last), &[("bitcoin".to_string(), candles.clone())]).unwrap();
+
+        assert_eq!(result.points.len(), candles.len());
+        close(result.dca.invested, 100.0 * f64::from(result.buys));
+        // Lump sum bought at the first close and is worth the last one
+        close(result.lump_sum.roi_percent, (candles.last().unwrap().close / candles[0].close - 1.0) * 100.0);
+        assert!(result.dca.max_drawdown_percent > 0.0 && result.dca.max_drawdown_percent < 100.0);
+        assert!(result.assets[0].average_cost > 0.0);
+    }
+}
@@ -0,0 +1,145 @@
+// src/models/token.rs
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::models::wallet::Chain;
+
+// A token contract (ERC-20) or mint (SPL) counted as holdings of a listed asset
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Token {
+    pub chain: Chain,
+    // Lowercase for EVM chains, as is for Solana
+    pub contract: String,
+    pub symbol: String,
+    // Market data id of the asset the token tracks, e.g. WBTC counts as bitcoin
+    pub asset_id: String,
+    pub decimals: u32,
+}
+
+impl Token {
+    fn new(chain: Chain, contract: &str, symbol: &str, asset_id: &str, decimals: u32) -> Self {
+        Self {
+            chain,
+            contract: contract.to_string(),
+            symbol: symbol.to_string(),
+            asset_id: asset_id.to_string(),
+            decimals,
+        }
+    }
+}
+
+// Wrapped and bridged versions of the listed assets on each chain
+fn builtin_tokens() -> Vec<Token> {
+    vec![
+        Token::new(Chain::Ethereum, "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599", "WBTC", "bitcoin", 8),
+        Token::new(Chain::Ethereum, "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2", "WETH", "ethereum", 18),
+        Token::new(Chain::Ethereum, "0xd31a59c85ae9d8edefec411d448f90841571b89c", "SOL", "solana", 9),
+        Token::new(Chain::Arbitrum, "0x2f2a2543b76a4166549f7aab2e75bef0aefc5b0f", "WBTC", "bitcoin", 8),
+        Token::new(Chain::Arbitrum, "0x82af49447d8a07e3bd95bd0d56f35241523fbab1", "WETH", "ethereum", 18),
+        Token::new(Chain::Optimism, "0x4200000000000000000000000000000000000006", "WETH", "ethereum", 18),
+        Token::new(Chain::Base, "0x4200000000000000000000000000000000000006", "WETH", "ethereum", 18),
+        Token::new(Chain::Solana, "So11111111111111111111111111111111111111112", "wSOL", "solana", 9),
+        Token::new(Chain::Solana, "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs", "WETH", "ethereum", 8),
+        Token::new(Chain::Solana, "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh", "WBTC", "bitcoin", 8),
+    ]
+}
+
+// Tokens looked for in watched wallets. Anything not on the list is never
+// counted, so airdropped spam only needs denying when it reuses a listed
+// contract's entry, e.g. a config entry copied from a scam site.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenList {
+    tokens: Vec<Token>,
+    denied: HashSet<String>,
+}
+
+impl Default for TokenList {
+    fn default() -> Self {
+        Self::new(&[], &[])
+    }
+}
+
+impl TokenList {
+    // The built-in list plus `extra`, which replaces built-in entries for the
+    // same contract, less any contract on `denylist`
+    pub fn new(extra: &[Token], denylist: &[String]) -> Self {
+        let mut tokens = builtin_tokens();
+        for token in extra {
+            let token = Token {
+                contract: normalize_contract(token.chain, &token.contract),
+                ..token.clone()
+            };
+            tokens.retain(|listed| (listed.chain, &listed.contract) != (token.chain, &token.contract));
+            tokens.push(token);
+        }
+        let denied: HashSet<String> = denylist.iter().map(|contract| contract.trim().to_ascii_lowercase()).collect();
+        tokens.retain(|token| !denied.contains(&token.contract.to_ascii_lowercase()));
+        Self { tokens, denied }
+    }
+
+    // The built-in list with `config.tokens` and `config.token_denylist` applied
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.tokens, &config.token_denylist)
+    }
+
+    pub fn on(&self, chain: Chain) -> impl Iterator<Item = &Token> {
+        self.tokens.iter().filter(move |token| token.chain == chain)
+    }
+
+    // The listed token at `contract` on `chain`
+    pub fn find(&self, chain: Chain, contract: &str) -> Option<&Token> {
+        let contract = normalize_contract(chain, contract);
+        self.on(chain).find(|token| token.contract == contract)
+    }
+
+    pub fn is_denied(&self, contract: &str) -> bool {
+        self.denied.contains(&contract.trim().to_ascii_lowercase())
+    }
+}
+
+fn normalize_contract(chain: Chain, contract: &str) -> String {
+    let contract = contract.trim();
+    if chain.is_evm() {
+        contract.to_ascii_lowercase()
+    } else {
+        contract.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WBTC: &str = "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599";
+
+    #[test]
+    fn finds_listed_tokens_by_contract() {
+        let list = TokenList::default();
+
+        let wbtc = list.find(Chain::Ethereum, "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599").unwrap();
+        assert_eq!((wbtc.asset_id.as_str(), wbtc.decimals), ("bitcoin", 8));
+        // Same contract, different chain
+        assert!(list.find(Chain::Arbitrum, WBTC).is_none());
+        // Base58 mints are case sensitive
+        assert!(list.find(Chain::Solana, "so11111111111111111111111111111111111111112").is_none());
+        assert!(list.on(Chain::Bitcoin).next().is_none());
+    }
+
+    #[test]
+    fn extra_tokens_replace_builtin_entries_and_the_denylist_wins() {
+        let extra = [
+            Token::new(Chain::Ethereum, "0x2260FAC5E5542A773AA44FBCFEDF7C193BC2C599", "BTC.b", "bitcoin", 8),
+            Token::new(Chain::Base, "0x0555e30da8f98308edb960aa94c0db47230d2b9c", "WBTC", "bitcoin", 8),
+        ];
+        let list = TokenList::new(&extra, &["0x4200000000000000000000000000000000000006".to_string()]);
+
+        assert_eq!(list.find(Chain::Ethereum, WBTC).unwrap().symbol, "BTC.b");
+        assert_eq!(list.on(Chain::Ethereum).filter(|token| token.contract == WBTC).count(), 1);
+        assert!(list.find(Chain::Base, "0x0555e30da8f98308edb960aa94c0db47230d2b9c").is_some());
+        assert!(list.find(Chain::Optimism, "0x4200000000000000000000000000000000000006").is_none());
+        assert!(list.is_denied(" 0x4200000000000000000000000000000000000006"));
+    }
+}
@@ -0,0 +1,369 @@
+// src/models/wallet.rs
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Networks whose native balances can be watched
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    Bitcoin,
+    Ethereum,
+    Arbitrum,
+    Optimism,
+    Base,
+    Solana,
+}
+
+impl Chain {
+    pub const ALL: [Chain; 6] = [
+        Chain::Bitcoin,
+        Chain::Ethereum,
+        Chain::Arbitrum,
+        Chain::Optimism,
+        Chain::Base,
+        Chain::Solana,
+    ];
+
+    // Used in storage, URLs and element classes
+    pub fn code(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "bitcoin",
+            Chain::Ethereum => "ethereum",
+            Chain::Arbitrum => "arbitrum",
+            Chain::Optimism => "optimism",
+            Chain::Base => "base",
+            Chain::Solana => "solana",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|chain| chain.code() == code)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "Bitcoin",
+            Chain::Ethereum => "Ethereum",
+            Chain::Arbitrum => "Arbitrum",
+            Chain::Optimism => "Optimism",
+            Chain::Base => "Base",
+            Chain::Solana => "Solana",
+        }
+    }
+
+    // Ticker of the native asset
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "BTC",
+            Chain::Solana => "SOL",
+            _ => "ETH",
+        }
+    }
+
+    // Ethereum and its rollups share the address format and JSON-RPC API
+    pub fn is_evm(self) -> bool {
+        matches!(self, Chain::Ethereum | Chain::Arbitrum | Chain::Optimism | Chain::Base)
+    }
+
+    // Market data id of the asset balances are held in; the rollups hold ETH
+    pub fn asset_id(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "bitcoin",
+            Chain::Solana => "solana",
+            _ => "ethereum",
+        }
+    }
+
+    // Decimal places of the smallest unit nodes report balances in
+    // (satoshi, wei, lamport)
+    pub fn decimals(self) -> u32 {
+        match self {
+            Chain::Bitcoin => 8,
+            Chain::Solana => 9,
+            _ => 18,
+        }
+    }
+}
+
+// An address whose balance is read from the chain rather than typed in
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedWallet {
+    pub chain: Chain,
+    // As returned by `validate_address`
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl WatchedWallet {
+    // Identifies the wallet among those watched
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.chain.code(), self.address)
+    }
+
+    // Label if one was given, else the shortened address
+    pub fn display_name(&self) -> String {
+        self.label.clone().unwrap_or_else(|| short_address(&self.address))
+    }
+}
+
+// What a wallet holds of one asset, as the chain's native coin or a token
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalletAsset {
+    pub asset_id: String,
+    pub symbol: String,
+    // Contract or mint of a token, `None` for the native coin
+    pub token: Option<String>,
+    pub amount: Decimal,
+}
+
+impl WalletAsset {
+    pub fn native(chain: Chain, amount: Decimal) -> Self {
+        Self {
+            asset_id: chain.asset_id().to_string(),
+            symbol: chain.symbol().to_string(),
+            token: None,
+            amount,
+        }
+    }
+}
+
+// Latest balances read for a watched wallet, the native coin first
+#[derive(Clone, Debug, PartialEq)]
+pub enum WalletBalance {
+    Loaded(Vec<WalletAsset>),
+    Failed(String),
+}
+
+// `bc1qar0s…5mdq`
+pub fn short_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 12 {
+        return address.to_string();
+    }
+    let head: String = chars[..8].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressError {
+    Empty,
+    // Not shaped like an address of the chain at all
+    Format,
+    // Shaped right, but the checksum doesn't match, e.g. a typo
+    Checksum,
+}
+
+impl AddressError {
+    // Suffix of the `wallets.error.*` message
+    pub fn code(self) -> &'static str {
+        match self {
+            AddressError::Empty => "empty",
+            AddressError::Format => "format",
+            AddressError::Checksum => "checksum",
+        }
+    }
+}
+
+// Checks `address` is well formed for `chain` and returns it in the form it
+// is stored and compared in: bech32 and EVM addresses lowercased, base58
+// ones as given. Legacy Bitcoin checksums need SHA-256 and aren't verified;
+// a mistyped address of that kind simply reports no balance.
+pub fn validate_address(chain: Chain, address: &str) -> Result<String, AddressError> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(AddressError::Empty);
+    }
+
+    match chain {
+        Chain::Bitcoin => {
+            if address.len() > 3 && address.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("bc1")) {
+                return validate_segwit(address);
+            }
+            match base58_decode(address) {
+                Some(bytes) if bytes.len() == 25 && matches!(bytes[0], 0x00 | 0x05) => Ok(address.to_string()),
+                _ => Err(AddressError::Format),
+            }
+        }
+        Chain::Solana => match base58_decode(address) {
+            Some(bytes) if bytes.len() == 32 => Ok(address.to_string()),
+            _ => Err(AddressError::Format),
+        },
+        _ => {
+            let hex = address.strip_prefix("0x").or_else(|| address.strip_prefix("0X"));
+            match hex {
+                Some(hex) if hex.len() == 40 && hex.bytes().all(|byte| byte.is_ascii_hexdigit()) => {
+                    Ok(format!("0x{}", hex.to_ascii_lowercase()))
+                }
+                _ => Err(AddressError::Format),
+            }
+        }
+    }
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_decode(text: &str) -> Option<Vec<u8>> {
+    // Little-endian base-256 digits
+    let mut bytes: Vec<u8> = Vec::new();
+    for char in text.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&digit| digit == char)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' stands for a zero byte
+    let zeros = text.bytes().take_while(|&char| char == b'1').count();
+    bytes.extend(std::iter::repeat_n(0, zeros));
+    bytes.reverse();
+    Some(bytes)
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+// Native SegWit (bech32) and Taproot (bech32m) mainnet addresses, BIP 173 and 350
+fn validate_segwit(address: &str) -> Result<String, AddressError> {
+    // Either case is fine, a mix of both isn't
+    if address.bytes().any(|byte| byte.is_ascii_lowercase()) && address.bytes().any(|byte| byte.is_ascii_uppercase()) {
+        return Err(AddressError::Format);
+    }
+    let address = address.to_ascii_lowercase();
+    // 20 and 32 byte witness programs
+    if !(42..=62).contains(&address.len()) {
+        return Err(AddressError::Format);
+    }
+    let data = address[3..]
+        .bytes()
+        .map(|char| BECH32_CHARSET.iter().position(|&digit| digit == char).map(|value| value as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(AddressError::Format)?;
+
+    let expected = match data[0] {
+        0 => BECH32_CONST,
+        1..=16 => BECH32M_CONST,
+        _ => return Err(AddressError::Format),
+    };
+    let mut values = vec![b'b' >> 5, b'c' >> 5, 0, b'b' & 31, b'c' & 31];
+    values.extend(&data);
+    if bech32_polymod(&values) != expected {
+        return Err(AddressError::Checksum);
+    }
+    Ok(address)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut checksum = 1_u32;
+    for &value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ value as u32;
+        for (bit, generator) in GENERATOR.iter().enumerate() {
+            if (top >> bit) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_bitcoin_legacy_segwit_and_taproot_addresses() {
+        for address in [
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297",
+        ] {
+            assert_eq!(validate_address(Chain::Bitcoin, address).as_deref(), Ok(address));
+        }
+        assert_eq!(
+            validate_address(Chain::Bitcoin, " BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ ").as_deref(),
+            Ok("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_bitcoin_addresses() {
+        assert_eq!(validate_address(Chain::Bitcoin, ""), Err(AddressError::Empty));
+        // A typo in the last character breaks the bech32 checksum
+        assert_eq!(
+            validate_address(Chain::Bitcoin, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdx"),
+            Err(AddressError::Checksum)
+        );
+        assert_eq!(
+            validate_address(Chain::Bitcoin, "bc1qAR0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+            Err(AddressError::Format)
+        );
+        // '0' isn't in the base58 alphabet, and an ETH address is the wrong shape
+        assert_eq!(validate_address(Chain::Bitcoin, "10zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"), Err(AddressError::Format));
+        assert_eq!(
+            validate_address(Chain::Bitcoin, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"),
+            Err(AddressError::Format)
+        );
+    }
+
+    #[test]
+    fn non_ascii_input_is_malformed_on_every_chain() {
+        for chain in Chain::ALL {
+            for address in ["ab€x", "bc1€qar0srrr7xfkvy5l643lydnw9re59gtzzwf5md", "0x€e0b295669a9fd93d5f28d9ec85e40f4cb697ba"] {
+                assert_eq!(validate_address(chain, address), Err(AddressError::Format), "{:?} {}", chain, address);
+            }
+        }
+    }
+
+    #[test]
+    fn evm_addresses_are_lowercased_on_every_evm_chain() {
+        for chain in Chain::ALL.into_iter().filter(|chain| chain.is_evm()) {
+            assert_eq!(
+                validate_address(chain, "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe").as_deref(),
+                Ok("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae")
+            );
+            assert_eq!(chain.asset_id(), "ethereum");
+        }
+        assert_eq!(validate_address(Chain::Base, "0xde0b2956"), Err(AddressError::Format));
+        assert_eq!(
+            validate_address(Chain::Ethereum, "0xzz0b295669a9fd93d5f28d9ec85e40f4cb697bae"),
+            Err(AddressError::Format)
+        );
+    }
+
+    #[test]
+    fn solana_addresses_are_32_byte_keys() {
+        for address in ["So11111111111111111111111111111111111111112", "11111111111111111111111111111111"] {
+            assert_eq!(validate_address(Chain::Solana, address).as_deref(), Ok(address));
+        }
+        assert_eq!(
+            validate_address(Chain::Solana, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+            Err(AddressError::Format)
+        );
+    }
+
+    #[test]
+    fn wallets_are_keyed_by_chain_and_shown_by_label() {
+        let mut wallet = WatchedWallet {
+            chain: Chain::Bitcoin,
+            address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+            label: None,
+        };
+        assert_eq!(wallet.key(), "bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(wallet.display_name(), "bc1qar0s…5mdq");
+
+        wallet.label = Some("Cold storage".to_string());
+        assert_eq!(wallet.display_name(), "Cold storage");
+        assert_eq!(Chain::from_code("optimism"), Some(Chain::Optimism));
+        assert_eq!(Chain::from_code("dogecoin"), None);
+    }
+}
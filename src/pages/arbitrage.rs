@@ -0,0 +1,246 @@
+// src/pages/arbitrage.rs
+use std::cell::Cell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use gloo::timers::callback::Interval;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::components::error::Error;
+use crate::components::loading::Loading;
+use crate::config::use_config;
+use crate::i18n::{use_i18n, I18n};
+use crate::models::arbitrage::{scan, sort, ArbitrageSettings, Opportunity, SortKey};
+use crate::models::crypto::Cryptocurrency;
+use crate::models::exchange::Quote;
+use crate::services::api;
+use crate::utils::settings::use_stored_settings;
+
+const SETTINGS_STORAGE_KEY: &str = "crypto_tracker.arbitrage";
+
+type Markets = Vec<(Cryptocurrency, Vec<Quote>)>;
+
+struct Setting {
+    key: &'static str,
+    step: &'static str,
+    get: fn(&ArbitrageSettings) -> Decimal,
+    set: fn(&mut ArbitrageSettings, Decimal),
+}
+
+const SETTINGS: [Setting; 4] = [
+    Setting {
+        key: "arbitrage.taker_fee",
+        step: "0.01",
+        get: |settings| settings.taker_fee_percent,
+        set: |settings, value| settings.taker_fee_percent = value,
+    },
+    Setting {
+        key: "arbitrage.withdrawal_cost",
+        step: "1",
+        get: |settings| settings.withdrawal_cost,
+        set: |settings, value| settings.withdrawal_cost = value,
+    },
+    Setting {
+        key: "arbitrage.trade_size",
+        step: "100",
+        get: |settings| settings.trade_size,
+        set: |settings, value| settings.trade_size = value,
+    },
+    Setting {
+        key: "arbitrage.min_profit",
+        step: "0.05",
+        get: |settings| settings.min_profit_percent,
+        set: |settings, value| settings.min_profit_percent = value,
+    },
+];
+
+// Assets one exchange bids more for than another asks, after costs
+#[function_component(Arbitrage)]
+pub fn arbitrage() -> Html {
+    let i18n = use_i18n();
+    let refresh_secs = use_config().refresh.prices_secs;
+    let (settings, set_settings) = use_stored_settings(SETTINGS_STORAGE_KEY, ArbitrageSettings::normalized);
+    let markets = use_state(|| None::<Result<Markets, String>>);
+    let updated = use_state(|| None::<DateTime<Utc>>);
+    let sort_by = use_state(|| (SortKey::Profit, true));
+    let reload = use_state(|| 0_u32);
+
+    {
+        let (markets, updated) = (markets.clone(), updated.clone());
+        use_effect_with((refresh_secs, *reload), move |(secs, _)| {
+            let current = Rc::new(Cell::new(true));
+            let busy = Rc::new(Cell::new(false));
+            let loaded = Rc::new(Cell::new(false));
+            let load = {
+                let current = current.clone();
+                move || {
+                    if busy.replace(true) {
+                        return;
+                    }
+                    let (current, busy, loaded) = (current.clone(), busy.clone(), loaded.clone());
+                    let (markets, updated) = (markets.clone(), updated.clone());
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let result = api::get_markets().await;
+                        busy.set(false);
+                        if !current.get() {
+                            return;
+                        }
+                        match result {
+                            Ok(latest) => {
+                                loaded.set(true);
+                                markets.set(Some(Ok(latest)));
+                                updated.set(Some(Utc::now()));
+                            }
+                            // Keep showing the last scan when a refresh fails
+                            Err(err) if loaded.get() => {
+                                log::warn!("Arbitrage refresh failed: {}", err)
+                            }
+                            Err(err) => markets.set(Some(Err(err.to_string()))),
+                        }
+                    });
+                }
+            };
+
+            load();
+            let refresh = (*secs > 0).then(|| Interval::new(secs.saturating_mul(1000), load));
+            move || {
+                current.set(false);
+                drop(refresh);
+            }
+        });
+    }
+
+    let inputs = SETTINGS.iter().map(|Setting { key, step, get, set }| {
+        let on_change = {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            let set = *set;
+            Callback::from(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                if let Ok(value) = Decimal::from_str(input.value().trim()) {
+                    let mut next = settings.clone();
+                    set(&mut next, value);
+                    set_settings.emit(next);
+                }
+            })
+        };
+        html! {
+            <label class="flex flex-col text-xs text-gray-500">
+                <span>{i18n.t(key)}</span>
+                <input
+                    type="number"
+                    min="0"
+                    step={*step}
+                    class="arbitrage-setting w-28 border rounded px-2 py-1 text-sm text-gray-800"
+                    value={get(&settings).normalize().to_string()}
+                    onchange={on_change}
+                />
+            </label>
+        }
+    });
+
+    let body = match &*markets {
+        None => html! { <Loading /> },
+        Some(Err(error)) => {
+            let on_retry = {
+                let reload = reload.clone();
+                Callback::from(move |_| reload.set(*reload + 1))
+            };
+            html! { <Error message={error.clone()} on_retry={Some(on_retry)} /> }
+        }
+        Some(Ok(markets)) => {
+            let (key, descending) = *sort_by;
+            let mut opportunities = scan(markets, &settings);
+            sort(&mut opportunities, key, descending);
+            if opportunities.is_empty() {
+                html! { <p class="arbitrage-empty text-gray-500">{i18n.t("arbitrage.empty")}</p> }
+            } else {
+                let header = |column: SortKey, label: &str| {
+                    let (key, descending) = *sort_by;
+                    let arrow = match (key == column, descending) {
+                        (false, _) => "",
+                        (true, true) => " ↓",
+                        (true, false) => " ↑",
+                    };
+                    let onclick = {
+                        let sort_by = sort_by.clone();
+                        // Names read best A to Z, numbers biggest first
+                        let first = column != SortKey::Asset;
+                        Callback::from(move |_| {
+                            let (key, descending) = *sort_by;
+                            sort_by.set((column, if key == column { !descending } else { first }));
+                        })
+                    };
+                    html! {
+                        <th aria-sort={(key == column).then_some(if descending { "descending" } else { "ascending" })}>
+                            <button class="arbitrage-sort font-semibold hover:text-blue-600" onclick={onclick}>{label}{arrow}</button>
+                        </th>
+                    }
+                };
+                html! {
+                    <div class="overflow-x-auto">
+                        <table class="w-full text-sm">
+                            <thead>
+                                <tr class="text-right text-xs text-gray-500 border-b">
+                                    {header(SortKey::Asset, &i18n.t("arbitrage.asset"))}
+                                    <th>{i18n.t("arbitrage.buy")}</th>
+                                    <th>{i18n.t("arbitrage.sell")}</th>
+                                    {header(SortKey::Spread, &i18n.t("arbitrage.spread"))}
+                                    <th>{i18n.t("arbitrage.net")}</th>
+                                    {header(SortKey::Profit, &i18n.t("arbitrage.profit"))}
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {for opportunities.iter().map(|opportunity| opportunity_row(&i18n, opportunity))}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+            }
+        }
+    };
+
+    html! {
+        <div class="arbitrage-page max-w-4xl mx-auto bg-white rounded-lg shadow p-8 mt-8">
+            <div class="flex items-baseline justify-between mb-2">
+                <h2 class="text-2xl font-bold text-blue-700">{i18n.t("arbitrage.title")}</h2>
+                if let Some(updated) = *updated {
+                    <span class="text-xs text-gray-500">
+                        {i18n.t_args("arbitrage.updated", &[("time", i18n.time(&updated))])}
+                    </span>
+                }
+            </div>
+            <p class="text-sm text-gray-500 mb-4">{i18n.t("arbitrage.description")}</p>
+            <div class="flex flex-wrap gap-4 mb-6">{for inputs}</div>
+            {body}
+        </div>
+    }
+}
+
+fn opportunity_row(i18n: &I18n, opportunity: &Opportunity) -> Html {
+    let percent = |value: Decimal| value.to_f64().unwrap_or_default();
+    html! {
+        <tr key={opportunity.id.clone()} class="arbitrage-row text-right font-mono border-b">
+            <td class="text-left font-sans py-1">
+                <Link<Route> to={Route::Details { id: opportunity.id.clone() }} classes="hover:text-blue-600">
+                    {&opportunity.name}{" "}<span class="text-gray-400">{&opportunity.symbol}</span>
+                </Link<Route>>
+            </td>
+            <td>
+                <span class="font-sans text-gray-500">{&opportunity.buy_exchange}</span>{" "}{i18n.price(&opportunity.buy_price)}
+            </td>
+            <td>
+                <span class="font-sans text-gray-500">{&opportunity.sell_exchange}</span>{" "}{i18n.price(&opportunity.sell_price)}
+            </td>
+            <td>{i18n.percent(percent(opportunity.gross_percent))}</td>
+            <td class="arbitrage-net">{i18n.signed_percent(percent(opportunity.net_percent))}</td>
+            <td class="arbitrage-profit text-green-600">{i18n.currency(&opportunity.profit)}</td>
+        </tr>
+    }
+}
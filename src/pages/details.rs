@@ -1,11 +1,37 @@
 // src/pages/details.rs
 use crate::components::crypto_card::CryptoCard;
 use crate::components::error::Error;
+use crate::components::exchange_prices::ExchangePrices;
 use crate::components::loading::Loading;
+use crate::components::market_depth::MarketDepth;
+use crate::components::price_chart::PriceChart;
+use crate::config::use_config;
+use crate::i18n::use_i18n;
 use crate::models::crypto::PortfolioItem;
 use crate::store::{use_store, StoreAction};
+use gloo::timers::callback::Interval;
 use yew::prelude::*;
 
+// Sections below the asset card, one shown at a time
+#[derive(Clone, Copy, PartialEq)]
+enum Tab {
+    Chart,
+    Book,
+    Exchanges,
+}
+
+impl Tab {
+    const ALL: [Tab; 3] = [Tab::Chart, Tab::Book, Tab::Exchanges];
+
+    fn code(&self) -> &'static str {
+        match self {
+            Tab::Chart => "chart",
+            Tab::Book => "book",
+            Tab::Exchanges => "exchanges",
+        }
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct DetailsProps {
     pub id: String,
@@ -14,6 +40,9 @@ pub struct DetailsProps {
 #[function_component(Details)]
 pub fn details(props: &DetailsProps) -> Html {
     let (store, dispatch) = use_store();
+    let i18n = use_i18n();
+    let refresh_secs = use_config().refresh.prices_secs;
+    let tab = use_state(|| Tab::Chart);
 
     let on_retry = {
         let dispatch = dispatch.clone();
@@ -55,15 +84,21 @@ pub fn details(props: &DetailsProps) -> Html {
     let in_portfolio = portfolio_item.is_some();
     let amount = portfolio_item.map(|item| item.amount);
 
-    let id = props.id.clone();
-    use_effect(move || {
+    // Load on mount and when the asset changes, then keep its price fresh
+    use_effect_with((props.id.clone(), refresh_secs), move |(id, secs)| {
         dispatch.emit(StoreAction::FetchCryptocurrencyDetails(id.clone()));
-        || ()
+        let refresh = (*secs > 0).then(|| {
+            let id = id.clone();
+            Interval::new(secs.saturating_mul(1000), move || {
+                dispatch.emit(StoreAction::RefreshCryptocurrencyDetails(id.clone()))
+            })
+        });
+        move || drop(refresh)
     });
 
     html! {
         <div class="details-page max-w-2xl mx-auto bg-white rounded-lg shadow p-8 mt-8">
-            <h2 class="text-2xl font-bold text-blue-700 mb-6">{"Cryptocurrency Details"}</h2>
+            <h2 class="text-2xl font-bold text-blue-700 mb-6">{i18n.t("details.title")}</h2>
 
             {
                 if store.loading {
@@ -83,10 +118,37 @@ pub fn details(props: &DetailsProps) -> Html {
                     }
                 } else {
                     html! {
-                        <p class="text-gray-500">{"No cryptocurrency data available."}</p>
+                        <p class="text-gray-500">{i18n.t("details.no_data")}</p>
                     }
                 }
             }
+
+            <div role="tablist" class="details-tabs flex border-b mt-8">
+                {for Tab::ALL.iter().map(|option| {
+                    let selected = *tab == *option;
+                    let onclick = {
+                        let (tab, option) = (tab.clone(), *option);
+                        Callback::from(move |_| tab.set(option))
+                    };
+                    html! {
+                        <button
+                            role="tab"
+                            aria-selected={selected.to_string()}
+                            class={classes!("details-tab", format!("details-tab-{}", option.code()), selected.then_some("details-tab-active"))}
+                            onclick={onclick}
+                        >
+                            {i18n.t(&format!("details.tab.{}", option.code()))}
+                        </button>
+                    }
+                })}
+            </div>
+            <div role="tabpanel">
+                {match *tab {
+                    Tab::Chart => html! { <PriceChart id={props.id.clone()} /> },
+                    Tab::Book => html! { <MarketDepth id={props.id.clone()} /> },
+                    Tab::Exchanges => html! { <ExchangePrices id={props.id.clone()} /> },
+                }}
+            </div>
         </div>
     }
 }
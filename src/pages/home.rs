@@ -1,16 +1,20 @@
 // src/pages/home.rs
-use gloo::console::log;
+use gloo::timers::callback::Interval;
 use yew::prelude::*;
 
 use crate::components::crypto_list::CryptoList;
 use crate::components::error::Error;
 use crate::components::loading::Loading;
-use crate::models::crypto::PortfolioItem;
+use crate::components::recently_viewed::RecentlyViewedStrip;
+use crate::config::use_config;
+use crate::i18n::use_i18n;
 use crate::store::{use_store, StoreAction};
 
 #[function_component(Home)]
 pub fn home() -> Html {
     let (store, dispatch) = use_store();
+    let i18n = use_i18n();
+    let refresh_secs = use_config().refresh.prices_secs;
 
     let on_retry = {
         let dispatch = dispatch.clone();
@@ -19,14 +23,25 @@ pub fn home() -> Html {
         })
     };
 
-    use_effect(move || {
+    // Load on mount, then keep prices fresh while the page is open
+    use_effect_with(refresh_secs, move |secs| {
         dispatch.emit(StoreAction::FetchCryptocurrencies);
-        || ()
+        let refresh = (*secs > 0).then(|| {
+            Interval::new(secs.saturating_mul(1000), move || {
+                dispatch.emit(StoreAction::RefreshCryptocurrencies)
+            })
+        });
+        move || drop(refresh)
     });
 
     html! {
         <div class="home-page max-w-3xl mx-auto bg-white rounded-lg shadow p-8 mt-8">
-            <h2 class="text-2xl font-bold text-blue-700 mb-6">{"HomePortfolio"}</h2>
+            <div class="flex items-baseline justify-between mb-6">
+                <h2 class="text-2xl font-bold text-blue-700">{i18n.t("home.title")}</h2>
+                <span class="text-sm text-gray-500">{i18n.plural("home.count", store.cryptocurrencies.len() as f64)}</span>
+            </div>
+
+            <RecentlyViewedStrip />
 
             {
                 if store.loading {
@@ -34,7 +49,7 @@ pub fn home() -> Html {
                 } else if let Some(error) = &store.error {
                     html! { <Error message={error.clone()} on_retry={Some(on_retry)} /> }
                 } else if store.cryptocurrencies.is_empty() {
-                    html! { <p class="text-gray-500">{"No cryptocurrencies available."}</p> }
+                    html! { <p class="text-gray-500">{i18n.t("home.empty")}</p> }
                 } else {
                     html! { <CryptoList cryptocurrencies={store.cryptocurrencies.clone()} /> }
                 }
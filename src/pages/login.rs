@@ -0,0 +1,127 @@
+// src/pages/login.rs
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::i18n::use_i18n;
+use crate::services::api;
+use crate::store::{use_store, StoreAction};
+
+// `?redirect=` carries the protected page that sent the visitor here
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoginQuery {
+    #[serde(default)]
+    pub redirect: Option<String>,
+}
+
+#[function_component(Login)]
+pub fn login() -> Html {
+    let i18n = use_i18n();
+    let (_, dispatch) = use_store();
+    let navigator = use_navigator().unwrap();
+    let location = use_location().unwrap();
+
+    let username = use_state(String::new);
+    let password = use_state(String::new);
+    let submitting = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let on_username = {
+        let username = username.clone();
+        Callback::from(move |e: InputEvent| username.set(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_password = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| password.set(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+
+    let on_submit = {
+        let i18n = i18n.clone();
+        let username = username.clone();
+        let password = password.clone();
+        let submitting = submitting.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if username.trim().is_empty() || password.is_empty() {
+                error.set(Some(i18n.t("auth.error.required")));
+                return;
+            }
+
+            let redirect = location
+                .query::<LoginQuery>()
+                .ok()
+                .and_then(|query| query.redirect)
+                .and_then(|path| Route::recognize(&path))
+                .unwrap_or(Route::Portfolio);
+            let i18n = i18n.clone();
+            let username = username.trim().to_string();
+            let password = (*password).clone();
+            let submitting = submitting.clone();
+            let error = error.clone();
+            let dispatch = dispatch.clone();
+            let navigator = navigator.clone();
+
+            submitting.set(true);
+            error.set(None);
+            wasm_bindgen_futures::spawn_local(async move {
+                match api::login(&username, &password).await {
+                    Ok(_) => {
+                        // Pull the account's portfolio
+                        dispatch.emit(StoreAction::SavePortfolio);
+                        navigator.push(&redirect);
+                    }
+                    Err(err) if err.is::<api::Unauthorized>() => {
+                        error.set(Some(i18n.t("auth.error.invalid")));
+                    }
+                    Err(err) => error.set(Some(err.to_string())),
+                }
+                submitting.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class="login-page max-w-sm mx-auto bg-white rounded-lg shadow p-8 mt-8">
+            <h2 class="text-2xl font-bold text-blue-700 mb-6">{i18n.t("auth.title")}</h2>
+            <form class="space-y-4" onsubmit={on_submit}>
+                <label class="block">
+                    <span class="text-sm text-gray-600">{i18n.t("auth.username")}</span>
+                    <input
+                        name="username"
+                        autocomplete="username"
+                        class="mt-1 w-full border rounded px-3 py-2"
+                        value={(*username).clone()}
+                        oninput={on_username}
+                    />
+                </label>
+                <label class="block">
+                    <span class="text-sm text-gray-600">{i18n.t("auth.password")}</span>
+                    <input
+                        name="password"
+                        type="password"
+                        autocomplete="current-password"
+                        class="mt-1 w-full border rounded px-3 py-2"
+                        value={(*password).clone()}
+                        oninput={on_password}
+                    />
+                </label>
+                if let Some(message) = (*error).clone() {
+                    <p class="login-error text-sm text-red-600" role="alert">{message}</p>
+                }
+                <button
+                    type="submit"
+                    class="w-full px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition disabled:opacity-50"
+                    disabled={*submitting}
+                >
+                    {if *submitting { i18n.t("auth.submitting") } else { i18n.t("auth.submit") }}
+                </button>
+            </form>
+            if !api::has_server() {
+                <p class="mt-4 text-sm text-gray-500">{i18n.t("auth.demo_hint")}</p>
+            }
+        </div>
+    }
+}
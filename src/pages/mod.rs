@@ -4,8 +4,14 @@ pub mod home;
 pub mod details;
 pub mod portfolio;
 pub mod not_found;
+pub mod login;
+pub mod arbitrage;
+pub mod simulate;
 
 pub use home::Home;
 pub use details::Details;
 pub use portfolio::Portfolio;
 pub use not_found::NotFound;
+pub use login::Login;
+pub use arbitrage::Arbitrage;
+pub use simulate::Simulate;
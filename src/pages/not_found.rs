@@ -3,10 +3,12 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::app::Route;
+use crate::i18n::use_i18n;
 
 #[function_component(NotFound)]
 pub fn not_found() -> Html {
     let navigator = use_navigator().unwrap();
+    let i18n = use_i18n();
     
     let go_home = {
         let navigator = navigator.clone();
@@ -17,13 +19,13 @@ pub fn not_found() -> Html {
     
     html! {
         <div class="not-found-page flex flex-col items-center justify-center min-h-[60vh] text-center space-y-4">
-            <h2 class="text-3xl font-bold text-red-600">{"404 - Page Not Found"}</h2>
-            <p class="text-lg text-gray-600">{"The page you are looking for does not exist."}</p>
+            <h2 class="text-3xl font-bold text-red-600">{i18n.t("not_found.title")}</h2>
+            <p class="text-lg text-gray-600">{i18n.t("not_found.message")}</p>
             <button
                 class="mt-4 px-6 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition"
                 onclick={go_home}
             >
-                {"Go to Home"}
+                {i18n.t("not_found.go_home")}
             </button>
         </div>
     }
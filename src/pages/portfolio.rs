@@ -1,15 +1,24 @@
 use yew::prelude::*;
 use std::collections::HashMap;
+use gloo::timers::callback::Interval;
 
 use crate::store::{use_store, StoreAction};
 use crate::components::portfolio_item::PortfolioItem;
+use crate::components::rebalance::Rebalancer;
+use crate::components::risk::RiskPanel;
 use crate::components::loading::Loading;
 use crate::components::error::Error;
+use crate::components::income::IncomeTracker;
+use crate::components::sync_status::SyncIndicator;
+use crate::components::wallets::{WalletHoldingItem, WalletManager};
+use crate::config::use_config;
+use crate::i18n::use_i18n;
 use crate::models::crypto::PortfolioItem as PortfolioItemModel;
 
 #[function_component(Portfolio)]
 pub fn portfolio() -> Html {
     let (store, dispatch) = use_store();
+    let i18n = use_i18n();
     
     let on_retry = {
         let dispatch = dispatch.clone();
@@ -52,7 +61,9 @@ pub fn portfolio() -> Html {
     // We need to check these values before potentially moving store
     let is_loading = store.loading;
     let error = store.error.clone();
-    let is_portfolio_empty = store.portfolio.is_empty();
+    // Manual holdings merged with watched wallet balances
+    let holdings = store.holdings();
+    let is_portfolio_empty = holdings.is_empty();
     
     // Clone necessary parts of the store for rendering instead of moving the whole store
     let portfolio = store.portfolio.clone();
@@ -63,10 +74,32 @@ pub fn portfolio() -> Html {
         }
         || ()
     });
+
+    // Read wallet balances whenever the watched wallets change, then on the
+    // portfolio refresh interval
+    {
+        let dispatch = dispatch.clone();
+        let refresh_secs = use_config().refresh.portfolio_secs;
+        use_effect_with((store.wallets.clone(), refresh_secs), move |(wallets, secs)| {
+            let refresh = (!wallets.is_empty()).then(|| {
+                dispatch.emit(StoreAction::FetchWalletBalances(wallets.clone()));
+                let wallets = wallets.clone();
+                (*secs > 0).then(|| {
+                    Interval::new(secs.saturating_mul(1000), move || {
+                        dispatch.emit(StoreAction::FetchWalletBalances(wallets.clone()));
+                    })
+                })
+            });
+            move || drop(refresh)
+        });
+    }
     
     html! {
         <div class="portfolio-page max-w-3xl mx-auto bg-white rounded-lg shadow p-8 mt-8">
-            <h2 class="text-2xl font-bold text-blue-700 mb-6">{"My Portfolio"}</h2>
+            <div class="flex items-center justify-between mb-6">
+                <h2 class="text-2xl font-bold text-blue-700">{i18n.t("portfolio.title")}</h2>
+                <SyncIndicator />
+            </div>
             
             {
                 if is_loading && should_fetch {
@@ -76,38 +109,59 @@ pub fn portfolio() -> Html {
                 } else if is_portfolio_empty {
                     html! {
                         <div class="empty-portfolio text-gray-500 text-center py-8">
-                            <p>{"Your portfolio is empty. Add cryptocurrencies from the Home page."}</p>
+                            <p>{i18n.t("portfolio.empty")}</p>
                         </div>
                     }
                 } else {
                     html! {
                         <>
                             <div class="portfolio-summary flex items-center justify-between bg-blue-50 rounded p-4 mb-6">
-                                <h3 class="text-lg font-semibold text-blue-700">{"Total Value"}</h3>
-                                <p class="total-value text-2xl font-bold text-green-600">{format!("${:.2}", total_value)}</p>
+                                <div>
+                                    <h3 class="text-lg font-semibold text-blue-700">{i18n.t("portfolio.total_value")}</h3>
+                                    <p class="text-sm text-gray-500">{i18n.plural("portfolio.holdings_count", holdings.len() as f64)}</p>
+                                </div>
+                                <p class="total-value text-2xl font-bold text-green-600">{i18n.currency(&total_value)}</p>
                             </div>
                             
                             <div class="portfolio-list space-y-4">
                                 {
-                                    portfolio.iter().map(|item| {
-                                        let cryptocurrency = crypto_map.get(&item.crypto_id).cloned();
+                                    holdings.iter().map(|holding| {
+                                        let cryptocurrency = crypto_map.get(&holding.crypto_id).cloned();
                                         
-                                        html! {
-                                            <PortfolioItem
-                                                key={item.crypto_id.clone()}
-                                                item={item.clone()}
-                                                cryptocurrency={cryptocurrency}
-                                                on_update={on_update_item.clone()}
-                                                on_remove={on_remove_item.clone()}
-                                            />
+                                        match portfolio.iter().find(|item| item.crypto_id == holding.crypto_id) {
+                                            Some(item) => html! {
+                                                <PortfolioItem
+                                                    key={item.crypto_id.clone()}
+                                                    item={item.clone()}
+                                                    cryptocurrency={cryptocurrency}
+                                                    on_update={on_update_item.clone()}
+                                                    on_remove={on_remove_item.clone()}
+                                                    sources={holding.sources.clone()}
+                                                />
+                                            },
+                                            None => html! {
+                                                <WalletHoldingItem
+                                                    key={holding.crypto_id.clone()}
+                                                    holding={holding.clone()}
+                                                    cryptocurrency={cryptocurrency}
+                                                />
+                                            },
                                         }
                                     }).collect::<Html>()
                                 }
                             </div>
+
+                            <RiskPanel />
+
+                            <Rebalancer />
                         </>
                     }
                 }
             }
+
+            <IncomeTracker />
+
+            <WalletManager />
         </div>
     }
 }
\ No newline at end of file
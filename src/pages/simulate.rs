@@ -0,0 +1,337 @@
+// src/pages/simulate.rs
+use std::cell::Cell;
+use std::rc::Rc;
+
+use chrono::{Days, NaiveDate, Utc};
+use futures::future::join_all;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::app::Route;
+use crate::components::error::Error;
+use crate::components::loading::Loading;
+use crate::components::price_chart::{line, Frame, WIDTH};
+use crate::i18n::{use_i18n, I18n};
+use crate::models::crypto::Cryptocurrency;
+use crate::models::history::Candle;
+use crate::models::simulation::{simulate, Outcome, Point, Schedule, Simulation, SimulationError, SimulationSettings};
+use crate::services::api;
+use crate::store::{use_store, StoreAction};
+
+// How far back the history goes
+const MAX_DAYS: u64 = 730;
+const DEFAULT_DAYS: u64 = 90;
+const CHART_HEIGHT: f64 = 200.0;
+
+type History = Vec<(String, Vec<Candle>)>;
+
+fn days_back(today: NaiveDate, days: u64) -> NaiveDate {
+    today.checked_sub_days(Days::new(days)).unwrap_or(today)
+}
+
+fn money(i18n: &I18n, value: f64) -> String {
+    i18n.currency(&Decimal::from_f64(value).unwrap_or_default())
+}
+
+// Replays price history to compare buying on a schedule with investing
+// everything up front
+#[function_component(Simulate)]
+pub fn simulate_page() -> Html {
+    let (store, dispatch) = use_store();
+    let i18n = use_i18n();
+    let today = Utc::now().date_naive();
+    let settings = use_state(|| SimulationSettings {
+        assets: vec!["bitcoin".to_string()],
+        amount: 100.0,
+        schedule: Schedule::Weekly,
+        start: days_back(today, DEFAULT_DAYS),
+        end: today,
+    });
+    let history = use_state(|| None::<Result<History, String>>);
+    let reload = use_state(|| 0_u32);
+
+    {
+        let should_fetch = store.cryptocurrencies.is_empty();
+        use_effect_with((), move |_| {
+            if should_fetch {
+                dispatch.emit(StoreAction::FetchCryptocurrencies);
+            }
+        });
+    }
+
+    // Daily candles from the start of the range until today for every chosen
+    // asset, reloaded when either changes
+    {
+        let history = history.clone();
+        let days = (today - settings.start).num_days().clamp(0, MAX_DAYS as i64) as u32 + 1;
+        use_effect_with((settings.assets.clone(), days, *reload), move |(assets, days, _)| {
+            let current = Rc::new(Cell::new(true));
+            history.set(None);
+            let (assets, days) = (assets.clone(), *days);
+            {
+                let current = current.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let loaded = join_all(assets.iter().map(|id| api::get_price_history(id, days))).await;
+                    if !current.get() {
+                        return;
+                    }
+                    let result: Result<Vec<Vec<Candle>>, _> = loaded.into_iter().collect();
+                    history.set(Some(
+                        result.map(|candles| assets.into_iter().zip(candles).collect()).map_err(|err| err.to_string()),
+                    ));
+                });
+            }
+            move || current.set(false)
+        });
+    }
+
+    let update = |change: fn(&mut SimulationSettings, String)| {
+        let settings = settings.clone();
+        move |value: String| {
+            let mut next = (*settings).clone();
+            change(&mut next, value);
+            settings.set(next);
+        }
+    };
+    let on_asset = {
+        let update = update(|settings, id| match settings.assets.iter().position(|asset| *asset == id) {
+            Some(index) => {
+                settings.assets.remove(index);
+            }
+            None => settings.assets.push(id),
+        });
+        move |id: String| {
+            let update = update.clone();
+            Callback::from(move |_: Event| update(id.clone()))
+        }
+    };
+    let on_amount = {
+        let update = update(|settings, value| {
+            if let Ok(amount) = value.trim().parse::<f64>() {
+                settings.amount = amount;
+            }
+        });
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_schedule = {
+        let update = update(|settings, code| {
+            if let Some(schedule) = Schedule::from_code(&code) {
+                settings.schedule = schedule;
+            }
+        });
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+    let on_start = {
+        let update = update(|settings, value| {
+            if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+                settings.start = date;
+            }
+        });
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_end = {
+        let update = update(|settings, value| {
+            // `max` doesn't stop a typed date
+            if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+                settings.end = date.min(Utc::now().date_naive());
+            }
+        });
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+
+    let earliest = days_back(today, MAX_DAYS).format("%Y-%m-%d").to_string();
+    let latest = today.format("%Y-%m-%d").to_string();
+    let form = html! {
+        <div class="simulate-form space-y-3 mb-6">
+            <fieldset class="flex flex-wrap gap-3">
+                <legend class="text-xs text-gray-500 mb-1">{i18n.t("simulate.assets")}</legend>
+                {for store.cryptocurrencies.iter().map(|crypto| html! {
+                    <label class="flex items-center space-x-1 text-sm">
+                        <input
+                            type="checkbox"
+                            class="simulate-asset"
+                            value={crypto.id.clone()}
+                            checked={settings.assets.contains(&crypto.id)}
+                            onchange={on_asset(crypto.id.clone())}
+                        />
+                        <span>{&crypto.name}</span>
+                    </label>
+                })}
+            </fieldset>
+            <div class="flex flex-wrap items-end gap-4">
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("simulate.amount")}</span>
+                    <input
+                        type="number"
+                        min="1"
+                        step="10"
+                        class="simulate-amount w-28 border rounded px-2 py-1 text-sm text-gray-800"
+                        value={settings.amount.to_string()}
+                        onchange={on_amount}
+                    />
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("simulate.schedule")}</span>
+                    <select class="simulate-schedule border rounded px-2 py-1 text-sm text-gray-800" onchange={on_schedule}>
+                        {for Schedule::ALL.into_iter().map(|schedule| html! {
+                            <option value={schedule.code()} selected={schedule == settings.schedule}>
+                                {i18n.t(&format!("simulate.schedule.{}", schedule.code()))}
+                            </option>
+                        })}
+                    </select>
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("simulate.start")}</span>
+                    <input
+                        type="date"
+                        class="simulate-start border rounded px-2 py-1 text-sm text-gray-800"
+                        min={earliest.clone()}
+                        max={latest.clone()}
+                        value={settings.start.format("%Y-%m-%d").to_string()}
+                        onchange={on_start}
+                    />
+                </label>
+                <label class="flex flex-col text-xs text-gray-500">
+                    <span>{i18n.t("simulate.end")}</span>
+                    <input
+                        type="date"
+                        class="simulate-end border rounded px-2 py-1 text-sm text-gray-800"
+                        min={earliest}
+                        max={latest}
+                        value={settings.end.format("%Y-%m-%d").to_string()}
+                        onchange={on_end}
+                    />
+                </label>
+            </div>
+        </div>
+    };
+
+    let body = match &*history {
+        _ if settings.assets.is_empty() => html! { <p class="simulate-error text-gray-500">{i18n.t("simulate.error.no_assets")}</p> },
+        None => html! { <Loading /> },
+        Some(Err(error)) => {
+            let on_retry = {
+                let reload = reload.clone();
+                Callback::from(move |_| reload.set(*reload + 1))
+            };
+            html! { <Error message={error.clone()} on_retry={Some(on_retry)} /> }
+        }
+        Some(Ok(history)) => match simulate(&settings, history) {
+            Ok(simulation) => results(&i18n, &store.cryptocurrencies, &simulation),
+            Err(error) => {
+                let key = match error {
+                    SimulationError::NoAssets => "simulate.error.no_assets",
+                    SimulationError::InvalidInput => "simulate.error.input",
+                    SimulationError::NoPrices => "simulate.error.no_prices",
+                };
+                html! { <p class="simulate-error text-red-600" role="alert">{i18n.t(key)}</p> }
+            }
+        },
+    };
+
+    html! {
+        <div class="simulate-page max-w-4xl mx-auto bg-white rounded-lg shadow p-8 mt-8">
+            <h2 class="text-2xl font-bold text-blue-700 mb-2">{i18n.t("simulate.title")}</h2>
+            <p class="text-sm text-gray-500 mb-4">{i18n.t("simulate.description")}</p>
+            {form}
+            {body}
+        </div>
+    }
+}
+
+fn outcome_card(i18n: &I18n, class: &'static str, title: &str, outcome: &Outcome) -> Html {
+    let row = |label: &str, class: &'static str, value: String| {
+        html! {
+            <div class="flex justify-between">
+                <dt class="text-gray-500">{label}</dt>
+                <dd class={classes!(class, "font-mono")}>{value}</dd>
+            </div>
+        }
+    };
+    html! {
+        <div class={classes!("simulate-outcome", class, "flex-1", "bg-blue-50", "rounded", "p-4")}>
+            <h3 class="font-semibold text-blue-700 mb-2">{title}</h3>
+            <dl class="text-sm space-y-1">
+                {row(&i18n.t("simulate.invested"), "simulate-invested", money(i18n, outcome.invested))}
+                {row(&i18n.t("simulate.value"), "simulate-value", money(i18n, outcome.value))}
+                {row(&i18n.t("simulate.roi"), "simulate-roi", i18n.signed_percent(outcome.roi_percent))}
+                {row(&i18n.t("simulate.drawdown"), "simulate-drawdown", i18n.percent(-outcome.max_drawdown_percent))}
+            </dl>
+        </div>
+    }
+}
+
+fn results(i18n: &I18n, cryptocurrencies: &[Cryptocurrency], simulation: &Simulation) -> Html {
+    let points = &simulation.points;
+    let series = |value: fn(&Point) -> f64| -> Vec<Option<f64>> {
+        points.iter().map(|point| Some(value(point))).collect()
+    };
+    let (dca, lump_sum, invested) = (
+        series(|point| point.dca_value),
+        series(|point| point.lump_sum_value),
+        series(|point| point.invested),
+    );
+    let frame = Frame::fit(
+        points.len(),
+        dca.iter().chain(&lump_sum).chain(&invested).flatten().copied(),
+        CHART_HEIGHT,
+    );
+
+    let rows = simulation.assets.iter().map(|asset| {
+        let crypto = cryptocurrencies.iter().find(|crypto| crypto.id == asset.id);
+        let quantity = Decimal::from_f64(asset.quantity).unwrap_or_default().round_dp(8).normalize();
+        html! {
+            <tr key={asset.id.clone()} class="simulate-asset-row text-right font-mono border-b">
+                <td class="text-left font-sans py-1">
+                    <Link<Route> to={Route::Details { id: asset.id.clone() }} classes="hover:text-blue-600">
+                        {crypto.map(|crypto| crypto.name.clone()).unwrap_or_else(|| asset.id.clone())}
+                    </Link<Route>>
+                </td>
+                <td class="simulate-quantity">
+                    {i18n.quantity(&quantity)}{" "}{crypto.map(|crypto| crypto.symbol.clone()).unwrap_or_default()}
+                </td>
+                <td>{money(i18n, asset.invested)}</td>
+                <td class="simulate-average-cost">{i18n.price(&Decimal::from_f64(asset.average_cost).unwrap_or_default())}</td>
+                <td>{money(i18n, asset.value)}</td>
+            </tr>
+        }
+    });
+
+    html! {
+        <div class="simulate-results">
+            <p class="simulate-summary text-sm text-gray-600 mb-3">
+                {i18n.plural("simulate.buys", f64::from(simulation.buys))}{" · "}
+                {i18n.t_args("simulate.range", &[("start", i18n.day(&simulation.start)), ("end", i18n.day(&simulation.end))])}
+            </p>
+            <div class="flex flex-col sm:flex-row gap-4 mb-6">
+                {outcome_card(i18n, "simulate-dca", &i18n.t("simulate.dca"), &simulation.dca)}
+                {outcome_card(i18n, "simulate-lump-sum", &i18n.t("simulate.lump_sum"), &simulation.lump_sum)}
+            </div>
+            <svg class="simulate-chart w-full h-48 mb-2" viewBox={format!("0 0 {} {}", WIDTH, CHART_HEIGHT)} preserveAspectRatio="none">
+                {line(&invested, &frame, "chart-invested")}
+                {line(&lump_sum, &frame, "chart-lump-sum")}
+                {line(&dca, &frame, "chart-dca")}
+            </svg>
+            <div class="flex gap-4 text-xs text-gray-500 mb-6">
+                <span class="legend-dca">{i18n.t("simulate.dca")}</span>
+                <span class="legend-lump-sum">{i18n.t("simulate.lump_sum")}</span>
+                <span class="legend-invested">{i18n.t("simulate.invested")}</span>
+            </div>
+            <table class="w-full text-sm">
+                <thead>
+                    <tr class="text-right text-xs text-gray-500 border-b">
+                        <th class="text-left">{i18n.t("simulate.asset")}</th>
+                        <th>{i18n.t("simulate.accumulated")}</th>
+                        <th>{i18n.t("simulate.invested")}</th>
+                        <th>{i18n.t("simulate.average_cost")}</th>
+                        <th>{i18n.t("simulate.value")}</th>
+                    </tr>
+                </thead>
+                <tbody>{for rows}</tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,276 @@
+// src/risk/mod.rs
+//
+// Risk statistics over daily price history. Returns are simple day-over-day
+// changes; annualized figures assume 365 trading days, as crypto markets
+// never close. Anything that needs more data than it's given is `None`.
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::NaiveDate;
+
+use crate::models::history::Candle;
+
+#[cfg(test)]
+mod tests;
+
+pub const PERIODS_PER_YEAR: f64 = 365.0;
+// Asset whose returns stand in for the market when measuring beta
+pub const BENCHMARK: &str = "bitcoin";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RiskMetrics {
+    // Annualized standard deviation of returns, as a fraction
+    pub volatility: Option<f64>,
+    pub sharpe: Option<f64>,
+    pub sortino: Option<f64>,
+    // Largest fall from an earlier high, as a fraction
+    pub max_drawdown: Option<f64>,
+    pub beta: Option<f64>,
+    // One-day losses not exceeded with the report's confidence, as fractions
+    pub var_historical: Option<f64>,
+    pub var_parametric: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RiskReport {
+    // In the order the histories were given
+    pub assets: Vec<(String, RiskMetrics)>,
+    // Holdings weighted by value, rebalanced daily
+    pub portfolio: RiskMetrics,
+    // Pearson correlation of daily returns, `assets` by `assets`
+    pub correlations: Vec<Vec<Option<f64>>>,
+    // Days of returns every asset has
+    pub days: usize,
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+// Sample standard deviation
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+// Day-over-day changes; one fewer than `closes`
+pub fn returns(closes: &[f64]) -> Vec<f64> {
+    closes
+        .windows(2)
+        .map(|pair| if pair[0] == 0.0 { 0.0 } else { pair[1] / pair[0] - 1.0 })
+        .collect()
+}
+
+pub fn volatility(returns: &[f64]) -> Option<f64> {
+    Some(std_dev(returns)? * PERIODS_PER_YEAR.sqrt())
+}
+
+// Annualized excess return per unit of volatility
+pub fn sharpe(returns: &[f64], risk_free_rate: f64) -> Option<f64> {
+    let volatility = volatility(returns)?;
+    let annual = mean(returns)? * PERIODS_PER_YEAR;
+    (volatility > 0.0).then(|| (annual - risk_free_rate) / volatility)
+}
+
+// Like Sharpe, but only returns below the risk-free rate count as risk
+pub fn sortino(returns: &[f64], risk_free_rate: f64) -> Option<f64> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let target = risk_free_rate / PERIODS_PER_YEAR;
+    let downside = returns.iter().map(|value| (value - target).min(0.0).powi(2)).sum::<f64>() / returns.len() as f64;
+    let downside = downside.sqrt() * PERIODS_PER_YEAR.sqrt();
+    let annual = mean(returns)? * PERIODS_PER_YEAR;
+    (downside > 0.0).then(|| (annual - risk_free_rate) / downside)
+}
+
+pub fn max_drawdown(closes: &[f64]) -> Option<f64> {
+    if closes.is_empty() {
+        return None;
+    }
+    let mut peak = f64::MIN;
+    let mut drawdown = 0.0_f64;
+    for close in closes {
+        peak = peak.max(*close);
+        if peak > 0.0 {
+            drawdown = drawdown.max((peak - close) / peak);
+        }
+    }
+    Some(drawdown)
+}
+
+fn covariance(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() != b.len() || a.len() < 2 {
+        return None;
+    }
+    let (mean_a, mean_b) = (mean(a)?, mean(b)?);
+    Some(a.iter().zip(b).map(|(a, b)| (a - mean_a) * (b - mean_b)).sum::<f64>() / (a.len() - 1) as f64)
+}
+
+// Sensitivity of `returns` to `market` returns over the same days
+pub fn beta(returns: &[f64], market: &[f64]) -> Option<f64> {
+    let variance = covariance(market, market)?;
+    (variance > 0.0).then(|| covariance(returns, market).map(|covariance| covariance / variance))?
+}
+
+// Pearson correlation; `None` when either side doesn't move
+pub fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (std_a, std_b) = (std_dev(a)?, std_dev(b)?);
+    (std_a > 0.0 && std_b > 0.0).then(|| covariance(a, b).map(|covariance| (covariance / (std_a * std_b)).clamp(-1.0, 1.0)))?
+}
+
+pub fn correlation_matrix(series: &[Vec<f64>]) -> Vec<Vec<Option<f64>>> {
+    series
+        .iter()
+        .map(|a| series.iter().map(|b| correlation(a, b)).collect())
+        .collect()
+}
+
+// The loss on the worst `1 - confidence` share of days, read off the sorted
+// returns with linear interpolation
+pub fn historical_var(returns: &[f64], confidence: f64) -> Option<f64> {
+    if returns.is_empty() || !(0.0..1.0).contains(&confidence) {
+        return None;
+    }
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = (1.0 - confidence) * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    let quantile = sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64);
+    Some((-quantile).max(0.0))
+}
+
+// The same loss assuming normally distributed returns
+pub fn parametric_var(returns: &[f64], confidence: f64) -> Option<f64> {
+    let z = inverse_normal(confidence)?;
+    Some((z * std_dev(returns)? - mean(returns)?).max(0.0))
+}
+
+// Quantile function of the standard normal distribution, after Acklam;
+// accurate to about 1e-9
+pub fn inverse_normal(p: f64) -> Option<f64> {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    const LOW: f64 = 0.02425;
+
+    if !(p > 0.0 && p < 1.0) {
+        return None;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let value = if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+    Some(value)
+}
+
+// Closes on the days every history has a candle for, oldest first
+pub fn aligned_closes(histories: &[&[Candle]]) -> Vec<Vec<f64>> {
+    let by_day: Vec<BTreeMap<NaiveDate, f64>> = histories
+        .iter()
+        .map(|candles| candles.iter().map(|candle| (candle.timestamp.date_naive(), candle.close)).collect())
+        .collect();
+    let Some((first, rest)) = by_day.split_first() else {
+        return Vec::new();
+    };
+    let days: BTreeSet<NaiveDate> = first.keys().filter(|day| rest.iter().all(|closes| closes.contains_key(day))).copied().collect();
+    by_day.iter().map(|closes| days.iter().map(|day| closes[day]).collect()).collect()
+}
+
+// Metrics of one return series; `closes` is the price or value path behind it
+fn metrics(returns: &[f64], closes: &[f64], market: Option<&[f64]>, risk_free_rate: f64, confidence: f64) -> RiskMetrics {
+    RiskMetrics {
+        volatility: volatility(returns),
+        sharpe: sharpe(returns, risk_free_rate),
+        sortino: sortino(returns, risk_free_rate),
+        max_drawdown: max_drawdown(closes),
+        beta: market.and_then(|market| beta(returns, market)),
+        var_historical: historical_var(returns, confidence),
+        var_parametric: parametric_var(returns, confidence),
+    }
+}
+
+// Metrics of every asset in `histories` and of the portfolio holding them in
+// proportion to `weights` (current values, in the same order). Beta is
+// against `market`'s returns. Only days all histories and the market share
+// are used, so the figures are comparable.
+pub fn analyze(
+    histories: &[(String, Vec<Candle>)],
+    weights: &[f64],
+    market: Option<&[Candle]>,
+    risk_free_rate: f64,
+    confidence: f64,
+) -> RiskReport {
+    let mut series: Vec<&[Candle]> = histories.iter().map(|(_, candles)| candles.as_slice()).collect();
+    series.extend(market);
+    let mut closes = aligned_closes(&series);
+    let market_closes = market.and_then(|_| closes.pop());
+    let market = market_closes.as_deref().map(returns);
+    let market = market.as_deref();
+    let returns: Vec<Vec<f64>> = closes.iter().map(|closes| returns(closes)).collect();
+
+    let assets = histories
+        .iter()
+        .zip(closes.iter().zip(&returns))
+        .map(|((id, _), (closes, returns))| (id.clone(), metrics(returns, closes, market, risk_free_rate, confidence)))
+        .collect();
+
+    let total: f64 = weights.iter().sum();
+    let days = returns.first().map_or(0, Vec::len);
+    let portfolio = if total > 0.0 && weights.len() == returns.len() {
+        let daily: Vec<f64> = (0..days)
+            .map(|day| returns.iter().zip(weights).map(|(returns, weight)| returns[day] * weight / total).sum())
+            .collect();
+        // Growth of one unit, for the drawdown
+        let path: Vec<f64> = std::iter::once(1.0)
+            .chain(daily.iter().scan(1.0, |value, change| {
+                *value *= 1.0 + change;
+                Some(*value)
+            }))
+            .collect();
+        metrics(&daily, &path, market, risk_free_rate, confidence)
+    } else {
+        RiskMetrics::default()
+    };
+
+    RiskReport {
+        assets,
+        portfolio,
+        correlations: correlation_matrix(&returns),
+        days,
+    }
+}
@@ -0,0 +1,153 @@
+// src/risk/tests.rs
+use chrono::{Duration, TimeZone, Utc};
+
+use super::*;
+
+fn candles(closes: &[f64]) -> Vec<Candle> {
+    closes
+        .iter()
+        .enumerate()
+        .map(|(day, close)| Candle {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(day as i64),
+            open: *close,
+            high: *close,
+            low: *close,
+            close: *close,
+            volume: 0.0,
+        })
+        .collect()
+}
+
+fn close(actual: Option<f64>, expected: f64) {
+    let actual = actual.expect("a value");
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+#[test]
+fn returns_and_volatility() {
+    let returns = returns(&[100.0, 110.0, 99.0, 99.0]);
+    assert_eq!(returns.len(), 3);
+    close(Some(returns[0]), 0.1);
+    close(Some(returns[1]), -0.1);
+    close(Some(returns[2]), 0.0);
+
+    // Sample deviation of 0.1, -0.1 and 0 is 0.1
+    close(volatility(&returns), 0.1 * 365.0_f64.sqrt());
+    assert_eq!(volatility(&[0.1]), None);
+}
+
+#[test]
+fn sharpe_and_sortino_scale_excess_return_by_risk() {
+    let returns = [0.02, -0.01, 0.02, -0.01];
+    let annual = 0.005 * 365.0;
+    close(sharpe(&returns, 0.0), annual / volatility(&returns).unwrap());
+    close(sharpe(&returns, 0.5), (annual - 0.5) / volatility(&returns).unwrap());
+
+    // Only the two -1% days count: sqrt((2 * 0.0001) / 4) a day
+    let downside = (0.0002_f64 / 4.0).sqrt() * 365.0_f64.sqrt();
+    close(sortino(&returns, 0.0), annual / downside);
+    // Nothing below the target, so no downside risk to divide by
+    assert_eq!(sortino(&[0.01, 0.02], 0.0), None);
+    assert_eq!(sharpe(&[0.01, 0.01], 0.0), None);
+}
+
+#[test]
+fn max_drawdown_is_the_deepest_fall_from_a_peak() {
+    close(max_drawdown(&[100.0, 120.0, 90.0, 130.0, 104.0]), 0.25);
+    close(max_drawdown(&[1.0, 2.0, 3.0]), 0.0);
+    assert_eq!(max_drawdown(&[]), None);
+}
+
+#[test]
+fn beta_and_correlation_compare_two_series() {
+    let market = [0.01, -0.02, 0.03, 0.0];
+    let levered: Vec<f64> = market.iter().map(|value| value * 2.0).collect();
+    let inverse: Vec<f64> = market.iter().map(|value| -value).collect();
+
+    close(beta(&levered, &market), 2.0);
+    close(beta(&market, &market), 1.0);
+    close(correlation(&levered, &market), 1.0);
+    close(correlation(&inverse, &market), -1.0);
+    assert_eq!(correlation(&[0.0, 0.0, 0.0, 0.0], &market), None);
+    assert_eq!(beta(&market, &[0.0; 4]), None);
+
+    let matrix = correlation_matrix(&[market.to_vec(), inverse]);
+    close(matrix[0][0], 1.0);
+    close(matrix[0][1], -1.0);
+    assert_eq!(matrix[1][0], matrix[0][1]);
+}
+
+#[test]
+fn value_at_risk_from_history_and_from_the_normal_distribution() {
+    // -10% to +9% in 1% steps
+    let returns: Vec<f64> = (-10..10).map(|step| f64::from(step) / 100.0).collect();
+    // 5% of the way up the 19 gaps between the sorted returns
+    close(historical_var(&returns, 0.95), 0.10 - 0.0095);
+    close(historical_var(&returns, 0.0), 0.0);
+    assert_eq!(historical_var(&[], 0.95), None);
+
+    close(inverse_normal(0.5), 0.0);
+    assert!((inverse_normal(0.95).unwrap() - 1.644853627).abs() < 1e-8);
+    assert!((inverse_normal(0.01).unwrap() + 2.326347874).abs() < 1e-8);
+    assert_eq!(inverse_normal(1.0), None);
+
+    let z = inverse_normal(0.99).unwrap();
+    close(parametric_var(&returns, 0.99), z * std_dev(&returns).unwrap() - mean(&returns).unwrap());
+}
+
+#[test]
+fn histories_are_aligned_on_shared_days() {
+    let mut gappy = candles(&[10.0, 11.0, 12.0, 13.0]);
+    gappy.remove(1);
+    let closes = aligned_closes(&[&candles(&[1.0, 2.0, 3.0, 4.0]), &gappy]);
+
+    assert_eq!(closes, [vec![1.0, 3.0, 4.0], vec![10.0, 12.0, 13.0]]);
+    assert!(aligned_closes(&[]).is_empty());
+}
+
+#[test]
+fn portfolio_metrics_weight_assets_by_value() {
+    let histories = [
+        ("bitcoin".to_string(), candles(&[100.0, 110.0, 99.0, 108.9])),
+        ("ethereum".to_string(), candles(&[10.0, 10.0, 10.0, 10.0])),
+    ];
+    let report = analyze(&histories, &[1.0, 1.0], Some(&histories[0].1), 0.0, 0.95);
+
+    assert_eq!(report.days, 3);
+    close(report.assets[0].1.beta, 1.0);
+    close(report.assets[0].1.max_drawdown, 0.1);
+    // A flat asset has no volatility, beta or correlation to speak of
+    close(report.assets[1].1.volatility, 0.0);
+    close(report.assets[1].1.beta, 0.0);
+    assert_eq!(report.correlations[0][1], None);
+    // Half in a flat asset halves both the moves and the beta
+    close(report.portfolio.beta, 0.5);
+    close(report.portfolio.volatility, report.assets[0].1.volatility.unwrap() / 2.0);
+    close(report.portfolio.max_drawdown, 0.05);
+
+    let unweighted = analyze(&histories, &[], None, 0.0, 0.95);
+    assert_eq!(unweighted.portfolio, RiskMetrics::default());
+    assert_eq!(unweighted.assets[0].1.beta, None);
+}
+
+#[test]
+fn replays_the_mock_server_fixtures() {
+    let load = |json: &str| -> Vec<Candle> { serde_json::from_str(json).unwrap() };
+    let bitcoin = load(include_str!("../../mock-server/fixtures/history/bitcoin.json"));
+    let histories = [
+        ("bitcoin".to_string(), bitcoin.clone()),
+        ("ethereum".to_string(), load(include_str!("../../mock-server/fixtures/history/ethereum.json"))),
+    ];
+    let report = analyze(&histories, &[2.0, 1.0], Some(&bitcoin), 0.04, 0.95);
+
+    assert_eq!(report.days, histories[0].1.len() - 1);
+    for (_, metrics) in report.assets.iter().chain([(String::new(), report.portfolio.clone())].iter()) {
+        let volatility = metrics.volatility.unwrap();
+        assert!(volatility > 0.05 && volatility < 3.0, "{}", volatility);
+        assert!(metrics.var_historical.unwrap() > 0.0 && metrics.var_parametric.unwrap() > 0.0);
+        assert!((0.0..1.0).contains(&metrics.max_drawdown.unwrap()));
+    }
+    close(report.assets[0].1.beta, 1.0);
+    let correlation = report.correlations[0][1].unwrap();
+    assert!((-1.0..=1.0).contains(&correlation));
+}
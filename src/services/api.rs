@@ -1,158 +1,544 @@
-
 // src/services/api.rs
-use reqwasm::http::Request;
-use anyhow::Result;
-use serde_json::json;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use futures::future::join_all;
+use reqwasm::http::{Request, Response};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::de::DeserializeOwned;
 
-use crate::models::crypto::{Cryptocurrency, PortfolioItem};
+use crate::config::{self, Exchange};
+use crate::models::auth::{Session, TokenResponse, User};
+use crate::models::crypto::{Cryptocurrency, PortfolioDocument, PortfolioItem};
+use crate::models::exchange::{aggregate, Quote, Ticker};
+use crate::models::history::Candle;
+use crate::models::market::{BookLevel, BookUpdate, OrderBook, Side, Trade};
+use crate::services::session;
 
-// This would normally point to an actual API
-const API_BASE_URL: &str = "https://api.example.com";
+// Errors for a 404 from the market data endpoints
+const NOT_LISTED: &str = "Cryptocurrency not found";
+const NOT_ON_EXCHANGE: &str = "Not listed on this exchange";
 
-// Simulated API calls that return dummy data
+// Every listed asset, priced at the aggregate of the configured exchanges
 pub async fn get_cryptocurrencies() -> Result<Vec<Cryptocurrency>> {
-    // In a real app, this would be:
-    // let response = Request::get(&format!("{}/cryptocurrencies", API_BASE_URL))
-    //     .send()
-    //     .await?
-    //     .json::<Vec<Cryptocurrency>>()
-    //     .await?;
-    // Ok(response)
-    
-    // For this example, we'll simulate API data
-    let dummy_data = vec![
+    Ok(get_markets().await?.into_iter().map(|(crypto, _)| crypto).collect())
+}
+
+// Every listed asset with its quotes on every configured exchange, priced at
+// their aggregate
+pub async fn get_markets() -> Result<Vec<(Cryptocurrency, Vec<Quote>)>> {
+    let cryptocurrencies: Vec<Cryptocurrency> = match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies", base), "Market data not found").await?,
+        None => sample_cryptocurrencies(),
+    };
+    let quotes = join_all(cryptocurrencies.iter().map(|crypto| get_exchange_quotes(&crypto.id))).await;
+    Ok(cryptocurrencies
+        .into_iter()
+        .zip(quotes)
+        .map(|(crypto, quotes)| (with_aggregate_price(crypto, &quotes), quotes))
+        .collect())
+}
+
+pub async fn get_cryptocurrency_details(id: &str) -> Result<Cryptocurrency> {
+    let crypto = get_listing(id).await?;
+    let quotes = get_exchange_quotes(id).await;
+    Ok(with_aggregate_price(crypto, &quotes))
+}
+
+// The price across exchanges instead of the API's single one, which stays when
+// no exchange answers
+fn with_aggregate_price(crypto: Cryptocurrency, quotes: &[Quote]) -> Cryptocurrency {
+    match aggregate(quotes.to_vec()).price {
+        Some(price) => Cryptocurrency { price, ..crypto },
+        None => crypto,
+    }
+}
+
+// The asset as the API lists it, with its single-source price
+async fn get_listing(id: &str) -> Result<Cryptocurrency> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}", base, id), NOT_LISTED).await,
+        None => sample_cryptocurrencies()
+            .into_iter()
+            .find(|crypto| crypto.id == id)
+            .ok_or_else(|| anyhow!(NOT_LISTED)),
+    }
+}
+
+// Daily candles for the last `days` days, oldest first
+pub async fn get_price_history(id: &str, days: u32) -> Result<Vec<Candle>> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}/history?days={}", base, id, days), NOT_LISTED).await,
+        None => {
+            let crypto = get_listing(id).await?;
+            Ok(sample_history(&crypto, days))
+        }
+    }
+}
+
+// Level-2 order book with up to `depth` levels a side
+pub async fn get_order_book(id: &str, depth: usize) -> Result<OrderBook> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}/book?depth={}", base, id, depth), NOT_LISTED).await,
+        None => {
+            let crypto = get_listing(id).await?;
+            Ok(sample_order_book(&crypto, depth))
+        }
+    }
+}
+
+// Book changes after sequence number `since`, oldest first. The sample book
+// doesn't move.
+pub async fn get_book_updates(id: &str, since: u64) -> Result<Vec<BookUpdate>> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}/book/updates?since={}", base, id, since), NOT_LISTED).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+// Latest `limit` trades, newest first
+pub async fn get_trades(id: &str, limit: usize) -> Result<Vec<Trade>> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}/trades?limit={}", base, id, limit), NOT_LISTED).await,
+        None => {
+            let crypto = get_listing(id).await?;
+            Ok(sample_trades(&crypto, limit))
+        }
+    }
+}
+
+// Ticker of `id` on one exchange
+pub async fn get_ticker(exchange: &Exchange, id: &str) -> Result<Ticker> {
+    if let Some(url) = &exchange.url {
+        // A third party, so the session's token isn't sent along
+        let url = format!("{}/cryptocurrencies/{}/ticker", url.trim().trim_end_matches('/'), id);
+        let response = Request::get(&url).send().await.map_err(|_| anyhow::Error::new(Offline))?;
+        return Ok(ensure_ok(response, NOT_ON_EXCHANGE)?.json().await?);
+    }
+    match base_url() {
+        Some(base) => get_json(&format!("{}/exchanges/{}/cryptocurrencies/{}/ticker", base, exchange.id, id), NOT_ON_EXCHANGE).await,
+        None => {
+            let crypto = get_listing(id).await?;
+            Ok(sample_ticker(&crypto, &exchange.id))
+        }
+    }
+}
+
+// Tickers of `id` on every configured exchange, requested concurrently. A
+// failing exchange is reported in its quote rather than failing the rest.
+pub async fn get_exchange_quotes(id: &str) -> Vec<Quote> {
+    let config = config::current();
+    let requests = config.exchanges.iter().map(|exchange| async move {
+        Quote {
+            exchange: exchange.id.clone(),
+            name: exchange.name.clone(),
+            ticker: get_ticker(exchange, id).await.map_err(|err| err.to_string()),
+        }
+    });
+    join_all(requests).await
+}
+
+pub async fn get_portfolio() -> Result<PortfolioDocument> {
+    match base_url() {
+        Some(base) => get_json(&format!("{}/portfolio", base), "No portfolio on this server").await,
+        None => Err(anyhow!("No portfolio server configured")),
+    }
+}
+
+pub enum SaveOutcome {
+    Saved(PortfolioDocument),
+    // Someone else wrote first; carries the server's current copy
+    Conflict(PortfolioDocument),
+}
+
+// Writes `items` on top of `base_revision`. Without a server the write is
+// accepted as is and the portfolio lives in local storage only.
+pub async fn save_portfolio(base_revision: u64, items: Vec<PortfolioItem>) -> Result<SaveOutcome> {
+    let Some(base) = base_url() else {
+        return Ok(SaveOutcome::Saved(PortfolioDocument {
+            revision: base_revision + 1,
+            items,
+        }));
+    };
+
+    let body = serde_json::json!({ "base_revision": base_revision, "items": items }).to_string();
+    let response = send(|| {
+        Request::put(&format!("{}/portfolio", base))
+            .header("Content-Type", "application/json")
+            .body(body.clone())
+    })
+    .await?;
+    if response.status() == 409 {
+        return Ok(SaveOutcome::Conflict(response.json().await?));
+    }
+    Ok(SaveOutcome::Saved(ensure_ok(response, "No portfolio on this server")?.json().await?))
+}
+
+// Signs in and stores the session. Without a server any credentials open a
+// local demo session.
+pub async fn login(username: &str, password: &str) -> Result<Session> {
+    let Some(base) = base_url() else {
+        let session = Session {
+            access_token: "demo".to_string(),
+            refresh_token: "demo".to_string(),
+            expires_at: Utc::now() + Duration::days(365),
+            user: User {
+                id: username.to_string(),
+                name: username.to_string(),
+            },
+        };
+        session::set(Some(session.clone()));
+        return Ok(session);
+    };
+
+    let body = serde_json::json!({ "username": username, "password": password }).to_string();
+    let response = Request::post(&format!("{}/auth/login", base))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|_| anyhow::Error::new(Offline))?;
+    let tokens: TokenResponse = ensure_ok(response, "Sign-in isn't available on this server")?.json().await?;
+    let session = Session::from_tokens(tokens, Utc::now()).ok_or_else(|| anyhow!("Invalid token response"))?;
+    session::set(Some(session.clone()));
+    Ok(session)
+}
+
+// Forgets the session locally and revokes it on the server, best effort
+pub async fn logout() {
+    let Some(current) = session::current() else {
+        return;
+    };
+    session::set(None);
+
+    if let Some(base) = base_url() {
+        let body = serde_json::json!({ "refresh_token": current.refresh_token }).to_string();
+        let result = Request::post(&format!("{}/auth/logout", base))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await;
+        if let Err(err) = result {
+            log::warn!("Failed to revoke session: {}", err);
+        }
+    }
+}
+
+// Whether requests go to a server rather than the bundled sample data
+pub fn has_server() -> bool {
+    base_url().is_some()
+}
+
+// Server configured in `config.json` or at build time (e.g.
+// `API_BASE_URL=http://localhost:8787` in `.env` to develop against
+// `mock-server`). Without one the bundled sample data is used.
+pub fn base_url() -> Option<String> {
+    config::current().api_base_url()
+}
+
+// Returned when a request can't reach the server at all
+#[derive(Debug)]
+pub struct Offline;
+
+impl fmt::Display for Offline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The server could not be reached")
+    }
+}
+
+impl std::error::Error for Offline {}
+
+// Returned for rejected credentials or a session that could not be refreshed
+#[derive(Debug)]
+pub struct Unauthorized;
+
+impl fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Your session has expired, please log in again")
+    }
+}
+
+impl std::error::Error for Unauthorized {}
+
+// Sends the request built by `build` with the session's bearer token,
+// refreshing the token first when it is about to expire and once more if the
+// server rejects it anyway
+async fn send(build: impl Fn() -> Request) -> Result<Response> {
+    if session::current().is_some_and(|current| current.expires_soon(Utc::now())) {
+        // A failed refresh surfaces as a 401 below
+        let _ = refresh_session().await;
+    }
+
+    let response = send_authorized(build()).await?;
+    if response.status() == 401 && refresh_session().await.is_ok() {
+        return send_authorized(build()).await;
+    }
+    Ok(response)
+}
+
+async fn send_authorized(request: Request) -> Result<Response> {
+    let request = match session::access_token() {
+        Some(token) => request.header("Authorization", &format!("Bearer {}", token)),
+        None => request,
+    };
+    request.send().await.map_err(|_| anyhow::Error::new(Offline))
+}
+
+// Swaps the refresh token for a new access token; a rejected refresh ends the session
+async fn refresh_session() -> Result<()> {
+    let (Some(base), Some(current)) = (base_url(), session::current()) else {
+        return Err(anyhow::Error::new(Unauthorized));
+    };
+
+    let body = serde_json::json!({ "refresh_token": current.refresh_token }).to_string();
+    let response = Request::post(&format!("{}/auth/refresh", base))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|_| anyhow::Error::new(Offline))?;
+    if response.status() == 401 {
+        session::set(None);
+        return Err(anyhow::Error::new(Unauthorized));
+    }
+    let tokens: TokenResponse = ensure_ok(response, "Session refresh isn't available on this server")?.json().await?;
+    let session = Session::from_tokens(tokens, Utc::now()).ok_or_else(|| anyhow!("Invalid token response"))?;
+    session::set(Some(session));
+    Ok(())
+}
+
+// `not_found` is the error for a 404, naming what the caller asked for
+async fn get_json<T: DeserializeOwned>(url: &str, not_found: &str) -> Result<T> {
+    let response = send(|| Request::get(url)).await?;
+    Ok(ensure_ok(response, not_found)?.json().await?)
+}
+
+fn ensure_ok(response: Response, not_found: &str) -> Result<Response> {
+    match response.status() {
+        200..=299 => Ok(response),
+        401 => Err(anyhow::Error::new(Unauthorized)),
+        404 => Err(anyhow!("{}", not_found)),
+        429 => Err(anyhow!("Too many requests, please try again in a moment")),
+        status => Err(anyhow!("Request failed: {} {}", status, response.status_text())),
+    }
+}
+
+// Market data used when no server is configured
+pub fn sample_cryptocurrencies() -> Vec<Cryptocurrency> {
+    vec![
         Cryptocurrency {
             id: "bitcoin".to_string(),
             name: "Bitcoin".to_string(),
             symbol: "BTC".to_string(),
-            price: 63542.87,
+            price: dec!(63542.87),
             market_cap: 1245678900000.0,
             volume_24h: 45678900000.0,
             price_change_24h: 2.34,
+            decimals: 8,
         },
         Cryptocurrency {
             id: "ethereum".to_string(),
             name: "Ethereum".to_string(),
             symbol: "ETH".to_string(),
-            price: 3421.65,
+            price: dec!(3421.65),
             market_cap: 412345678900.0,
             volume_24h: 21345678900.0,
             price_change_24h: -1.23,
+            decimals: 18,
         },
         Cryptocurrency {
             id: "solana".to_string(),
             name: "Solana".to_string(),
             symbol: "SOL".to_string(),
-            price: 189.32,
+            price: dec!(189.32),
             market_cap: 86234567890.0,
             volume_24h: 7423456789.0,
             price_change_24h: 5.67,
+            decimals: 9,
         },
         Cryptocurrency {
             id: "cardano".to_string(),
             name: "Cardano".to_string(),
             symbol: "ADA".to_string(),
-            price: 0.93,
+            price: dec!(0.93),
             market_cap: 34256789012.0,
             volume_24h: 1923456789.0,
             price_change_24h: -0.42,
+            decimals: 6,
         },
         Cryptocurrency {
             id: "polkadot".to_string(),
             name: "Polkadot".to_string(),
             symbol: "DOT".to_string(),
-            price: 14.78,
+            price: dec!(14.78),
             market_cap: 18234567890.0,
             volume_24h: 987654321.0,
             price_change_24h: 3.18,
+            decimals: 10,
         },
-    ];
-    
-    // Simulate network delay
-    // gloo::timers::callback::Timeout::new(500, move || {}).forget();
-    
-    Ok(dummy_data)
+    ]
 }
 
-pub async fn get_cryptocurrency_details(id: &str) -> Result<Cryptocurrency> {
-    // In a real app, this would be:
-    // let response = Request::get(&format!("{}/cryptocurrencies/{}", API_BASE_URL, id))
-    //     .send()
-    //     .await?
-    //     .json::<Cryptocurrency>()
-    //     .await?;
-    // Ok(response)
-    
-    // For this example, we'll simulate API data
-    let dummy_data = match id {
-        "bitcoin" => Cryptocurrency {
-            id: "bitcoin".to_string(),
-            name: "Bitcoin".to_string(),
-            symbol: "BTC".to_string(),
-            price: 63542.87,
-            market_cap: 1245678900000.0,
-            volume_24h: 45678900000.0,
-            price_change_24h: 2.34,
-        },
-        "ethereum" => Cryptocurrency {
-            id: "ethereum".to_string(),
-            name: "Ethereum".to_string(),
-            symbol: "ETH".to_string(),
-            price: 3421.65,
-            market_cap: 412345678900.0,
-            volume_24h: 21345678900.0,
-            price_change_24h: -1.23,
-        },
-        "solana" => Cryptocurrency {
-            id: "solana".to_string(),
-            name: "Solana".to_string(),
-            symbol: "SOL".to_string(),
-            price: 189.32,
-            market_cap: 86234567890.0,
-            volume_24h: 7423456789.0,
-            price_change_24h: 5.67,
-        },
-        "cardano" => Cryptocurrency {
-            id: "cardano".to_string(),
-            name: "Cardano".to_string(),
-            symbol: "ADA".to_string(),
-            price: 0.93,
-            market_cap: 34256789012.0,
-            volume_24h: 1923456789.0,
-            price_change_24h: -0.42,
-        },
-        "polkadot" => Cryptocurrency {
-            id: "polkadot".to_string(),
-            name: "Polkadot".to_string(),
-            symbol: "DOT".to_string(),
-            price: 14.78,
-            market_cap: 18234567890.0,
-            volume_24h: 987654321.0,
-            price_change_24h: 3.18,
-        },
-        _ => return Err(anyhow::anyhow!("Cryptocurrency not found")),
+// Deterministic numbers between 0 and 1 seeded from `id`, so sample data
+// looks plausible and stays the same between reloads
+fn sample_random(id: &str) -> impl FnMut() -> f64 {
+    let mut seed = id.bytes().fold(0x9e37_79b9_u32, |hash, byte| {
+        hash.rotate_left(5) ^ u32::from(byte)
+    });
+    move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        f64::from(seed) / f64::from(u32::MAX)
+    }
+}
+
+// Daily walk ending at the asset's current price
+fn sample_history(crypto: &Cryptocurrency, days: u32) -> Vec<Candle> {
+    let mut random = sample_random(&crypto.id);
+    let mut next_step = move || (random() - 0.5) * 0.06;
+
+    let today = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let mut close = crypto.price.to_f64().unwrap_or_default();
+    let mut candles = Vec::with_capacity(days as usize);
+    for offset in 0..days {
+        let open = close / (1.0 + next_step());
+        let spread = (next_step().abs() / 2.0) * open.max(close);
+        candles.push(Candle {
+            timestamp: today - Duration::days(i64::from(offset)),
+            open,
+            high: open.max(close) + spread,
+            low: (open.min(close) - spread).max(0.0),
+            close,
+            volume: crypto.volume_24h * (1.0 + next_step() * 5.0),
+        });
+        close = open;
+    }
+    candles.reverse();
+    candles
+}
+
+// Levels a few basis points apart around the current price
+fn sample_order_book(crypto: &Cryptocurrency, depth: usize) -> OrderBook {
+    let mut random = sample_random(&format!("{}/book", crypto.id));
+    let places = if crypto.price >= Decimal::ONE { 2 } else { 6 };
+    let tick = (crypto.price * Decimal::new(5, 4)).round_dp(places).max(Decimal::new(1, places));
+    // Roughly $20k a level
+    let typical = 20_000.0 / crypto.price.to_f64().unwrap_or(1.0).max(f64::EPSILON);
+    let mut level = |offset: i64| BookLevel {
+        price: crypto.price + tick * Decimal::from(offset),
+        amount: Decimal::from_f64(typical * (0.2 + random() * 1.6))
+            .unwrap_or_default()
+            .round_dp(crypto.decimals.min(4)),
     };
-    
-    // Simulate network delay
-    // gloo::timers::callback::Timeout::new(700, move || {}).forget();
-    
-    Ok(dummy_data)
-}
-
-pub async fn update_portfolio(portfolio: Vec<PortfolioItem>) -> Result<Vec<PortfolioItem>> {
-    // In a real app, this would be:
-    // let response = Request::post(&format!("{}/portfolio", API_BASE_URL))
-    //     .header("Content-Type", "application/json")
-    //     .body(json!(portfolio).to_string())
-    //     .send()
-    //     .await?
-    //     .json::<Vec<PortfolioItem>>()
-    //     .await?;
-    // Ok(response)
-    
-    // For this example, we'll just return the same portfolio
-    // Simulate network delay
-    // gloo::timers::callback::Timeout::new(300, move || {}).forget();
-    
-    Ok(portfolio)
+
+    let depth = depth as i64;
+    OrderBook {
+        sequence: 0,
+        bids: (1..=depth).map(|offset| level(-offset)).collect(),
+        asks: (1..=depth).map(&mut level).collect(),
+    }
+}
+
+fn sample_trades(crypto: &Cryptocurrency, limit: usize) -> Vec<Trade> {
+    let mut random = sample_random(&format!("{}/trades", crypto.id));
+    let places = if crypto.price >= Decimal::ONE { 2 } else { 6 };
+    let typical = 2_000.0 / crypto.price.to_f64().unwrap_or(1.0).max(f64::EPSILON);
+    let now = Utc::now();
+
+    (0..limit)
+        .map(|index| {
+            let drift = Decimal::from_f64((random() - 0.5) * 0.002).unwrap_or_default();
+            Trade {
+                id: (limit - index) as u64,
+                price: (crypto.price * (Decimal::ONE + drift)).round_dp(places),
+                amount: Decimal::from_f64(typical * random()).unwrap_or_default().round_dp(crypto.decimals.min(4)),
+                side: if random() < 0.5 { Side::Buy } else { Side::Sell },
+                timestamp: now - Duration::seconds(index as i64 * 7),
+            }
+        })
+        .collect()
+}
+
+// Each exchange a little off the asset's price with its own spread and
+// share of the volume
+fn sample_ticker(crypto: &Cryptocurrency, exchange: &str) -> Ticker {
+    let mut random = sample_random(&format!("{}/{}", exchange, crypto.id));
+    let places = if crypto.price >= Decimal::ONE { 2 } else { 6 };
+    let offset = Decimal::from_f64((random() - 0.5) * 0.006).unwrap_or_default();
+    let last = (crypto.price * (Decimal::ONE + offset)).round_dp(places);
+    let half_spread = (last * Decimal::from_f64(0.0001 + random() * 0.0004).unwrap_or_default())
+        .round_dp(places)
+        .max(Decimal::new(1, places));
+
+    Ticker {
+        bid: last - half_spread,
+        ask: last + half_spread,
+        last,
+        volume_24h: crypto.volume_24h * (0.05 + random() * 0.3),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_history_ends_at_current_price() {
+        let bitcoin = sample_cryptocurrencies().remove(0);
+        let history = sample_history(&bitcoin, 30);
+
+        assert_eq!(history.len(), 30);
+        assert_eq!(history.last().unwrap().close, bitcoin.price.to_f64().unwrap());
+        assert!(history.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+        assert!(history.windows(2).all(|pair| pair[0].close == pair[1].open));
+        assert!(history
+            .iter()
+            .all(|candle| candle.low <= candle.open.min(candle.close) && candle.high >= candle.open.max(candle.close)));
+    }
+
+    #[test]
+    fn sample_history_is_deterministic() {
+        let bitcoin = sample_cryptocurrencies().remove(0);
+        assert_eq!(sample_history(&bitcoin, 10), sample_history(&bitcoin, 10));
+    }
+
+    #[test]
+    fn sample_order_book_brackets_the_price() {
+        for crypto in sample_cryptocurrencies() {
+            let book = sample_order_book(&crypto, 10);
+
+            assert_eq!((book.bids.len(), book.asks.len()), (10, 10));
+            assert!(book.best_bid().unwrap().price < crypto.price && crypto.price < book.best_ask().unwrap().price);
+            assert!(book.bids.windows(2).all(|pair| pair[0].price > pair[1].price));
+            assert!(book.asks.windows(2).all(|pair| pair[0].price < pair[1].price));
+            assert!(book.bids.iter().chain(&book.asks).all(|level| level.amount > Decimal::ZERO));
+        }
+    }
+
+    #[test]
+    fn sample_trades_are_newest_first() {
+        let bitcoin = sample_cryptocurrencies().remove(0);
+        let trades = sample_trades(&bitcoin, 20);
+
+        assert_eq!(trades.len(), 20);
+        assert!(trades.windows(2).all(|pair| pair[0].timestamp > pair[1].timestamp && pair[0].id > pair[1].id));
+    }
+
+    #[test]
+    fn sample_tickers_differ_by_exchange_and_stay_close_to_the_price() {
+        for crypto in sample_cryptocurrencies() {
+            let tickers: Vec<Ticker> = ["binance", "kraken"].iter().map(|exchange| sample_ticker(&crypto, exchange)).collect();
+
+            assert_ne!(tickers[0], tickers[1]);
+            for ticker in &tickers {
+                assert!(ticker.bid < ticker.last && ticker.last < ticker.ask);
+                assert!((ticker.last - crypto.price).abs() / crypto.price < Decimal::new(5, 3));
+                assert!(ticker.volume_24h > 0.0);
+            }
+        }
+    }
 }
@@ -0,0 +1,609 @@
+// src/services/chain.rs
+//
+// Reads native and token balances of watched wallets. `client()` picks the
+// implementation: node endpoints from `config.json`, the API server's RPC
+// proxy under `/rpc/{chain}`, or made-up balances in sample mode.
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use futures::future::{join, join_all, LocalBoxFuture};
+use futures::FutureExt;
+use reqwasm::http::{Request, Response};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::config::{self, Config};
+use crate::models::token::{Token, TokenList};
+use crate::models::wallet::{Chain, WalletAsset, WalletBalance, WatchedWallet};
+use crate::services::api::Offline;
+
+// SPL Token program, owner of classic token accounts
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+// `balanceOf(address)`
+const BALANCE_OF_SELECTOR: &str = "70a08231";
+
+// A block explorer or node that knows balances, in whole coins
+pub trait ChainClient {
+    fn balance<'a>(&'a self, wallet: &'a WatchedWallet) -> LocalBoxFuture<'a, Result<Decimal>>;
+
+    // Tokens from `tokens` held by `wallet`. Tokens it holds none of may be
+    // left out or reported as zero.
+    fn token_balances<'a>(
+        &'a self,
+        wallet: &'a WatchedWallet,
+        tokens: &'a TokenList,
+    ) -> LocalBoxFuture<'a, Result<Vec<(Token, Decimal)>>>;
+}
+
+// Client for the configured data source
+pub fn client() -> Rc<dyn ChainClient> {
+    let config = config::current();
+    if config.api_base_url().is_none() && config.rpc.is_empty() {
+        Rc::new(FixtureClient::sample())
+    } else {
+        Rc::new(RpcClient::from_config(&config))
+    }
+}
+
+// Balances of `wallets` keyed by `WatchedWallet::key`, read concurrently. A
+// failing wallet is reported in its entry rather than failing the rest; when
+// only its tokens can't be read, the native balance is still shown.
+pub async fn fetch_balances(
+    client: &dyn ChainClient,
+    tokens: &TokenList,
+    wallets: &[WatchedWallet],
+) -> Vec<(String, WalletBalance)> {
+    let requests = wallets.iter().map(|wallet| async move {
+        let (native, held) = join(client.balance(wallet), client.token_balances(wallet, tokens)).await;
+        let balance = match native {
+            Ok(amount) => {
+                let mut assets = vec![WalletAsset::native(wallet.chain, amount)];
+                match held {
+                    Ok(held) => assets.extend(held.into_iter().filter(|(_, amount)| !amount.is_zero()).map(
+                        |(token, amount)| WalletAsset {
+                            asset_id: token.asset_id,
+                            symbol: token.symbol,
+                            token: Some(token.contract),
+                            amount,
+                        },
+                    )),
+                    Err(err) => log::warn!("Cannot read the tokens of {}: {}", wallet.display_name(), err),
+                }
+                WalletBalance::Loaded(assets)
+            }
+            Err(err) => WalletBalance::Failed(err.to_string()),
+        };
+        (wallet.key(), balance)
+    });
+    join_all(requests).await
+}
+
+// Esplora's REST API for Bitcoin, JSON-RPC (`eth_getBalance` and `eth_call`,
+// `getBalance` and `getTokenAccountsByOwner`) for the other chains.
+// Endpoints are third parties, so the session's token isn't sent along.
+pub struct RpcClient {
+    endpoints: BTreeMap<Chain, String>,
+}
+
+impl RpcClient {
+    pub fn new(endpoints: BTreeMap<Chain, String>) -> Self {
+        Self { endpoints }
+    }
+
+    // `config.rpc` where set, else the API server's proxy
+    pub fn from_config(config: &Config) -> Self {
+        let base = config.api_base_url();
+        let endpoints = Chain::ALL
+            .into_iter()
+            .filter_map(|chain| {
+                let url = match config.rpc.get(&chain) {
+                    Some(url) => url.trim().trim_end_matches('/').to_string(),
+                    None => format!("{}/rpc/{}", base.as_deref()?, chain.code()),
+                };
+                Some((chain, url))
+            })
+            .collect();
+        Self::new(endpoints)
+    }
+
+    fn endpoint(&self, chain: Chain) -> Result<&str> {
+        self.endpoints
+            .get(&chain)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("No RPC endpoint configured for {}", chain.name()))
+    }
+
+    async fn fetch(&self, wallet: &WatchedWallet) -> Result<Decimal> {
+        let endpoint = self.endpoint(wallet.chain)?;
+        match wallet.chain {
+            Chain::Bitcoin => {
+                let url = format!("{}/address/{}", endpoint, wallet.address);
+                let response = Request::get(&url).send().await.map_err(|_| anyhow::Error::new(Offline))?;
+                let address: EsploraAddress = ensure_ok(wallet.chain, response)?.json().await?;
+                Ok(address.balance())
+            }
+            Chain::Solana => {
+                let balance: SolanaBalance = call(endpoint, wallet.chain, "getBalance", json!([wallet.address])).await?;
+                from_units(balance.value.into(), wallet.chain.decimals())
+            }
+            chain => {
+                let wei: String = call(endpoint, chain, "eth_getBalance", json!([wallet.address, "latest"])).await?;
+                from_units(decode_uint(&wei)?, chain.decimals())
+            }
+        }
+    }
+
+    async fn fetch_tokens(&self, wallet: &WatchedWallet, tokens: &TokenList) -> Result<Vec<(Token, Decimal)>> {
+        let listed: Vec<&Token> = tokens.on(wallet.chain).collect();
+        if listed.is_empty() {
+            return Ok(Vec::new());
+        }
+        let endpoint = self.endpoint(wallet.chain)?;
+
+        if wallet.chain == Chain::Solana {
+            let params = json!([wallet.address, { "programId": TOKEN_PROGRAM }, { "encoding": "jsonParsed" }]);
+            let accounts: TokenAccounts = call(endpoint, wallet.chain, "getTokenAccountsByOwner", params).await?;
+            return Ok(accounts.holdings(tokens));
+        }
+
+        // One `balanceOf` per listed token, sent as a single batch
+        let calls = listed
+            .iter()
+            .map(|token| {
+                let params = json!([{ "to": token.contract, "data": balance_of_data(&wallet.address) }, "latest"]);
+                ("eth_call", params)
+            })
+            .collect();
+        let results = batch::<String>(endpoint, wallet.chain, calls).await?;
+        let mut held = Vec::new();
+        for (token, result) in listed.into_iter().zip(results) {
+            // A contract that reverts or returns garbage is skipped
+            match result.and_then(|data| from_units(decode_uint(&data)?, token.decimals)) {
+                Ok(amount) => held.push((token.clone(), amount)),
+                Err(err) => log::warn!("Cannot read {} on {}: {}", token.symbol, wallet.chain.name(), err),
+            }
+        }
+        Ok(held)
+    }
+}
+
+impl ChainClient for RpcClient {
+    fn balance<'a>(&'a self, wallet: &'a WatchedWallet) -> LocalBoxFuture<'a, Result<Decimal>> {
+        self.fetch(wallet).boxed_local()
+    }
+
+    fn token_balances<'a>(
+        &'a self,
+        wallet: &'a WatchedWallet,
+        tokens: &'a TokenList,
+    ) -> LocalBoxFuture<'a, Result<Vec<(Token, Decimal)>>> {
+        self.fetch_tokens(wallet, tokens).boxed_local()
+    }
+}
+
+// Balances looked up in a table, for sample mode and tests. Wallets missing
+// from the table hold nothing, or in sample mode a made-up amount of the
+// native coin.
+#[derive(Default)]
+pub struct FixtureClient {
+    balances: HashMap<String, Decimal>,
+    // Contract and amount by `WatchedWallet::key`
+    tokens: HashMap<String, Vec<(String, Decimal)>>,
+    made_up: bool,
+}
+
+impl FixtureClient {
+    pub fn new(balances: impl IntoIterator<Item = (WatchedWallet, Decimal)>) -> Self {
+        Self {
+            balances: balances.into_iter().map(|(wallet, amount)| (wallet.key(), amount)).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn sample() -> Self {
+        Self {
+            made_up: true,
+            ..Self::default()
+        }
+    }
+
+    // Lets `wallet` hold `amount` of the token at `contract`, listed or not
+    pub fn with_token(mut self, wallet: &WatchedWallet, contract: &str, amount: Decimal) -> Self {
+        self.tokens.entry(wallet.key()).or_default().push((contract.to_string(), amount));
+        self
+    }
+}
+
+impl ChainClient for FixtureClient {
+    fn balance<'a>(&'a self, wallet: &'a WatchedWallet) -> LocalBoxFuture<'a, Result<Decimal>> {
+        let balance = match self.balances.get(&wallet.key()) {
+            Some(amount) => *amount,
+            None if self.made_up => sample_balance(wallet),
+            None => Decimal::ZERO,
+        };
+        futures::future::ready(Ok(balance)).boxed_local()
+    }
+
+    fn token_balances<'a>(
+        &'a self,
+        wallet: &'a WatchedWallet,
+        tokens: &'a TokenList,
+    ) -> LocalBoxFuture<'a, Result<Vec<(Token, Decimal)>>> {
+        let held = self
+            .tokens
+            .get(&wallet.key())
+            .into_iter()
+            .flatten()
+            .filter_map(|(contract, amount)| Some((tokens.find(wallet.chain, contract)?.clone(), *amount)))
+            .collect();
+        futures::future::ready(Ok(held)).boxed_local()
+    }
+}
+
+// Stable made-up balance for `wallet`, so the demo has something to show:
+// up to 1 BTC, 10 ETH or 100 SOL
+pub fn sample_balance(wallet: &WatchedWallet) -> Decimal {
+    // FNV-1a
+    let hash = wallet
+        .key()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    let scale = match wallet.chain {
+        Chain::Bitcoin => Decimal::ONE,
+        Chain::Solana => Decimal::ONE_HUNDRED,
+        _ => Decimal::TEN,
+    };
+    (Decimal::new((hash % 10_000) as i64 + 1, 4) * scale).normalize()
+}
+
+#[derive(Deserialize)]
+struct EsploraStats {
+    funded_txo_sum: u64,
+    spent_txo_sum: u64,
+}
+
+#[derive(Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraStats,
+}
+
+impl EsploraAddress {
+    // Confirmed balance in BTC
+    fn balance(&self) -> Decimal {
+        let sats = self.chain_stats.funded_txo_sum.saturating_sub(self.chain_stats.spent_txo_sum);
+        Decimal::new(sats as i64, Chain::Bitcoin.decimals())
+    }
+}
+
+#[derive(Deserialize)]
+struct SolanaBalance {
+    value: u64,
+}
+
+// `getTokenAccountsByOwner` with `jsonParsed` encoding, trimmed to what's read
+#[derive(Deserialize)]
+struct TokenAccounts {
+    value: Vec<TokenAccount>,
+}
+
+#[derive(Deserialize)]
+struct TokenAccount {
+    account: TokenAccountData,
+}
+
+#[derive(Deserialize)]
+struct TokenAccountData {
+    data: ParsedAccount,
+}
+
+#[derive(Deserialize)]
+struct ParsedAccount {
+    parsed: ParsedInfo,
+}
+
+#[derive(Deserialize)]
+struct ParsedInfo {
+    info: TokenAccountInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenAccountInfo {
+    mint: String,
+    token_amount: TokenAmount,
+}
+
+#[derive(Deserialize)]
+struct TokenAmount {
+    // Raw units, as a string since they can exceed 2^53
+    amount: String,
+    decimals: u32,
+}
+
+impl TokenAccounts {
+    // Listed mints the owner holds, summed over their token accounts. Mints
+    // not on the list, such as airdropped spam, are ignored.
+    fn holdings(&self, tokens: &TokenList) -> Vec<(Token, Decimal)> {
+        let mut held: Vec<(Token, Decimal)> = Vec::new();
+        for account in &self.value {
+            let info = &account.account.data.parsed.info;
+            let Some(token) = tokens.find(Chain::Solana, &info.mint) else {
+                continue;
+            };
+            let Some(amount) = info
+                .token_amount
+                .amount
+                .parse::<u128>()
+                .ok()
+                .and_then(|units| from_units(units, info.token_amount.decimals).ok())
+            else {
+                continue;
+            };
+            match held.iter_mut().find(|(listed, _)| listed.contract == token.contract) {
+                Some((_, total)) => *total += amount,
+                None => held.push((token.clone(), amount)),
+            }
+        }
+        held
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    #[serde(default)]
+    id: Option<usize>,
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+impl<T> RpcResponse<T> {
+    fn into_result(self) -> Result<T> {
+        match (self.result, self.error) {
+            (_, Some(error)) => Err(anyhow!(error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(anyhow!("Empty RPC response")),
+        }
+    }
+}
+
+async fn post(endpoint: &str, chain: Chain, body: Value) -> Result<Response> {
+    let response = Request::post(endpoint)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|_| anyhow::Error::new(Offline))?;
+    ensure_ok(chain, response)
+}
+
+async fn call<T: DeserializeOwned>(endpoint: &str, chain: Chain, method: &str, params: Value) -> Result<T> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let response: RpcResponse<T> = post(endpoint, chain, body).await?.json().await?;
+    response.into_result()
+}
+
+// Sends `calls` as one JSON-RPC batch; results come back in the same order
+async fn batch<T: DeserializeOwned>(endpoint: &str, chain: Chain, calls: Vec<(&str, Value)>) -> Result<Vec<Result<T>>> {
+    let count = calls.len();
+    let body: Vec<Value> = calls
+        .into_iter()
+        .enumerate()
+        .map(|(id, (method, params))| json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+        .collect();
+    let responses: Vec<RpcResponse<T>> = post(endpoint, chain, Value::Array(body)).await?.json().await?;
+    Ok(order_batch(responses, count))
+}
+
+// Nodes may answer a batch in any order
+fn order_batch<T>(responses: Vec<RpcResponse<T>>, count: usize) -> Vec<Result<T>> {
+    let mut results: Vec<Result<T>> = (0..count).map(|_| Err(anyhow!("Missing from the batch response"))).collect();
+    for response in responses {
+        if let Some(slot) = response.id.and_then(|id| results.get_mut(id)) {
+            *slot = response.into_result();
+        }
+    }
+    results
+}
+
+fn ensure_ok(chain: Chain, response: Response) -> Result<Response> {
+    match response.status() {
+        200..=299 => Ok(response),
+        429 => Err(anyhow!("Too many requests, please try again in a moment")),
+        status => Err(anyhow!("{} node answered {} {}", chain.name(), status, response.status_text())),
+    }
+}
+
+// Calldata for `balanceOf(owner)`: the selector and the address padded to a
+// 32-byte word
+fn balance_of_data(owner: &str) -> String {
+    format!("0x{}{:0>64}", BALANCE_OF_SELECTOR, owner.trim_start_matches("0x"))
+}
+
+// A hex quantity (`0x1bc1…`) or ABI-encoded uint256 word. Values that don't
+// fit in 128 bits are rejected rather than truncated.
+fn decode_uint(hex: &str) -> Result<u128> {
+    let digits = hex.strip_prefix("0x").ok_or_else(|| anyhow!("Invalid quantity `{}`", hex))?;
+    let digits = digits.trim_start_matches('0');
+    if digits.len() > 32 {
+        return Err(anyhow!("Quantity `{}` is out of range", hex));
+    }
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(digits, 16).map_err(|_| anyhow!("Invalid quantity `{}`", hex))
+}
+
+// `units` of the smallest denomination, in whole coins
+fn from_units(units: u128, decimals: u32) -> Result<Decimal> {
+    i128::try_from(units)
+        .ok()
+        .and_then(|units| Decimal::try_from_i128_with_scale(units, decimals).ok())
+        .map(|amount| amount.normalize())
+        .ok_or_else(|| anyhow!("Balance of {} units is out of range", units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use rust_decimal_macros::dec;
+
+    const OWNER: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+    const WBTC: &str = "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599";
+
+    fn wallet(chain: Chain, address: &str) -> WatchedWallet {
+        WatchedWallet {
+            chain,
+            address: address.to_string(),
+            label: None,
+        }
+    }
+
+    fn loaded(balance: &WalletBalance) -> Vec<(&str, Decimal)> {
+        match balance {
+            WalletBalance::Loaded(assets) => assets.iter().map(|asset| (asset.symbol.as_str(), asset.amount)).collect(),
+            WalletBalance::Failed(message) => panic!("balance failed: {}", message),
+        }
+    }
+
+    #[test]
+    fn fixture_client_reports_known_balances_and_zero_otherwise() {
+        let known = wallet(Chain::Ethereum, OWNER);
+        let unknown = wallet(Chain::Solana, "11111111111111111111111111111111");
+        let client = FixtureClient::new([(known.clone(), dec!(1.5))]);
+
+        let balances = block_on(fetch_balances(&client, &TokenList::default(), &[known.clone(), unknown.clone()]));
+        assert_eq!(balances[0].0, known.key());
+        assert_eq!(loaded(&balances[0].1), [("ETH", dec!(1.5))]);
+        assert_eq!(loaded(&balances[1].1), [("SOL", Decimal::ZERO)]);
+    }
+
+    #[test]
+    fn listed_tokens_follow_the_native_balance() {
+        let owner = wallet(Chain::Ethereum, OWNER);
+        let client = FixtureClient::new([(owner.clone(), dec!(1))])
+            .with_token(&owner, WBTC, dec!(0.2))
+            .with_token(&owner, "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2", Decimal::ZERO)
+            .with_token(&owner, "0x000000000000000000000000000000000000dead", dec!(1000000));
+
+        let balances = block_on(fetch_balances(&client, &TokenList::default(), std::slice::from_ref(&owner)));
+        // Unlisted spam and empty balances are left out
+        assert_eq!(loaded(&balances[0].1), [("ETH", dec!(1)), ("WBTC", dec!(0.2))]);
+        let WalletBalance::Loaded(assets) = &balances[0].1 else { unreachable!() };
+        assert_eq!(assets[1].asset_id, "bitcoin");
+        assert_eq!(assets[1].token.as_deref(), Some(WBTC));
+
+        let denied = TokenList::new(&[], &[WBTC.to_string()]);
+        let balances = block_on(fetch_balances(&client, &denied, &[owner]));
+        assert_eq!(loaded(&balances[0].1), [("ETH", dec!(1))]);
+    }
+
+    #[test]
+    fn sample_balances_are_stable_and_in_range() {
+        let bitcoin = wallet(Chain::Bitcoin, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        let balance = block_on(FixtureClient::sample().balance(&bitcoin)).unwrap();
+
+        assert_eq!(balance, sample_balance(&bitcoin));
+        assert!(balance > Decimal::ZERO && balance <= Decimal::ONE);
+        assert_ne!(sample_balance(&bitcoin), sample_balance(&wallet(Chain::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa")));
+    }
+
+    #[test]
+    fn node_responses_are_converted_to_whole_coins() {
+        let address: EsploraAddress =
+            serde_json::from_str(r#"{ "chain_stats": { "funded_txo_sum": 250000000, "spent_txo_sum": 50000000, "tx_count": 3 } }"#).unwrap();
+        assert_eq!(address.balance(), dec!(2));
+
+        assert_eq!(from_units(decode_uint("0x1bc16d674ec80000").unwrap(), 18).unwrap(), dec!(2));
+        assert_eq!(decode_uint("0x0").unwrap(), 0);
+        assert!(decode_uint("1bc16d674ec80000").is_err());
+
+        let ok: RpcResponse<SolanaBalance> = serde_json::from_str(r#"{ "jsonrpc": "2.0", "id": 1, "result": { "context": { "slot": 1 }, "value": 1500000000 } }"#).unwrap();
+        assert_eq!(ok.into_result().unwrap().value, 1_500_000_000);
+        let failed: RpcResponse<String> = serde_json::from_str(r#"{ "jsonrpc": "2.0", "id": 1, "error": { "code": -32602, "message": "invalid address" } }"#).unwrap();
+        assert_eq!(failed.into_result().unwrap_err().to_string(), "invalid address");
+    }
+
+    #[test]
+    fn encodes_balance_of_and_decodes_uint256_words() {
+        assert_eq!(
+            balance_of_data(OWNER),
+            "0x70a08231000000000000000000000000de0b295669a9fd93d5f28d9ec85e40f4cb697bae"
+        );
+        // 0.2 WBTC as a full 32-byte word
+        let word = format!("0x{:064x}", 20_000_000);
+        assert_eq!(from_units(decode_uint(&word).unwrap(), 8).unwrap(), dec!(0.2));
+        // Calls to an address without code return no data
+        assert_eq!(decode_uint("0x").unwrap(), 0);
+        assert!(decode_uint(&format!("0x1{:064x}", 0)).is_err());
+        assert!(from_units(u128::MAX, 18).is_err());
+    }
+
+    #[test]
+    fn batch_results_are_matched_by_id() {
+        let responses: Vec<RpcResponse<String>> = serde_json::from_str(
+            r#"[
+                { "jsonrpc": "2.0", "id": 2, "result": "0x2" },
+                { "jsonrpc": "2.0", "id": 0, "error": { "code": 3, "message": "execution reverted" } }
+            ]"#,
+        )
+        .unwrap();
+        let results = order_batch(responses, 3);
+
+        assert_eq!(results[0].as_ref().unwrap_err().to_string(), "execution reverted");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_deref().ok(), Some("0x2"));
+    }
+
+    #[test]
+    fn spl_token_accounts_are_summed_per_listed_mint() {
+        let account = |mint: &str, amount: &str, decimals: u32| {
+            json!({
+                "pubkey": "account",
+                "account": { "data": { "parsed": { "info": { "mint": mint, "owner": "owner", "tokenAmount": {
+                    "amount": amount, "decimals": decimals, "uiAmountString": "ignored"
+                } }, "type": "account" }, "program": "spl-token" } }
+            })
+        };
+        let accounts: TokenAccounts = serde_json::from_value(json!({
+            "context": { "slot": 1 },
+            "value": [
+                account("3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh", "10000000", 8),
+                account("3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh", "5000000", 8),
+                account("So11111111111111111111111111111111111111112", "2500000000", 9),
+                // Airdropped spam
+                account("SpamMint1111111111111111111111111111111111", "1000000000000", 6),
+            ],
+        }))
+        .unwrap();
+
+        let held = accounts.holdings(&TokenList::default());
+        let held: Vec<(&str, Decimal)> = held.iter().map(|(token, amount)| (token.symbol.as_str(), *amount)).collect();
+        assert_eq!(held, [("WBTC", dec!(0.15)), ("wSOL", dec!(2.5))]);
+    }
+
+    #[test]
+    fn endpoints_come_from_config_then_the_api_server() {
+        let config = Config::parse(
+            r#"{ "provider": "api", "api_base_url": "https://api.example.com/", "rpc": { "solana": "https://solana.example.com/" } }"#,
+        )
+        .unwrap();
+        let client = RpcClient::from_config(&config);
+
+        assert_eq!(client.endpoints[&Chain::Solana], "https://solana.example.com");
+        assert_eq!(client.endpoints[&Chain::Base], "https://api.example.com/rpc/base");
+
+        let client = RpcClient::from_config(&Config::parse(r#"{ "rpc": { "bitcoin": "https://mempool.example.com/api" } }"#).unwrap());
+        assert_eq!(client.endpoints.len(), 1);
+        let error = block_on(client.balance(&wallet(Chain::Ethereum, "0x00"))).unwrap_err();
+        assert_eq!(error.to_string(), "No RPC endpoint configured for Ethereum");
+    }
+}
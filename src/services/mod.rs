@@ -1,2 +1,4 @@
 // src/services/mod.rs
 pub mod api;
+pub mod chain;
+pub mod session;
@@ -0,0 +1,55 @@
+// src/services/session.rs
+//
+// The signed-in session, shared between `services::api` (which attaches and
+// refreshes tokens) and `AuthProvider` (which renders it).
+use std::cell::RefCell;
+
+use gloo::storage::{LocalStorage, Storage};
+use yew::Callback;
+
+use crate::models::auth::Session;
+
+const SESSION_STORAGE_KEY: &str = "crypto_tracker.session";
+
+thread_local! {
+    static SESSION: RefCell<Option<Option<Session>>> = const { RefCell::new(None) };
+    static LISTENER: RefCell<Option<Callback<Option<Session>>>> = const { RefCell::new(None) };
+}
+
+// Loaded from local storage on first use
+pub fn current() -> Option<Session> {
+    SESSION.with(|session| {
+        session
+            .borrow_mut()
+            .get_or_insert_with(|| LocalStorage::get(SESSION_STORAGE_KEY).ok())
+            .clone()
+    })
+}
+
+pub fn access_token() -> Option<String> {
+    current().map(|session| session.access_token)
+}
+
+// Persists `session` and tells the listener, if any
+pub fn set(session: Option<Session>) {
+    let result = match &session {
+        Some(session) => LocalStorage::set(SESSION_STORAGE_KEY, session),
+        None => {
+            LocalStorage::delete(SESSION_STORAGE_KEY);
+            Ok(())
+        }
+    };
+    if let Err(err) = result {
+        log::warn!("Failed to persist session: {}", err);
+    }
+
+    SESSION.with(|current| *current.borrow_mut() = Some(session.clone()));
+    if let Some(listener) = LISTENER.with(|listener| listener.borrow().clone()) {
+        listener.emit(session);
+    }
+}
+
+// Notified when the session changes outside the UI, e.g. on token refresh
+pub fn subscribe(listener: Option<Callback<Option<Session>>>) {
+    LISTENER.with(|current| *current.borrow_mut() = listener);
+}
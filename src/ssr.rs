@@ -0,0 +1,87 @@
+// src/ssr.rs
+//
+// State handed from a server-rendered page to the client. `ssr-server`
+// renders `ServerApp` with it and embeds it in the page as JSON; `main.rs`
+// reads it back and hydrates `App` from the same state, so that the first
+// client render matches the HTML it takes over.
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::i18n::Locale;
+use crate::store::Store;
+
+// Id of the `<script type="application/json">` element holding the state
+pub const INITIAL_STATE_ID: &str = "initial-state";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InitialState {
+    // Path the page was rendered for
+    pub path: String,
+    // The server's `config.json`, used instead of fetching it again
+    pub config: Config,
+    pub locale: Locale,
+    // Market data prefetched for the route; the portfolio stays on the client
+    pub store: Store,
+}
+
+impl InitialState {
+    // JSON that can be placed inside a script element as is
+    pub fn to_script(&self) -> String {
+        serde_json::to_string(self)
+            .expect("initial state is serializable")
+            .replace('<', "\\u003c")
+    }
+
+    pub fn from_script(text: &str) -> Option<Self> {
+        serde_json::from_str(text)
+            .map_err(|err| log::warn!("Ignoring unreadable initial state: {}", err))
+            .ok()
+    }
+
+    // State embedded in the current page, if it was server-rendered
+    pub fn from_document() -> Option<Self> {
+        let element = gloo::utils::document().get_element_by_id(INITIAL_STATE_ID)?;
+        Self::from_script(&element.text_content()?)
+    }
+
+    // Pages can be served for another route than they were rendered for,
+    // e.g. the service worker's offline fallback
+    pub fn is_for_current_page(&self) -> bool {
+        gloo::utils::window().location().pathname().is_ok_and(|path| path == self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::crypto::Cryptocurrency;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn script_round_trips_and_cannot_close_its_element() {
+        let state = InitialState {
+            path: "/details/evil".to_string(),
+            config: Config::default(),
+            locale: Locale::De,
+            store: Store {
+                selected_cryptocurrency: Some(Cryptocurrency {
+                    id: "evil".to_string(),
+                    name: "</script><script>alert(1)</script>".to_string(),
+                    symbol: "EVL".to_string(),
+                    price: dec!(1.5),
+                    market_cap: 0.0,
+                    volume_24h: 0.0,
+                    price_change_24h: 0.0,
+                    decimals: 8,
+                }),
+                ..Store::new()
+            },
+        };
+
+        let script = state.to_script();
+        assert!(!script.contains('<'));
+        assert!(script.contains(r#""locale":"de""#));
+        assert_eq!(InitialState::from_script(&script), Some(state));
+        assert_eq!(InitialState::from_script("{"), None);
+    }
+}
@@ -0,0 +1,201 @@
+// src/store/history.rs
+use std::collections::VecDeque;
+
+use crate::models::crypto::PortfolioItem;
+
+// Maximum number of portfolio edits that can be undone
+pub const HISTORY_LIMIT: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortfolioEdit {
+    Add(String),
+    Update(String),
+    Remove(String),
+}
+
+impl PortfolioEdit {
+    pub fn crypto_id(&self) -> &str {
+        match self {
+            PortfolioEdit::Add(id) | PortfolioEdit::Update(id) | PortfolioEdit::Remove(id) => id,
+        }
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, PortfolioEdit::Remove(_))
+    }
+}
+
+// Portfolio as it was on one side of an edit
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub edit: PortfolioEdit,
+    pub portfolio: Vec<PortfolioItem>,
+}
+
+// Undo/redo stacks for portfolio edits
+#[derive(Clone, Debug, PartialEq)]
+pub struct History {
+    past: VecDeque<Snapshot>,
+    future: Vec<Snapshot>,
+    limit: usize,
+    // Bumped on every recorded edit so views can tell fresh edits apart
+    revision: u64,
+}
+
+impl History {
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            past: VecDeque::new(),
+            future: Vec::new(),
+            limit,
+            revision: 0,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.past.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.past.is_empty()
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn last_edit(&self) -> Option<&PortfolioEdit> {
+        self.past.back().map(|snapshot| &snapshot.edit)
+    }
+
+    // Record the portfolio as it was before `edit`; clears the redo stack
+    pub fn record(&mut self, edit: PortfolioEdit, previous: Vec<PortfolioItem>) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.past.len() == self.limit {
+            self.past.pop_front();
+        }
+        self.past.push_back(Snapshot {
+            edit,
+            portfolio: previous,
+        });
+        self.future.clear();
+        self.revision += 1;
+    }
+
+    // Rewrites every saved portfolio, e.g. to carry changes pulled from the
+    // server into the states undo and redo go back to
+    pub fn rebase(&mut self, rewrite: impl Fn(&[PortfolioItem]) -> Vec<PortfolioItem>) {
+        for snapshot in self.past.iter_mut().chain(self.future.iter_mut()) {
+            snapshot.portfolio = rewrite(&snapshot.portfolio);
+        }
+    }
+
+    // Returns the portfolio to restore, saving `current` for redo
+    pub fn undo(&mut self, current: Vec<PortfolioItem>) -> Option<Vec<PortfolioItem>> {
+        let snapshot = self.past.pop_back()?;
+        self.future.push(Snapshot {
+            edit: snapshot.edit,
+            portfolio: current,
+        });
+        Some(snapshot.portfolio)
+    }
+
+    // Returns the portfolio to restore, saving `current` for undo
+    pub fn redo(&mut self, current: Vec<PortfolioItem>) -> Option<Vec<PortfolioItem>> {
+        let snapshot = self.future.pop()?;
+        self.past.push_back(Snapshot {
+            edit: snapshot.edit,
+            portfolio: current,
+        });
+        Some(snapshot.portfolio)
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::with_limit(HISTORY_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn portfolio(amount: rust_decimal::Decimal) -> Vec<PortfolioItem> {
+        vec![PortfolioItem {
+            crypto_id: "bitcoin".to_string(),
+            amount,
+        }]
+    }
+
+    #[test]
+    fn undo_and_redo_swap_snapshots() {
+        let mut history = History::default();
+        history.record(PortfolioEdit::Update("bitcoin".to_string()), portfolio(dec!(1)));
+
+        assert_eq!(history.undo(portfolio(dec!(2))), Some(portfolio(dec!(1))));
+        assert!(!history.can_undo());
+        assert_eq!(history.redo(portfolio(dec!(1))), Some(portfolio(dec!(2))));
+        assert!(!history.can_redo());
+        assert_eq!(history.last_edit(), Some(&PortfolioEdit::Update("bitcoin".to_string())));
+    }
+
+    #[test]
+    fn recording_clears_redo_stack() {
+        let mut history = History::default();
+        history.record(PortfolioEdit::Add("bitcoin".to_string()), Vec::new());
+        history.undo(portfolio(dec!(1)));
+        assert!(history.can_redo());
+
+        history.record(PortfolioEdit::Add("ethereum".to_string()), Vec::new());
+        assert!(!history.can_redo());
+        assert_eq!(history.revision(), 2);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = History::with_limit(3);
+        for amount in 1..=5 {
+            history.record(PortfolioEdit::Update("bitcoin".to_string()), portfolio(amount.into()));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.undo(Vec::new()), Some(portfolio(dec!(5))));
+        assert_eq!(history.undo(Vec::new()), Some(portfolio(dec!(4))));
+        assert_eq!(history.undo(Vec::new()), Some(portfolio(dec!(3))));
+        assert_eq!(history.undo(Vec::new()), None);
+    }
+
+    #[test]
+    fn rebase_rewrites_both_stacks() {
+        let mut history = History::default();
+        history.record(PortfolioEdit::Update("bitcoin".to_string()), portfolio(dec!(1)));
+        history.record(PortfolioEdit::Update("bitcoin".to_string()), portfolio(dec!(2)));
+        history.undo(portfolio(dec!(3)));
+
+        history.rebase(|items| items.iter().map(|item| PortfolioItem { amount: item.amount * dec!(10), ..item.clone() }).collect());
+        assert_eq!(history.redo(Vec::new()), Some(portfolio(dec!(30))));
+        assert_eq!(history.undo(Vec::new()), Some(Vec::new()));
+        assert_eq!(history.undo(Vec::new()), Some(portfolio(dec!(10))));
+        assert_eq!(history.revision(), 2);
+    }
+
+    #[test]
+    fn empty_stacks_return_none() {
+        let mut history = History::default();
+        assert_eq!(history.undo(Vec::new()), None);
+        assert_eq!(history.redo(Vec::new()), None);
+        assert!(history.is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+// src/store/holdings.rs
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::models::crypto::PortfolioItem;
+use crate::models::income::{self, IncomeEvent};
+use crate::models::wallet::{WalletAsset, WalletBalance, WatchedWallet};
+
+// Where part of a holding comes from
+#[derive(Clone, Debug, PartialEq)]
+pub enum HoldingSource {
+    // Typed in on the portfolio page
+    Manual,
+    // Read from a watched wallet, as its native coin or the token with this
+    // symbol
+    Wallet {
+        wallet: WatchedWallet,
+        token: Option<String>,
+    },
+    // Received as staking rewards, interest and other income
+    Income,
+}
+
+// Everything held of one asset, across the manual portfolio and wallets
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holding {
+    pub crypto_id: String,
+    pub sources: Vec<(HoldingSource, Decimal)>,
+}
+
+impl Holding {
+    pub fn amount(&self) -> Decimal {
+        self.sources.iter().map(|(_, amount)| *amount).sum()
+    }
+
+    // The typed-in amount, if the asset is in the manual portfolio
+    pub fn manual(&self) -> Option<Decimal> {
+        self.sources
+            .iter()
+            .find(|(source, _)| *source == HoldingSource::Manual)
+            .map(|(_, amount)| *amount)
+    }
+
+    pub fn has_wallets(&self) -> bool {
+        self.sources.iter().any(|(source, _)| matches!(source, HoldingSource::Wallet { .. }))
+    }
+}
+
+// Manual items in portfolio order, then assets only held in wallets or
+// received as income. Wallets without a loaded balance are left out; a wallet
+// holding an asset both natively and as a token adds a source for each.
+// Income paid into a watched wallet is left out, as it's already part of the
+// wallet's balance.
+pub fn merge(
+    portfolio: &[PortfolioItem],
+    wallets: &[WatchedWallet],
+    balances: &HashMap<String, WalletBalance>,
+    income: &[IncomeEvent],
+) -> Vec<Holding> {
+    let mut holdings: Vec<Holding> = portfolio
+        .iter()
+        .map(|item| Holding {
+            crypto_id: item.crypto_id.clone(),
+            sources: vec![(HoldingSource::Manual, item.amount)],
+        })
+        .collect();
+
+    for wallet in wallets {
+        let Some(WalletBalance::Loaded(assets)) = balances.get(&wallet.key()) else {
+            continue;
+        };
+        for WalletAsset { asset_id, symbol, token, amount } in assets {
+            let source = HoldingSource::Wallet {
+                wallet: wallet.clone(),
+                token: token.as_ref().map(|_| symbol.clone()),
+            };
+            match holdings.iter_mut().find(|holding| holding.crypto_id == *asset_id) {
+                Some(holding) => holding.sources.push((source, *amount)),
+                None => holdings.push(Holding {
+                    crypto_id: asset_id.clone(),
+                    sources: vec![(source, *amount)],
+                }),
+            }
+        }
+    }
+
+    let income: Vec<IncomeEvent> = income
+        .iter()
+        .filter(|event| event.wallet.as_ref().is_none_or(|key| !wallets.iter().any(|wallet| wallet.key() == *key)))
+        .cloned()
+        .collect();
+    for event in &income {
+        if holdings.iter().any(|holding| holding.crypto_id == event.crypto_id) {
+            continue;
+        }
+        holdings.push(Holding {
+            crypto_id: event.crypto_id.clone(),
+            sources: Vec::new(),
+        });
+    }
+    for holding in &mut holdings {
+        let received = income::received(&income, &holding.crypto_id);
+        if !received.is_zero() {
+            holding.sources.push((HoldingSource::Income, received));
+        }
+    }
+    holdings.retain(|holding| !holding.sources.is_empty());
+    holdings
+}
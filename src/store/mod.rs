@@ -1,154 +1,68 @@
 use std::rc::Rc;
-use std::collections::HashMap;
+use anyhow::anyhow;
+use gloo::events::EventListener;
+use gloo::storage::{LocalStorage, Storage};
+use gloo::timers::callback::Interval;
 use yew::prelude::*;
 
-use crate::models::crypto::{Cryptocurrency, PortfolioItem};
-use crate::services::api;
-
-// Define our application state
-#[derive(Clone, PartialEq)]
-pub struct Store {
-    pub cryptocurrencies: Vec<Cryptocurrency>,
-    pub portfolio: Vec<PortfolioItem>,
-    pub selected_cryptocurrency: Option<Cryptocurrency>,
-    pub loading: bool,
-    pub error: Option<String>,
-}
+use crate::config::{self, use_config};
+use crate::models::crypto::{PortfolioDocument, PortfolioItem};
+use crate::models::income::IncomeEvent;
+use crate::models::token::TokenList;
+use crate::models::wallet::WatchedWallet;
+use crate::services::api::{self, SaveOutcome};
+use crate::services::{chain, session};
 
-impl Store {
-    pub fn new() -> Self {
-        Self {
-            cryptocurrencies: Vec::new(),
-            portfolio: Vec::new(),
-            selected_cryptocurrency: None,
-            loading: false,
-            error: None,
-        }
-    }
-    
-    pub fn calculate_portfolio_value(&self) -> f64 {
-        let crypto_map: HashMap<String, &Cryptocurrency> = self.cryptocurrencies
-            .iter()
-            .map(|crypto| (crypto.id.clone(), crypto))
-            .collect();
-        
-        self.portfolio
-            .iter()
-            .filter_map(|item| {
-                crypto_map.get(&item.crypto_id).map(|crypto| crypto.price * item.amount)
-            })
-            .sum()
-    }
-}
+pub mod history;
+pub mod holdings;
+pub mod recent;
+pub mod state;
+pub mod sync;
 
-// Context provider for global state
-pub type StoreContext = Rc<UseReducerHandle<StoreState>>;
+#[cfg(test)]
+mod tests;
 
-// Store actions for state management
-#[derive(Clone, Debug)]
-pub enum StoreAction {
-    FetchCryptocurrencies,
-    SetCryptocurrencies(Vec<Cryptocurrency>),
-    FetchCryptocurrencyDetails(String),
-    SetSelectedCryptocurrency(Cryptocurrency),
-    AddToPortfolio(PortfolioItem),
-    RemoveFromPortfolio(String),
-    UpdatePortfolioItem(PortfolioItem),
-    SavePortfolio,
-    SetError(String),
-    ClearError,
-    SetLoading(bool),
-}
+pub use history::History;
+pub use holdings::{Holding, HoldingSource};
+pub use recent::{RecentAsset, RecentlyViewed};
+pub use state::{Store, StoreAction, StoreState};
+pub use sync::{SyncState, SyncStatus};
 
-#[derive(PartialEq, Clone)]
-pub struct StoreState {
-    pub store: Store,
-}
+const PORTFOLIO_STORAGE_KEY: &str = "crypto_tracker.portfolio";
+const RECENT_STORAGE_KEY: &str = "crypto_tracker.recently_viewed";
+const WALLETS_STORAGE_KEY: &str = "crypto_tracker.wallets";
+const INCOME_STORAGE_KEY: &str = "crypto_tracker.income";
+
+// Conflicting writes are merged and retried this many times before giving up
+const MAX_SYNC_ATTEMPTS: usize = 3;
+
+// Context provider for global state
+pub type StoreContext = Rc<UseReducerHandle<StoreState>>;
 
 impl Reducible for StoreState {
     type Action = StoreAction;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
-        let next_store = match action {
-            StoreAction::FetchCryptocurrencies => {
-                let mut store = self.store.clone();
-                store.loading = true;
-                store.error = None;
-                store
-            },
-            StoreAction::SetCryptocurrencies(cryptocurrencies) => {
-                let mut store = self.store.clone();
-                store.cryptocurrencies = cryptocurrencies;
-                store.loading = false;
-                store
-            },
-            StoreAction::FetchCryptocurrencyDetails(_) => {
-                let mut store = self.store.clone();
-                store.loading = true;
-                store.error = None;
-                store
-            },
-            StoreAction::SetSelectedCryptocurrency(cryptocurrency) => {
-                let mut store = self.store.clone();
-                store.selected_cryptocurrency = Some(cryptocurrency);
-                store.loading = false;
-                store
-            },
-            StoreAction::AddToPortfolio(item) => {
-                let mut store = self.store.clone();
-                if !store.portfolio.iter().any(|i| i.crypto_id == item.crypto_id) {
-                    store.portfolio.push(item);
-                }
-                store
-            },
-            StoreAction::RemoveFromPortfolio(crypto_id) => {
-                let mut store = self.store.clone();
-                store.portfolio.retain(|item| item.crypto_id != crypto_id);
-                store
-            },
-            StoreAction::UpdatePortfolioItem(updated_item) => {
-                let mut store = self.store.clone();
-                if let Some(item) = store.portfolio.iter_mut().find(|i| i.crypto_id == updated_item.crypto_id) {
-                    *item = updated_item;
-                }
-                store
-            },
-            StoreAction::SavePortfolio => {
-                let mut store = self.store.clone();
-                store.loading = true;
-                store
-            },
-            StoreAction::SetError(error) => {
-                let mut store = self.store.clone();
-                store.error = Some(error);
-                store.loading = false;
-                store
-            },
-            StoreAction::ClearError => {
-                let mut store = self.store.clone();
-                store.error = None;
-                store
-            },
-            StoreAction::SetLoading(loading) => {
-                let mut store = self.store.clone();
-                store.loading = loading;
-                store
-            },
-        };
-
-        Rc::new(Self {
-            store: next_store,
-        })
+        Rc::new(self.apply(action))
     }
 }
 
+// Undo/redo history for portfolio edits
+#[hook]
+pub fn use_history() -> History {
+    use_context::<StoreContext>()
+        .expect("Store context not set")
+        .history
+        .clone()
+}
 
-impl Default for StoreState {
-    fn default() -> Self {
-        Self {
-            store: Store::new(),
-        }
-    }
+// Portfolio sync progress
+#[hook]
+pub fn use_sync() -> SyncState {
+    use_context::<StoreContext>()
+        .expect("Store context not set")
+        .sync
+        .clone()
 }
 
 // Custom hook to use the store
@@ -192,20 +106,40 @@ pub fn use_store() -> (Store, Callback<StoreAction>) {
                     // Do NOT dispatch here, async handler will dispatch SetSelectedCryptocurrency/SetError
                     return;
                 },
-                StoreAction::SavePortfolio => {
+                StoreAction::RefreshCryptocurrencies => {
                     let store = store.clone();
-                    let portfolio = store.store.portfolio.clone();
                     wasm_bindgen_futures::spawn_local(async move {
-                        match api::update_portfolio(portfolio).await {
-                            Ok(_) => {
-                                store.dispatch(StoreAction::SetLoading(false));
+                        match api::get_cryptocurrencies().await {
+                            Ok(cryptocurrencies) => {
+                                store.dispatch(StoreAction::SetCryptocurrencies(cryptocurrencies));
                             },
-                            Err(err) => {
-                                store.dispatch(StoreAction::SetError(err.to_string()));
-                            }
+                            // Keep showing the last prices
+                            Err(err) => log::warn!("Price refresh failed: {}", err),
                         }
                     });
-                    // Do NOT dispatch here, async handler will dispatch SetLoading/SetError
+                    return;
+                },
+                StoreAction::RefreshCryptocurrencyDetails(id) => {
+                    let store = store.clone();
+                    let id = id.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        match api::get_cryptocurrency_details(&id).await {
+                            Ok(cryptocurrency) => {
+                                store.dispatch(StoreAction::SetSelectedCryptocurrency(cryptocurrency));
+                            },
+                            Err(err) => log::warn!("Refresh of {} failed: {}", id, err),
+                        }
+                    });
+                    return;
+                },
+                StoreAction::FetchWalletBalances(wallets) => {
+                    let store = store.clone();
+                    let wallets = wallets.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let tokens = TokenList::from_config(&config::current());
+                        let balances = chain::fetch_balances(chain::client().as_ref(), &tokens, &wallets).await;
+                        store.dispatch(StoreAction::SetWalletBalances(balances));
+                    });
                     return;
                 },
                 _ => {}
@@ -223,12 +157,127 @@ pub fn use_store() -> (Store, Callback<StoreAction>) {
 pub struct StoreProviderProps {
     #[prop_or_default]
     pub children: Html,
+    // Seed state, e.g. for tests; its portfolio counts as synced. Seeded
+    // stores don't touch local storage or sync on startup.
+    #[prop_or_default]
+    pub initial: Option<Store>,
+    // Treat `initial` as server-rendered market data instead: render it as
+    // is, then restore the saved portfolio and sync once mounted
+    #[prop_or_default]
+    pub hydrate: bool,
 }
 
 
 #[function_component]
 pub fn StoreProvider(props: &StoreProviderProps) -> Html {
-    let store = use_reducer(|| StoreState::default());
+    let initial = props.initial.clone();
+    let hydrate = props.hydrate;
+    let persistent = initial.is_none() || hydrate;
+    let store = use_reducer(move || match initial {
+        Some(store) if hydrate => StoreState {
+            store,
+            ..StoreState::default()
+        },
+        Some(store) => StoreState {
+            sync: SyncState::new(0, store.portfolio.clone()),
+            store,
+            ..StoreState::default()
+        },
+        None => StoreState::default()
+            .apply(StoreAction::RestorePortfolio(saved_portfolio()))
+            .apply(StoreAction::RestoreRecentlyViewed(saved_recently_viewed()))
+            .apply(StoreAction::RestoreWallets(saved_wallets()))
+            .apply(StoreAction::RestoreIncome(saved_income())),
+    });
+
+    // Sync once on startup and again whenever the browser comes back online
+    {
+        let store = store.clone();
+        use_effect_with((), move |_| {
+            let listener = persistent.then(|| {
+                if hydrate {
+                    store.dispatch(StoreAction::RestorePortfolio(saved_portfolio()));
+                    store.dispatch(StoreAction::RestoreRecentlyViewed(saved_recently_viewed()));
+                    store.dispatch(StoreAction::RestoreWallets(saved_wallets()));
+                    store.dispatch(StoreAction::RestoreIncome(saved_income()));
+                }
+                store.dispatch(StoreAction::SavePortfolio);
+                EventListener::new(&gloo::utils::window(), "online", move |_| {
+                    store.dispatch(StoreAction::SavePortfolio);
+                })
+            });
+            move || drop(listener)
+        });
+    }
+
+    // Pull changes made on other devices every so often while signed in
+    {
+        let store = store.clone();
+        let refresh_secs = use_config().refresh.portfolio_secs;
+        use_effect_with(refresh_secs, move |secs| {
+            let refresh = (persistent && *secs > 0 && api::has_server()).then(|| {
+                Interval::new(secs.saturating_mul(1000), move || {
+                    if session::current().is_some() {
+                        store.dispatch(StoreAction::SavePortfolio);
+                    }
+                })
+            });
+            move || drop(refresh)
+        });
+    }
+
+    // Runs each requested sync against the state of the render that asked for it
+    {
+        let store = store.clone();
+        use_effect_with(store.sync.requested, move |requested| {
+            if *requested > 0 {
+                run_sync(store);
+            }
+        });
+    }
+
+    // While hydrating, the first render holds the server's empty portfolio
+    // and history, which must not overwrite the saved ones
+    let skip_persist = use_mut_ref(move || hydrate);
+    let skip_persist_recent = use_mut_ref(move || hydrate);
+    let skip_persist_wallets = use_mut_ref(move || hydrate);
+    let skip_persist_income = use_mut_ref(move || hydrate);
+    {
+        let saved = sync::SavedPortfolio {
+            revision: store.sync.revision,
+            base: store.sync.base.clone(),
+            items: store.store.portfolio.clone(),
+        };
+        use_effect_with(saved, move |saved| {
+            if persistent && !std::mem::take(&mut *skip_persist.borrow_mut()) {
+                if let Err(err) = LocalStorage::set(PORTFOLIO_STORAGE_KEY, saved) {
+                    log::warn!("Failed to persist portfolio: {}", err);
+                }
+            }
+        });
+    }
+    use_effect_with(store.store.recently_viewed.clone(), move |recently_viewed| {
+        if persistent && !std::mem::take(&mut *skip_persist_recent.borrow_mut()) {
+            if let Err(err) = LocalStorage::set(RECENT_STORAGE_KEY, recently_viewed) {
+                log::warn!("Failed to persist recently viewed assets: {}", err);
+            }
+        }
+    });
+    use_effect_with(store.store.wallets.clone(), move |wallets| {
+        if persistent && !std::mem::take(&mut *skip_persist_wallets.borrow_mut()) {
+            if let Err(err) = LocalStorage::set(WALLETS_STORAGE_KEY, wallets) {
+                log::warn!("Failed to persist watched wallets: {}", err);
+            }
+        }
+    });
+    use_effect_with(store.store.income.clone(), move |income| {
+        if persistent && !std::mem::take(&mut *skip_persist_income.borrow_mut()) {
+            if let Err(err) = LocalStorage::set(INCOME_STORAGE_KEY, income) {
+                log::warn!("Failed to persist income: {}", err);
+            }
+        }
+    });
+
     let store = Rc::new(store);
 
     html! {
@@ -237,3 +286,72 @@ pub fn StoreProvider(props: &StoreProviderProps) -> Html {
         </ContextProvider<StoreContext>>
     }
 }
+
+fn saved_portfolio() -> sync::SavedPortfolio {
+    LocalStorage::get(PORTFOLIO_STORAGE_KEY).unwrap_or_default()
+}
+
+fn saved_recently_viewed() -> RecentlyViewed {
+    LocalStorage::get(RECENT_STORAGE_KEY)
+        .map(RecentlyViewed::new)
+        .unwrap_or_default()
+}
+
+fn saved_wallets() -> Vec<WatchedWallet> {
+    LocalStorage::get(WALLETS_STORAGE_KEY).unwrap_or_default()
+}
+
+fn saved_income() -> Vec<IncomeEvent> {
+    LocalStorage::get(INCOME_STORAGE_KEY).unwrap_or_default()
+}
+
+fn run_sync(store: UseReducerHandle<StoreState>) {
+    let revision = store.sync.revision;
+    let base = store.sync.base.clone();
+    let sent = store.store.portfolio.clone();
+    store.dispatch(StoreAction::SyncStarted);
+
+    wasm_bindgen_futures::spawn_local(async move {
+        match sync_portfolio(revision, base, sent.clone()).await {
+            Ok(document) => store.dispatch(StoreAction::SyncCompleted { sent, document }),
+            Err(err) => {
+                let offline = err.is::<api::Offline>() || !gloo::utils::window().navigator().on_line();
+                store.dispatch(StoreAction::SyncFailed {
+                    offline,
+                    message: err.to_string(),
+                });
+            }
+        }
+    });
+}
+
+// Pushes `local` on top of `revision`, merging in concurrent server changes.
+// With nothing to push it just pulls the server's copy.
+async fn sync_portfolio(
+    mut revision: u64,
+    mut base: Vec<PortfolioItem>,
+    local: Vec<PortfolioItem>,
+) -> anyhow::Result<PortfolioDocument> {
+    if local == base {
+        if !api::has_server() {
+            return Ok(PortfolioDocument { revision, items: local });
+        }
+        return api::get_portfolio().await;
+    }
+
+    let mut items = local;
+    for _ in 0..MAX_SYNC_ATTEMPTS {
+        match api::save_portfolio(revision, items.clone()).await? {
+            SaveOutcome::Saved(document) => return Ok(document),
+            SaveOutcome::Conflict(remote) => {
+                items = sync::merge(&base, &items, &remote.items);
+                if items == remote.items {
+                    return Ok(remote);
+                }
+                revision = remote.revision;
+                base = remote.items;
+            }
+        }
+    }
+    Err(anyhow!("The portfolio keeps changing on the server, please try again"))
+}
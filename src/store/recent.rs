@@ -0,0 +1,127 @@
+// src/store/recent.rs
+use serde::{Deserialize, Serialize};
+
+use crate::models::crypto::Cryptocurrency;
+
+// Maximum number of assets remembered as recently viewed
+pub const RECENT_LIMIT: usize = 8;
+
+// Enough of an asset to link back to it without market data loaded
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecentAsset {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+}
+
+impl From<&Cryptocurrency> for RecentAsset {
+    fn from(crypto: &Cryptocurrency) -> Self {
+        Self {
+            id: crypto.id.clone(),
+            name: crypto.name.clone(),
+            symbol: crypto.symbol.clone(),
+        }
+    }
+}
+
+// Assets opened on the details page, most recent first
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RecentlyViewed(Vec<RecentAsset>);
+
+impl RecentlyViewed {
+    // Keeps the first `RECENT_LIMIT` distinct assets, e.g. from local storage
+    pub fn new(assets: Vec<RecentAsset>) -> Self {
+        let mut recent = Self::default();
+        for asset in assets.into_iter().rev() {
+            recent.push_front(asset);
+        }
+        recent
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RecentAsset> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Moves `crypto` to the front, dropping the oldest entry past the limit
+    pub fn visit(&mut self, crypto: &Cryptocurrency) {
+        self.push_front(RecentAsset::from(crypto));
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn push_front(&mut self, asset: RecentAsset) {
+        self.0.retain(|recent| recent.id != asset.id);
+        self.0.insert(0, asset);
+        self.0.truncate(RECENT_LIMIT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn crypto(id: &str) -> Cryptocurrency {
+        Cryptocurrency {
+            id: id.to_string(),
+            name: id.to_string(),
+            symbol: id[..3].to_uppercase(),
+            price: dec!(1),
+            market_cap: 0.0,
+            volume_24h: 0.0,
+            price_change_24h: 0.0,
+            decimals: 8,
+        }
+    }
+
+    fn ids(recent: &RecentlyViewed) -> Vec<&str> {
+        recent.iter().map(|asset| asset.id.as_str()).collect()
+    }
+
+    #[test]
+    fn visiting_again_moves_an_asset_to_the_front() {
+        let mut recent = RecentlyViewed::default();
+        for id in ["bitcoin", "ethereum", "cardano", "bitcoin"] {
+            recent.visit(&crypto(id));
+        }
+
+        assert_eq!(ids(&recent), ["bitcoin", "cardano", "ethereum"]);
+    }
+
+    #[test]
+    fn oldest_assets_fall_off_past_the_limit() {
+        let mut recent = RecentlyViewed::default();
+        for index in 0..RECENT_LIMIT + 2 {
+            recent.visit(&crypto(&format!("coin{}", index)));
+        }
+
+        assert_eq!(recent.len(), RECENT_LIMIT);
+        assert_eq!(recent.iter().next().unwrap().id, format!("coin{}", RECENT_LIMIT + 1));
+        assert!(recent.iter().all(|asset| asset.id != "coin0" && asset.id != "coin1"));
+    }
+
+    #[test]
+    fn new_drops_duplicates_and_extra_entries() {
+        let assets: Vec<RecentAsset> = ["solana", "bitcoin", "solana"]
+            .into_iter()
+            .map(str::to_string)
+            .chain((0..RECENT_LIMIT).map(|index| format!("coin{}", index)))
+            .map(|id| RecentAsset::from(&crypto(&id)))
+            .collect();
+        let recent = RecentlyViewed::new(assets);
+
+        assert_eq!(recent.len(), RECENT_LIMIT);
+        assert_eq!(ids(&recent)[..3], ["solana", "bitcoin", "coin0"]);
+    }
+}
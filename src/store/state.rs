@@ -0,0 +1,393 @@
+// src/store/state.rs
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::crypto::{Cryptocurrency, PortfolioDocument, PortfolioItem, DEFAULT_ASSET_DECIMALS};
+use crate::models::income::IncomeEvent;
+use crate::models::wallet::{WalletBalance, WatchedWallet};
+use super::history::{History, PortfolioEdit};
+use super::holdings::{self, Holding};
+use super::recent::RecentlyViewed;
+use super::sync::{merge, SavedPortfolio, SyncState};
+
+// Define our application state
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Store {
+    pub cryptocurrencies: Vec<Cryptocurrency>,
+    pub portfolio: Vec<PortfolioItem>,
+    pub selected_cryptocurrency: Option<Cryptocurrency>,
+    pub loading: bool,
+    pub error: Option<String>,
+    // Kept in local storage rather than sent with server-rendered pages
+    #[serde(default, skip_serializing)]
+    pub recently_viewed: RecentlyViewed,
+    // Also kept in local storage
+    #[serde(default, skip_serializing)]
+    pub wallets: Vec<WatchedWallet>,
+    // Latest balance of each watched wallet by `WatchedWallet::key`, missing
+    // until the first read completes
+    #[serde(skip)]
+    pub wallet_balances: HashMap<String, WalletBalance>,
+    // Staking rewards, interest and the like, oldest first. Also kept in
+    // local storage.
+    #[serde(default, skip_serializing)]
+    pub income: Vec<IncomeEvent>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self {
+            cryptocurrencies: Vec::new(),
+            portfolio: Vec::new(),
+            selected_cryptocurrency: None,
+            loading: false,
+            error: None,
+            recently_viewed: RecentlyViewed::default(),
+            wallets: Vec::new(),
+            wallet_balances: HashMap::new(),
+            income: Vec::new(),
+        }
+    }
+
+    // Manual holdings merged with watched wallet balances and income
+    pub fn holdings(&self) -> Vec<Holding> {
+        holdings::merge(&self.portfolio, &self.wallets, &self.wallet_balances, &self.income)
+    }
+
+    pub fn calculate_portfolio_value(&self) -> Decimal {
+        let crypto_map: HashMap<String, &Cryptocurrency> = self.cryptocurrencies
+            .iter()
+            .map(|crypto| (crypto.id.clone(), crypto))
+            .collect();
+        
+        self.holdings()
+            .iter()
+            .filter_map(|holding| {
+                crypto_map.get(&holding.crypto_id).map(|crypto| crypto.value_of(holding.amount()))
+            })
+            .sum()
+    }
+
+    // Precision of an asset, if its market data is loaded
+    fn decimals_of(&self, crypto_id: &str) -> Option<u32> {
+        self.cryptocurrencies
+            .iter()
+            .chain(self.selected_cryptocurrency.iter())
+            .find(|crypto| crypto.id == crypto_id)
+            .map(|crypto| crypto.decimals)
+    }
+
+    // Round an incoming amount to the precision of its asset
+    fn normalize_item(&self, item: PortfolioItem) -> PortfolioItem {
+        let decimals = self.decimals_of(&item.crypto_id).unwrap_or(DEFAULT_ASSET_DECIMALS);
+
+        PortfolioItem {
+            amount: item.amount.round_dp(decimals).normalize(),
+            ..item
+        }
+    }
+
+    // Round stored or synced amounts of assets whose precision is known. The
+    // rest are kept as they are rather than cut to the default precision;
+    // they are rounded once their market data arrives.
+    fn normalize_known(&self, items: Vec<PortfolioItem>) -> Vec<PortfolioItem> {
+        items
+            .into_iter()
+            .map(|item| match self.decimals_of(&item.crypto_id) {
+                Some(decimals) => PortfolioItem {
+                    amount: item.amount.round_dp(decimals).normalize(),
+                    ..item
+                },
+                None => item,
+            })
+            .collect()
+    }
+
+    fn normalize_income(&self, event: IncomeEvent) -> IncomeEvent {
+        let PortfolioItem { amount, .. } = self.normalize_item(PortfolioItem {
+            crypto_id: event.crypto_id.clone(),
+            amount: event.amount,
+        });
+        IncomeEvent {
+            amount,
+            value: event.value.round_dp(2),
+            ..event
+        }
+    }
+}
+
+// Store actions for state management
+#[derive(Clone, Debug)]
+pub enum StoreAction {
+    FetchCryptocurrencies,
+    SetCryptocurrencies(Vec<Cryptocurrency>),
+    FetchCryptocurrencyDetails(String),
+    // Fetch again without the spinner, keeping what is shown if it fails;
+    // handled in `use_store`
+    RefreshCryptocurrencies,
+    RefreshCryptocurrencyDetails(String),
+    // Also records the asset as recently viewed
+    SetSelectedCryptocurrency(Cryptocurrency),
+    AddToPortfolio(PortfolioItem),
+    RemoveFromPortfolio(String),
+    UpdatePortfolioItem(PortfolioItem),
+    Undo,
+    Redo,
+    // Push local edits to the server and pull in remote ones
+    SavePortfolio,
+    SyncStarted,
+    SyncCompleted {
+        sent: Vec<PortfolioItem>,
+        document: PortfolioDocument,
+    },
+    SyncFailed {
+        offline: bool,
+        message: String,
+    },
+    // Forget the local portfolio, watched wallets and income, e.g. when
+    // signing out
+    ResetPortfolio,
+    // Load the portfolio kept in local storage
+    RestorePortfolio(SavedPortfolio),
+    // Load the recently viewed assets kept in local storage
+    RestoreRecentlyViewed(RecentlyViewed),
+    ClearRecentlyViewed,
+    // Start watching an address; one already watched is ignored
+    AddWallet(WatchedWallet),
+    // Stop watching the wallet with this `WatchedWallet::key`
+    RemoveWallet(String),
+    // Load the watched wallets kept in local storage
+    RestoreWallets(Vec<WatchedWallet>),
+    // Read the balances of these wallets; handled in `use_store`
+    FetchWalletBalances(Vec<WatchedWallet>),
+    // Balances read, by `WatchedWallet::key`
+    SetWalletBalances(Vec<(String, WalletBalance)>),
+    // Record income; its `id` is assigned here
+    AddIncome(IncomeEvent),
+    // Forget the income event with this id
+    RemoveIncome(u64),
+    // Load the income kept in local storage
+    RestoreIncome(Vec<IncomeEvent>),
+    SetError(String),
+    ClearError,
+    SetLoading(bool),
+}
+
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct StoreState {
+    pub store: Store,
+    pub history: History,
+    pub sync: SyncState,
+}
+
+impl StoreState {
+    // Pure state transition; async side effects are handled in `use_store`
+    // and, for portfolio sync, `StoreProvider`
+    pub fn apply(&self, action: StoreAction) -> StoreState {
+        let mut history = self.history.clone();
+        let mut sync = self.sync.clone();
+        // Portfolio-mutating actions are recorded so they can be undone
+        let edit = match &action {
+            StoreAction::AddToPortfolio(item) => Some(PortfolioEdit::Add(item.crypto_id.clone())),
+            StoreAction::RemoveFromPortfolio(crypto_id) => Some(PortfolioEdit::Remove(crypto_id.clone())),
+            StoreAction::UpdatePortfolioItem(item) => Some(PortfolioEdit::Update(item.crypto_id.clone())),
+            _ => None,
+        };
+
+        let next_store = match action {
+            StoreAction::FetchCryptocurrencies => {
+                let mut store = self.store.clone();
+                store.loading = true;
+                store.error = None;
+                store
+            },
+            StoreAction::SetCryptocurrencies(cryptocurrencies) => {
+                let mut store = self.store.clone();
+                store.cryptocurrencies = cryptocurrencies;
+                store.portfolio = store.normalize_known(store.portfolio.clone());
+                store.loading = false;
+                store
+            },
+            StoreAction::FetchCryptocurrencyDetails(_) => {
+                let mut store = self.store.clone();
+                store.loading = true;
+                store.error = None;
+                store
+            },
+            StoreAction::RefreshCryptocurrencies | StoreAction::RefreshCryptocurrencyDetails(_) => self.store.clone(),
+            StoreAction::SetSelectedCryptocurrency(cryptocurrency) => {
+                let mut store = self.store.clone();
+                store.recently_viewed.visit(&cryptocurrency);
+                store.selected_cryptocurrency = Some(cryptocurrency);
+                store.loading = false;
+                store
+            },
+            StoreAction::AddToPortfolio(item) => {
+                let mut store = self.store.clone();
+                if !store.portfolio.iter().any(|i| i.crypto_id == item.crypto_id) {
+                    let item = store.normalize_item(item);
+                    store.portfolio.push(item);
+                }
+                store
+            },
+            StoreAction::RemoveFromPortfolio(crypto_id) => {
+                let mut store = self.store.clone();
+                store.portfolio.retain(|item| item.crypto_id != crypto_id);
+                store
+            },
+            StoreAction::UpdatePortfolioItem(updated_item) => {
+                let mut store = self.store.clone();
+                let updated_item = store.normalize_item(updated_item);
+                if let Some(item) = store.portfolio.iter_mut().find(|i| i.crypto_id == updated_item.crypto_id) {
+                    *item = updated_item;
+                }
+                store
+            },
+            StoreAction::Undo => {
+                let mut store = self.store.clone();
+                if let Some(portfolio) = history.undo(store.portfolio.clone()) {
+                    store.portfolio = portfolio;
+                }
+                store
+            },
+            StoreAction::Redo => {
+                let mut store = self.store.clone();
+                if let Some(portfolio) = history.redo(store.portfolio.clone()) {
+                    store.portfolio = portfolio;
+                }
+                store
+            },
+            StoreAction::SavePortfolio => {
+                sync.request();
+                self.store.clone()
+            },
+            StoreAction::SyncStarted => {
+                sync.start();
+                self.store.clone()
+            },
+            StoreAction::SyncCompleted { sent, document } => {
+                let mut store = self.store.clone();
+                let merged = store.normalize_known(sync.complete(&sent, document, &store.portfolio));
+                // Undo and redo keep what was pulled in, rather than reverting
+                // it and then pushing that as a deletion
+                if merged != store.portfolio {
+                    let current = std::mem::take(&mut store.portfolio);
+                    history.rebase(|snapshot| merge(&current, snapshot, &merged));
+                }
+                store.portfolio = merged;
+                store
+            },
+            StoreAction::SyncFailed { offline, message } => {
+                sync.fail(offline, message);
+                self.store.clone()
+            },
+            StoreAction::ResetPortfolio => {
+                history = History::default();
+                sync = SyncState::default();
+                let mut store = self.store.clone();
+                store.portfolio.clear();
+                store.wallets.clear();
+                store.wallet_balances.clear();
+                store.income.clear();
+                store
+            },
+            StoreAction::RestorePortfolio(saved) => {
+                history = History::default();
+                sync = SyncState::new(saved.revision, saved.base);
+                let mut store = self.store.clone();
+                store.portfolio = store.normalize_known(saved.items);
+                store
+            },
+            StoreAction::RestoreRecentlyViewed(recently_viewed) => {
+                let mut store = self.store.clone();
+                store.recently_viewed = recently_viewed;
+                store
+            },
+            StoreAction::ClearRecentlyViewed => {
+                let mut store = self.store.clone();
+                store.recently_viewed.clear();
+                store
+            },
+            StoreAction::AddWallet(wallet) => {
+                let mut store = self.store.clone();
+                if !store.wallets.iter().any(|watched| watched.key() == wallet.key()) {
+                    store.wallets.push(wallet);
+                }
+                store
+            },
+            StoreAction::RemoveWallet(key) => {
+                let mut store = self.store.clone();
+                store.wallets.retain(|wallet| wallet.key() != key);
+                store.wallet_balances.remove(&key);
+                store
+            },
+            StoreAction::RestoreWallets(wallets) => {
+                let mut store = self.store.clone();
+                store.wallets = wallets;
+                store.wallet_balances.clear();
+                store
+            },
+            StoreAction::FetchWalletBalances(_) => self.store.clone(),
+            StoreAction::SetWalletBalances(balances) => {
+                let mut store = self.store.clone();
+                // Reads for wallets removed in the meantime are dropped
+                for (key, balance) in balances {
+                    if store.wallets.iter().any(|wallet| wallet.key() == key) {
+                        store.wallet_balances.insert(key, balance);
+                    }
+                }
+                store
+            },
+            StoreAction::AddIncome(event) => {
+                let mut store = self.store.clone();
+                let id = store.income.iter().map(|event| event.id).max().map_or(1, |id| id + 1);
+                let event = IncomeEvent { id, ..store.normalize_income(event) };
+                // Kept in date order; same-day events in the order recorded
+                let index = store.income.partition_point(|recorded| recorded.received_on <= event.received_on);
+                store.income.insert(index, event);
+                store
+            },
+            StoreAction::RemoveIncome(id) => {
+                let mut store = self.store.clone();
+                store.income.retain(|event| event.id != id);
+                store
+            },
+            StoreAction::RestoreIncome(mut income) => {
+                let mut store = self.store.clone();
+                income.sort_by_key(|event| event.received_on);
+                store.income = income;
+                store
+            },
+            StoreAction::SetError(error) => {
+                let mut store = self.store.clone();
+                store.error = Some(error);
+                store.loading = false;
+                store
+            },
+            StoreAction::ClearError => {
+                let mut store = self.store.clone();
+                store.error = None;
+                store
+            },
+            StoreAction::SetLoading(loading) => {
+                let mut store = self.store.clone();
+                store.loading = loading;
+                store
+            },
+        };
+
+        // No-op edits (duplicate adds, updates of missing items) aren't recorded
+        if let Some(edit) = edit {
+            if next_store.portfolio != self.store.portfolio {
+                history.record(edit, self.store.portfolio.clone());
+            }
+        }
+
+        Self {
+            store: next_store,
+            history,
+            sync,
+        }
+    }
+}
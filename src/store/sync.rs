@@ -0,0 +1,221 @@
+// src/store/sync.rs
+use serde::{Deserialize, Serialize};
+
+use crate::models::crypto::{PortfolioDocument, PortfolioItem};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SyncStatus {
+    // Nothing sent or fetched yet this session
+    #[default]
+    Idle,
+    Syncing,
+    Synced,
+    // Edits stay queued until the browser is back online
+    Offline,
+    Failed(String),
+}
+
+// Bookkeeping for syncing the portfolio with the server. Edits apply to the
+// local portfolio straight away; whatever differs from `base` is still to be
+// pushed, and survives reloads and offline periods via `SavedPortfolio`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SyncState {
+    // Server revision `base` was read at
+    pub revision: u64,
+    // Portfolio as last agreed with the server
+    pub base: Vec<PortfolioItem>,
+    pub status: SyncStatus,
+    // Bumped to ask the provider to run a sync
+    pub requested: u64,
+}
+
+impl SyncState {
+    pub fn new(revision: u64, base: Vec<PortfolioItem>) -> Self {
+        Self {
+            revision,
+            base,
+            ..Self::default()
+        }
+    }
+
+    // Number of holdings added, changed or removed since the last sync
+    pub fn pending_changes(&self, portfolio: &[PortfolioItem]) -> usize {
+        let changed = portfolio.iter().filter(|item| !self.base.contains(item)).count();
+        let removed = self
+            .base
+            .iter()
+            .filter(|old| !portfolio.iter().any(|item| item.crypto_id == old.crypto_id))
+            .count();
+        changed + removed
+    }
+
+    pub fn is_syncing(&self) -> bool {
+        self.status == SyncStatus::Syncing
+    }
+
+    // A sync already in flight picks up later edits when it completes
+    pub fn request(&mut self) {
+        if !self.is_syncing() {
+            self.requested += 1;
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.status = SyncStatus::Syncing;
+    }
+
+    // Adopts the server's copy of what was `sent`, keeping edits made while
+    // the request was in flight. Returns the portfolio to show.
+    pub fn complete(
+        &mut self,
+        sent: &[PortfolioItem],
+        document: PortfolioDocument,
+        current: &[PortfolioItem],
+    ) -> Vec<PortfolioItem> {
+        let portfolio = merge(sent, current, &document.items);
+        self.revision = document.revision;
+        self.base = document.items;
+        self.status = SyncStatus::Synced;
+        if portfolio != self.base {
+            self.requested += 1;
+        }
+        portfolio
+    }
+
+    pub fn fail(&mut self, offline: bool, message: String) {
+        self.status = if offline {
+            SyncStatus::Offline
+        } else {
+            SyncStatus::Failed(message)
+        };
+    }
+}
+
+// Three-way merge of two portfolios that both started from `base`. Each
+// holding is taken from whichever side changed it; when both did, the local
+// edit wins, except that a local removal never discards a remote change.
+pub fn merge(base: &[PortfolioItem], local: &[PortfolioItem], remote: &[PortfolioItem]) -> Vec<PortfolioItem> {
+    let find = |items: &[PortfolioItem], crypto_id: &str| {
+        items.iter().find(|item| item.crypto_id == crypto_id).cloned()
+    };
+
+    // Remote order first, then holdings only this side knows about
+    let mut ids: Vec<&str> = remote.iter().map(|item| item.crypto_id.as_str()).collect();
+    for item in local.iter().chain(base) {
+        if !ids.contains(&item.crypto_id.as_str()) {
+            ids.push(&item.crypto_id);
+        }
+    }
+
+    ids.into_iter()
+        .filter_map(|crypto_id| {
+            let base = find(base, crypto_id);
+            let local = find(local, crypto_id);
+            let remote = find(remote, crypto_id);
+            if local == base {
+                remote
+            } else if remote == base || local.is_some() {
+                local
+            } else {
+                remote
+            }
+        })
+        .collect()
+}
+
+// What is kept in local storage between visits
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedPortfolio {
+    pub revision: u64,
+    pub base: Vec<PortfolioItem>,
+    pub items: Vec<PortfolioItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn item(crypto_id: &str, amount: Decimal) -> PortfolioItem {
+        PortfolioItem {
+            crypto_id: crypto_id.to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn merge_takes_each_sides_changes() {
+        let base = vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2)), item("cardano", dec!(3))];
+        let local = vec![item("bitcoin", dec!(1.5)), item("ethereum", dec!(2)), item("cardano", dec!(3)), item("solana", dec!(4))];
+        let remote = vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2.5))];
+
+        assert_eq!(
+            merge(&base, &local, &remote),
+            vec![item("bitcoin", dec!(1.5)), item("ethereum", dec!(2.5)), item("solana", dec!(4))]
+        );
+    }
+
+    #[test]
+    fn merge_prefers_local_when_both_change_a_holding() {
+        let base = vec![item("bitcoin", dec!(1))];
+        let local = vec![item("bitcoin", dec!(2))];
+        let remote = vec![item("bitcoin", dec!(3))];
+
+        assert_eq!(merge(&base, &local, &remote), local);
+    }
+
+    #[test]
+    fn merge_keeps_remote_change_to_locally_removed_holding() {
+        let base = vec![item("bitcoin", dec!(1))];
+        let remote = vec![item("bitcoin", dec!(3))];
+
+        assert_eq!(merge(&base, &[], &remote), remote);
+        assert_eq!(merge(&base, &[], &base), Vec::new());
+    }
+
+    #[test]
+    fn merge_with_unchanged_local_is_remote() {
+        let base = vec![item("bitcoin", dec!(1))];
+        let remote = vec![item("ethereum", dec!(2))];
+
+        assert_eq!(merge(&base, &base, &remote), remote);
+    }
+
+    #[test]
+    fn pending_changes_counts_additions_updates_and_removals() {
+        let sync = SyncState::new(3, vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2))]);
+
+        assert_eq!(sync.pending_changes(&sync.base.clone()), 0);
+        assert_eq!(sync.pending_changes(&[item("bitcoin", dec!(1.5)), item("solana", dec!(1))]), 3);
+    }
+
+    #[test]
+    fn requests_are_not_stacked_while_syncing() {
+        let mut sync = SyncState::default();
+        sync.request();
+        sync.start();
+        sync.request();
+
+        assert_eq!(sync.requested, 1);
+    }
+
+    #[test]
+    fn completing_with_later_edits_requests_another_sync() {
+        let mut sync = SyncState::default();
+        sync.request();
+        sync.start();
+        let sent = vec![item("bitcoin", dec!(1))];
+        let current = vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2))];
+        let document = PortfolioDocument {
+            revision: 4,
+            items: sent.clone(),
+        };
+
+        assert_eq!(sync.complete(&sent, document, &current), current);
+        assert_eq!(sync.revision, 4);
+        assert_eq!(sync.base, sent);
+        assert_eq!(sync.status, SyncStatus::Synced);
+        assert_eq!(sync.requested, 2);
+    }
+}
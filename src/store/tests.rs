@@ -0,0 +1,855 @@
+// src/store/tests.rs
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use super::history::PortfolioEdit;
+use super::holdings::HoldingSource;
+use super::recent::{RecentAsset, RecentlyViewed};
+use super::state::{Store, StoreAction, StoreState};
+use super::sync::{SavedPortfolio, SyncState, SyncStatus};
+use crate::models::crypto::{Cryptocurrency, PortfolioDocument, PortfolioItem};
+use crate::models::income::{IncomeEvent, IncomeKind};
+use crate::models::wallet::{Chain, WalletAsset, WalletBalance, WatchedWallet};
+
+fn crypto(id: &str, price: Decimal, decimals: u32) -> Cryptocurrency {
+    Cryptocurrency {
+        id: id.to_string(),
+        name: id.to_string(),
+        symbol: id[..3].to_uppercase(),
+        price,
+        market_cap: 0.0,
+        volume_24h: 0.0,
+        price_change_24h: 0.0,
+        decimals,
+    }
+}
+
+fn item(crypto_id: &str, amount: Decimal) -> PortfolioItem {
+    PortfolioItem {
+        crypto_id: crypto_id.to_string(),
+        amount,
+    }
+}
+
+fn market() -> Vec<Cryptocurrency> {
+    vec![
+        crypto("bitcoin", dec!(63542.87), 8),
+        crypto("ethereum", dec!(3421.65), 18),
+        crypto("cardano", dec!(0.93), 6),
+    ]
+}
+
+fn state_with(store: Store) -> StoreState {
+    StoreState {
+        store,
+        ..StoreState::default()
+    }
+}
+
+fn apply_all(state: StoreState, actions: Vec<StoreAction>) -> StoreState {
+    actions.into_iter().fold(state, |state, action| state.apply(action))
+}
+
+// Market data actions
+
+#[test]
+fn fetch_cryptocurrencies_sets_loading_and_clears_error() {
+    let state = state_with(Store {
+        error: Some("boom".to_string()),
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::FetchCryptocurrencies);
+    assert!(next.store.loading);
+    assert_eq!(next.store.error, None);
+}
+
+#[test]
+fn set_cryptocurrencies_replaces_list_and_stops_loading() {
+    let state = state_with(Store {
+        cryptocurrencies: vec![crypto("dogecoin", dec!(0.1), 8)],
+        loading: true,
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::SetCryptocurrencies(market()));
+    assert_eq!(next.store.cryptocurrencies, market());
+    assert!(!next.store.loading);
+}
+
+#[test]
+fn fetch_details_sets_loading_and_keeps_selection() {
+    let selected = crypto("bitcoin", dec!(1), 8);
+    let state = state_with(Store {
+        selected_cryptocurrency: Some(selected.clone()),
+        error: Some("boom".to_string()),
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::FetchCryptocurrencyDetails("ethereum".to_string()));
+    assert!(next.store.loading);
+    assert_eq!(next.store.error, None);
+    assert_eq!(next.store.selected_cryptocurrency, Some(selected));
+}
+
+#[test]
+fn refreshes_leave_loading_error_and_shown_data_alone() {
+    let selected = crypto("bitcoin", dec!(1), 8);
+    let state = state_with(Store {
+        cryptocurrencies: market(),
+        selected_cryptocurrency: Some(selected.clone()),
+        ..Store::new()
+    });
+
+    let next = apply_all(state, vec![
+        StoreAction::RefreshCryptocurrencies,
+        StoreAction::RefreshCryptocurrencyDetails("bitcoin".to_string()),
+    ]);
+    assert!(!next.store.loading);
+    assert_eq!(next.store.error, None);
+    assert_eq!(next.store.cryptocurrencies, market());
+    assert_eq!(next.store.selected_cryptocurrency, Some(selected));
+}
+
+#[test]
+fn set_selected_cryptocurrency_stops_loading() {
+    let state = StoreState::default().apply(StoreAction::SetLoading(true));
+    let selected = crypto("solana", dec!(189.32), 9);
+
+    let next = state.apply(StoreAction::SetSelectedCryptocurrency(selected.clone()));
+    assert_eq!(next.store.selected_cryptocurrency, Some(selected));
+    assert!(!next.store.loading);
+}
+
+// Portfolio actions
+
+#[test]
+fn add_to_portfolio_appends_item() {
+    let next = StoreState::default().apply(StoreAction::AddToPortfolio(item("bitcoin", dec!(0.5))));
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(0.5))]);
+}
+
+#[test]
+fn duplicate_add_keeps_original_amount() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(0.5))),
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(2))),
+        ],
+    );
+
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(0.5))]);
+}
+
+#[test]
+fn add_rounds_amount_to_asset_precision() {
+    let state = state_with(Store {
+        cryptocurrencies: market(),
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::AddToPortfolio(item("cardano", dec!(10.1234567))));
+    assert_eq!(next.store.portfolio, vec![item("cardano", dec!(10.123457))]);
+}
+
+#[test]
+fn add_uses_selected_cryptocurrency_precision() {
+    let state = state_with(Store {
+        selected_cryptocurrency: Some(crypto("cardano", dec!(0.93), 2)),
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::AddToPortfolio(item("cardano", dec!(1.239))));
+    assert_eq!(next.store.portfolio[0].amount, dec!(1.24));
+}
+
+#[test]
+fn add_unknown_asset_uses_default_precision() {
+    let next = StoreState::default().apply(StoreAction::AddToPortfolio(item("unknown", dec!(0.123456789))));
+    assert_eq!(next.store.portfolio[0].amount, dec!(0.12345679));
+}
+
+#[test]
+fn remove_from_portfolio_drops_item() {
+    let state = state_with(Store {
+        portfolio: vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2))],
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::RemoveFromPortfolio("bitcoin".to_string()));
+    assert_eq!(next.store.portfolio, vec![item("ethereum", dec!(2))]);
+}
+
+#[test]
+fn remove_missing_item_is_noop() {
+    let state = state_with(Store {
+        portfolio: vec![item("bitcoin", dec!(1))],
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::RemoveFromPortfolio("solana".to_string()));
+    assert_eq!(next.store.portfolio, state.store.portfolio);
+    assert!(next.history.is_empty());
+}
+
+#[test]
+fn update_portfolio_item_replaces_amount() {
+    let state = state_with(Store {
+        portfolio: vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2))],
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::UpdatePortfolioItem(item("ethereum", dec!(3.5))));
+    assert_eq!(
+        next.store.portfolio,
+        vec![item("bitcoin", dec!(1)), item("ethereum", dec!(3.5))]
+    );
+}
+
+#[test]
+fn update_missing_item_does_not_insert() {
+    let state = state_with(Store {
+        portfolio: vec![item("bitcoin", dec!(1))],
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::UpdatePortfolioItem(item("ethereum", dec!(3))));
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(1))]);
+    assert!(next.history.is_empty());
+}
+
+#[test]
+fn update_rounds_amount_to_asset_precision() {
+    let state = state_with(Store {
+        cryptocurrencies: market(),
+        portfolio: vec![item("bitcoin", dec!(1))],
+        ..Store::new()
+    });
+
+    let next = state.apply(StoreAction::UpdatePortfolioItem(item("bitcoin", dec!(0.123456789))));
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(0.12345679))]);
+}
+
+#[test]
+fn save_portfolio_requests_sync_without_loading() {
+    let next = StoreState::default().apply(StoreAction::SavePortfolio);
+    assert_eq!(next.sync.requested, 1);
+    assert!(!next.store.loading);
+}
+
+// Undo / redo
+
+#[test]
+fn undo_restores_removed_item() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::RemoveFromPortfolio("bitcoin".to_string()),
+        ],
+    );
+    assert!(next.store.portfolio.is_empty());
+    assert_eq!(next.history.last_edit(), Some(&PortfolioEdit::Remove("bitcoin".to_string())));
+
+    let next = next.apply(StoreAction::Undo);
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(1))]);
+
+    let next = next.apply(StoreAction::Redo);
+    assert!(next.store.portfolio.is_empty());
+}
+
+#[test]
+fn undo_reverts_update() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::UpdatePortfolioItem(item("bitcoin", dec!(2))),
+            StoreAction::Undo,
+        ],
+    );
+
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(1))]);
+    assert!(next.history.can_redo());
+}
+
+#[test]
+fn undo_and_redo_without_history_are_noops() {
+    let state = state_with(Store {
+        portfolio: vec![item("bitcoin", dec!(1))],
+        ..Store::new()
+    });
+
+    assert_eq!(state.apply(StoreAction::Undo), state);
+    assert_eq!(state.apply(StoreAction::Redo), state);
+}
+
+#[test]
+fn new_edit_after_undo_discards_redo() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::Undo,
+            StoreAction::AddToPortfolio(item("ethereum", dec!(1))),
+        ],
+    );
+
+    assert!(!next.history.can_redo());
+    assert_eq!(next.apply(StoreAction::Redo).store.portfolio, next.store.portfolio);
+}
+
+#[test]
+fn no_op_edits_are_not_recorded() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(2))),
+            StoreAction::UpdatePortfolioItem(item("ethereum", dec!(3))),
+            StoreAction::UpdatePortfolioItem(item("bitcoin", dec!(1))),
+            StoreAction::RemoveFromPortfolio("solana".to_string()),
+        ],
+    );
+
+    assert_eq!(next.history.len(), 1);
+}
+
+#[test]
+fn undo_leaves_market_data_untouched() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::SetCryptocurrencies(market()),
+            StoreAction::SetError("offline".to_string()),
+            StoreAction::Undo,
+        ],
+    );
+
+    assert!(next.store.portfolio.is_empty());
+    assert_eq!(next.store.cryptocurrencies, market());
+    assert_eq!(next.store.error.as_deref(), Some("offline"));
+}
+
+// Status actions
+
+#[test]
+fn set_error_stores_message_and_stops_loading() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::FetchCryptocurrencies,
+            StoreAction::SetError("network down".to_string()),
+        ],
+    );
+
+    assert_eq!(next.store.error.as_deref(), Some("network down"));
+    assert!(!next.store.loading);
+}
+
+#[test]
+fn clear_error_removes_message() {
+    let next = apply_all(
+        StoreState::default(),
+        vec![StoreAction::SetError("boom".to_string()), StoreAction::ClearError],
+    );
+
+    assert_eq!(next.store.error, None);
+}
+
+#[test]
+fn set_loading_toggles_flag() {
+    let next = StoreState::default().apply(StoreAction::SetLoading(true));
+    assert!(next.store.loading);
+    assert!(!next.apply(StoreAction::SetLoading(false)).store.loading);
+}
+
+#[test]
+fn apply_does_not_mutate_previous_state() {
+    let state = StoreState::default();
+    let _ = state.apply(StoreAction::AddToPortfolio(item("bitcoin", dec!(1))));
+    assert!(state.store.portfolio.is_empty());
+}
+
+// Sync
+
+#[test]
+fn sync_adopts_server_revision_and_merges_remote_edits() {
+    let state = StoreState {
+        sync: SyncState::new(1, vec![item("bitcoin", dec!(1))]),
+        ..state_with(Store {
+            portfolio: vec![item("bitcoin", dec!(2))],
+            ..Store::new()
+        })
+    };
+    let sent = state.store.portfolio.clone();
+    let document = PortfolioDocument {
+        revision: 3,
+        items: vec![item("bitcoin", dec!(2)), item("ethereum", dec!(5))],
+    };
+
+    let next = apply_all(
+        state,
+        vec![StoreAction::SavePortfolio, StoreAction::SyncStarted, StoreAction::SyncCompleted { sent, document }],
+    );
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(2)), item("ethereum", dec!(5))]);
+    assert_eq!(next.sync.revision, 3);
+    assert_eq!(next.sync.status, SyncStatus::Synced);
+    assert_eq!(next.sync.pending_changes(&next.store.portfolio), 0);
+    // Remote changes aren't user edits
+    assert!(!next.history.can_undo());
+}
+
+#[test]
+fn undo_after_a_pull_keeps_remote_edits() {
+    let state = apply_all(
+        state_with(Store {
+            portfolio: vec![item("bitcoin", dec!(1))],
+            ..Store::new()
+        }),
+        vec![StoreAction::UpdatePortfolioItem(item("bitcoin", dec!(2))), StoreAction::SavePortfolio, StoreAction::SyncStarted],
+    );
+    // Another device added ethereum in the meantime
+    let sent = state.store.portfolio.clone();
+    let document = PortfolioDocument {
+        revision: 2,
+        items: vec![item("bitcoin", dec!(2)), item("ethereum", dec!(5))],
+    };
+    let state = state.apply(StoreAction::SyncCompleted { sent, document });
+
+    let undone = state.apply(StoreAction::Undo);
+    assert_eq!(undone.store.portfolio, vec![item("bitcoin", dec!(1)), item("ethereum", dec!(5))]);
+    let redone = undone.apply(StoreAction::Redo);
+    assert_eq!(redone.store.portfolio, state.store.portfolio);
+}
+
+#[test]
+fn edits_during_sync_stay_queued() {
+    let state = apply_all(
+        StoreState::default(),
+        vec![StoreAction::AddToPortfolio(item("bitcoin", dec!(1))), StoreAction::SavePortfolio, StoreAction::SyncStarted],
+    );
+    let sent = state.store.portfolio.clone();
+
+    let next = apply_all(
+        state,
+        vec![
+            StoreAction::AddToPortfolio(item("ethereum", dec!(2))),
+            StoreAction::SavePortfolio,
+            StoreAction::SyncCompleted {
+                sent: sent.clone(),
+                document: PortfolioDocument { revision: 1, items: sent },
+            },
+        ],
+    );
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(1)), item("ethereum", dec!(2))]);
+    assert_eq!(next.sync.pending_changes(&next.store.portfolio), 1);
+    // The follow-up sync is requested once the first completes
+    assert_eq!(next.sync.requested, 2);
+}
+
+#[test]
+fn failed_sync_keeps_local_edits() {
+    let state = apply_all(
+        StoreState::default(),
+        vec![StoreAction::AddToPortfolio(item("bitcoin", dec!(1))), StoreAction::SavePortfolio, StoreAction::SyncStarted],
+    );
+
+    let offline = state.apply(StoreAction::SyncFailed {
+        offline: true,
+        message: "The server could not be reached".to_string(),
+    });
+    assert_eq!(offline.sync.status, SyncStatus::Offline);
+    assert_eq!(offline.store.portfolio, vec![item("bitcoin", dec!(1))]);
+    assert_eq!(offline.sync.pending_changes(&offline.store.portfolio), 1);
+
+    let failed = state.apply(StoreAction::SyncFailed {
+        offline: false,
+        message: "Request failed: 500".to_string(),
+    });
+    assert_eq!(failed.sync.status, SyncStatus::Failed("Request failed: 500".to_string()));
+    assert_eq!(failed.apply(StoreAction::SavePortfolio).sync.requested, 2);
+}
+
+#[test]
+fn reset_portfolio_forgets_holdings_history_and_sync() {
+    let ledger = wallet(Chain::Bitcoin, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+    let state = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::SetCryptocurrencies(market()),
+            StoreAction::AddToPortfolio(item("bitcoin", dec!(1))),
+            StoreAction::SavePortfolio,
+            StoreAction::AddWallet(ledger.clone()),
+            StoreAction::SetWalletBalances(vec![(ledger.key(), native(&ledger, dec!(0.5)))]),
+            StoreAction::AddIncome(reward("ethereum", dec!(0.01), "2024-03-01")),
+        ],
+    );
+
+    let next = state.apply(StoreAction::ResetPortfolio);
+    assert!(next.store.portfolio.is_empty());
+    assert!(next.store.wallets.is_empty());
+    assert!(next.store.wallet_balances.is_empty());
+    assert!(next.store.income.is_empty());
+    assert!(next.store.holdings().is_empty());
+    assert_eq!(next.store.cryptocurrencies, market());
+    assert!(!next.history.can_undo());
+    assert_eq!(next.sync, SyncState::default());
+}
+
+#[test]
+fn restore_portfolio_loads_saved_items_and_sync_base() {
+    let state = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::SetCryptocurrencies(market()),
+            StoreAction::AddToPortfolio(item("ethereum", dec!(2))),
+        ],
+    );
+    let saved = SavedPortfolio {
+        revision: 4,
+        base: vec![item("bitcoin", dec!(1))],
+        items: vec![item("bitcoin", dec!(1.5))],
+    };
+
+    let next = state.apply(StoreAction::RestorePortfolio(saved));
+    assert_eq!(next.store.portfolio, vec![item("bitcoin", dec!(1.5))]);
+    assert_eq!(next.store.cryptocurrencies, market());
+    assert!(!next.history.can_undo());
+    assert_eq!(next.sync, SyncState::new(4, vec![item("bitcoin", dec!(1))]));
+    assert_eq!(next.sync.pending_changes(&next.store.portfolio), 1);
+}
+
+#[test]
+fn restored_and_synced_amounts_are_rounded_to_asset_precision() {
+    let saved = SavedPortfolio {
+        revision: 1,
+        base: Vec::new(),
+        items: vec![item("cardano", dec!(1.2345678)), item("ethereum", dec!(0.1234567890123))],
+    };
+    let state = apply_all(
+        StoreState::default(),
+        vec![StoreAction::RestorePortfolio(saved.clone()), StoreAction::SetCryptocurrencies(market())],
+    );
+    assert_eq!(state.store.portfolio, vec![item("cardano", dec!(1.234568)), item("ethereum", dec!(0.1234567890123))]);
+
+    // Without market data nothing is cut to the default precision
+    let unknown = StoreState::default().apply(StoreAction::RestorePortfolio(saved));
+    assert_eq!(unknown.store.portfolio[1].amount, dec!(0.1234567890123));
+
+    let synced = state.apply(StoreAction::SyncCompleted {
+        sent: state.store.portfolio.clone(),
+        document: PortfolioDocument {
+            revision: 2,
+            items: vec![item("cardano", dec!(2.0000004)), item("bitcoin", dec!(0.123456789))],
+        },
+    });
+    assert_eq!(synced.store.portfolio, vec![item("cardano", dec!(2)), item("bitcoin", dec!(0.12345679))]);
+}
+
+// Recently viewed
+
+fn recent_ids(state: &StoreState) -> Vec<&str> {
+    state.store.recently_viewed.iter().map(|asset| asset.id.as_str()).collect()
+}
+
+#[test]
+fn selecting_an_asset_records_it_as_recently_viewed() {
+    let state = apply_all(
+        StoreState::default(),
+        market().into_iter().chain([crypto("bitcoin", dec!(1), 8)]).map(StoreAction::SetSelectedCryptocurrency).collect(),
+    );
+
+    assert_eq!(recent_ids(&state), ["bitcoin", "cardano", "ethereum"]);
+    assert!(!state.history.can_undo());
+}
+
+#[test]
+fn restore_and_clear_recently_viewed() {
+    let saved = RecentlyViewed::new(vec![RecentAsset::from(&crypto("solana", dec!(1), 9))]);
+    let state = StoreState::default().apply(StoreAction::RestoreRecentlyViewed(saved));
+    assert_eq!(recent_ids(&state), ["solana"]);
+
+    let state = state.apply(StoreAction::SetSelectedCryptocurrency(crypto("bitcoin", dec!(1), 8)));
+    assert_eq!(recent_ids(&state), ["bitcoin", "solana"]);
+
+    let state = state.apply(StoreAction::ClearRecentlyViewed);
+    assert!(state.store.recently_viewed.is_empty());
+    assert!(state.store.selected_cryptocurrency.is_some());
+}
+
+// Watched wallets
+
+fn wallet(chain: Chain, address: &str) -> WatchedWallet {
+    WatchedWallet {
+        chain,
+        address: address.to_string(),
+        label: None,
+    }
+}
+
+fn native(wallet: &WatchedWallet, amount: Decimal) -> WalletBalance {
+    WalletBalance::Loaded(vec![WalletAsset::native(wallet.chain, amount)])
+}
+
+#[test]
+fn wallets_are_added_once_and_removed_with_their_balance() {
+    let ledger = wallet(Chain::Bitcoin, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+    let state = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddWallet(ledger.clone()),
+            StoreAction::AddWallet(WatchedWallet {
+                label: Some("Again".to_string()),
+                ..ledger.clone()
+            }),
+            StoreAction::SetWalletBalances(vec![(ledger.key(), native(&ledger, dec!(0.5)))]),
+        ],
+    );
+    assert_eq!(state.store.wallets, std::slice::from_ref(&ledger));
+    assert_eq!(state.store.wallet_balances[&ledger.key()], native(&ledger, dec!(0.5)));
+    assert!(!state.history.can_undo());
+
+    let state = state.apply(StoreAction::RemoveWallet(ledger.key()));
+    assert!(state.store.wallets.is_empty());
+    assert!(state.store.wallet_balances.is_empty());
+
+    // A read finishing after the wallet was removed is dropped
+    let state = state.apply(StoreAction::SetWalletBalances(vec![(ledger.key(), native(&ledger, dec!(0.5)))]));
+    assert!(state.store.wallet_balances.is_empty());
+}
+
+#[test]
+fn holdings_merge_wallet_balances_by_asset() {
+    let cold = wallet(Chain::Bitcoin, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+    let mainnet = wallet(Chain::Ethereum, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae");
+    let rollup = wallet(Chain::Arbitrum, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae");
+    let failing = wallet(Chain::Solana, "11111111111111111111111111111111");
+    let state = apply_all(
+        state_with(Store {
+            cryptocurrencies: market(),
+            portfolio: vec![item("bitcoin", dec!(0.5)), item("cardano", dec!(100))],
+            ..Store::new()
+        }),
+        [&cold, &mainnet, &rollup, &failing]
+            .into_iter()
+            .map(|wallet| StoreAction::AddWallet(wallet.clone()))
+            .chain([StoreAction::SetWalletBalances(vec![
+                (cold.key(), native(&cold, dec!(0.25))),
+                (
+                    mainnet.key(),
+                    WalletBalance::Loaded(vec![
+                        WalletAsset::native(Chain::Ethereum, dec!(1)),
+                        WalletAsset {
+                            asset_id: "bitcoin".to_string(),
+                            symbol: "WBTC".to_string(),
+                            token: Some("0x2260fac5e5542a773aa44fbcfedf7c193bc2c599".to_string()),
+                            amount: dec!(0.1),
+                        },
+                    ]),
+                ),
+                (rollup.key(), native(&rollup, dec!(0.5))),
+                (failing.key(), WalletBalance::Failed("timeout".to_string())),
+            ])])
+            .collect(),
+    );
+
+    let holdings = state.store.holdings();
+    let ids: Vec<&str> = holdings.iter().map(|holding| holding.crypto_id.as_str()).collect();
+    assert_eq!(ids, ["bitcoin", "cardano", "ethereum"]);
+    assert_eq!(holdings[0].amount(), dec!(0.85));
+    assert_eq!(holdings[0].manual(), Some(dec!(0.5)));
+    assert_eq!(holdings[0].sources[1].0, HoldingSource::Wallet { wallet: cold, token: None });
+    // Tokens count towards the asset they track
+    assert_eq!(
+        holdings[0].sources[2],
+        (HoldingSource::Wallet { wallet: mainnet, token: Some("WBTC".to_string()) }, dec!(0.1))
+    );
+    assert!(!holdings[1].has_wallets());
+    assert_eq!(holdings[2].manual(), None);
+    assert_eq!(holdings[2].amount(), dec!(1.5));
+
+    // 54011.4395 + 93 + 5132.475
+    assert_eq!(state.store.calculate_portfolio_value(), dec!(59236.9145));
+}
+
+#[test]
+fn restoring_wallets_forgets_old_balances() {
+    let cold = wallet(Chain::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+    let state = apply_all(
+        StoreState::default(),
+        vec![
+            StoreAction::AddWallet(cold.clone()),
+            StoreAction::SetWalletBalances(vec![(cold.key(), native(&cold, dec!(1)))]),
+            StoreAction::RestoreWallets(vec![cold.clone()]),
+        ],
+    );
+
+    assert_eq!(state.store.wallets, [cold]);
+    assert!(state.store.holdings().is_empty());
+}
+
+// Income
+
+fn reward(crypto_id: &str, amount: Decimal, received_on: &str) -> IncomeEvent {
+    IncomeEvent {
+        id: 0,
+        crypto_id: crypto_id.to_string(),
+        kind: IncomeKind::Staking,
+        amount,
+        value: dec!(10.005),
+        received_on: received_on.parse().unwrap(),
+        wallet: None,
+    }
+}
+
+#[test]
+fn income_is_kept_in_date_order_with_fresh_ids() {
+    let state = apply_all(
+        state_with(Store {
+            cryptocurrencies: market(),
+            ..Store::new()
+        }),
+        vec![
+            StoreAction::AddIncome(reward("ethereum", dec!(0.01), "2024-03-01")),
+            StoreAction::AddIncome(reward("cardano", dec!(1.2345678), "2024-01-01")),
+            StoreAction::AddIncome(reward("ethereum", dec!(0.02), "2024-03-01")),
+        ],
+    );
+
+    let ids: Vec<u64> = state.store.income.iter().map(|event| event.id).collect();
+    assert_eq!(ids, [2, 1, 3]);
+    // Rounded to the asset's precision and to cents
+    assert_eq!(state.store.income[0].amount, dec!(1.234568));
+    assert_eq!(state.store.income[0].value, dec!(10.00));
+
+    let state = state.apply(StoreAction::RemoveIncome(1));
+    let ids: Vec<u64> = state.store.income.iter().map(|event| event.id).collect();
+    assert_eq!(ids, [2, 3]);
+    let state = state.apply(StoreAction::AddIncome(reward("ethereum", dec!(0.01), "2024-04-01")));
+    assert_eq!(state.store.income[2].id, 4);
+
+    let state = state.apply(StoreAction::RestoreIncome(vec![reward("bitcoin", dec!(1), "2024-01-02")]));
+    assert_eq!(state.store.income.len(), 1);
+}
+
+#[test]
+fn income_grows_holdings() {
+    let state = apply_all(
+        state_with(Store {
+            cryptocurrencies: market(),
+            portfolio: vec![item("ethereum", dec!(2))],
+            ..Store::new()
+        }),
+        vec![
+            StoreAction::AddIncome(reward("ethereum", dec!(0.01), "2024-03-01")),
+            StoreAction::AddIncome(reward("ethereum", dec!(0.02), "2024-04-01")),
+            StoreAction::AddIncome(reward("cardano", dec!(50), "2024-04-01")),
+        ],
+    );
+
+    let holdings = state.store.holdings();
+    let ids: Vec<&str> = holdings.iter().map(|holding| holding.crypto_id.as_str()).collect();
+    assert_eq!(ids, ["ethereum", "cardano"]);
+    assert_eq!(holdings[0].manual(), Some(dec!(2)));
+    assert_eq!(holdings[0].sources[1], (HoldingSource::Income, dec!(0.03)));
+    assert_eq!(holdings[0].amount(), dec!(2.03));
+    assert_eq!(holdings[1].sources, [(HoldingSource::Income, dec!(50))]);
+    // The synced portfolio itself is untouched
+    assert_eq!(state.store.portfolio, [item("ethereum", dec!(2))]);
+}
+
+#[test]
+fn income_paid_into_a_watched_wallet_is_not_counted_twice() {
+    let staking = wallet(Chain::Ethereum, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae");
+    let base = wallet(Chain::Base, "0x00000000219ab540356cbb839cbe05303d7705fa");
+    let state = apply_all(
+        state_with(Store {
+            cryptocurrencies: market(),
+            portfolio: vec![item("ethereum", dec!(2))],
+            ..Store::new()
+        }),
+        vec![
+            StoreAction::AddWallet(staking.clone()),
+            StoreAction::AddWallet(base.clone()),
+            StoreAction::AddIncome(IncomeEvent {
+                wallet: Some(staking.key()),
+                ..reward("ethereum", dec!(0.01), "2024-03-01")
+            }),
+            // From an exchange, so in no watched balance
+            StoreAction::AddIncome(reward("ethereum", dec!(0.02), "2024-04-01")),
+        ],
+    );
+
+    // Counted the same before the wallet's balance is read
+    let holdings = state.store.holdings();
+    assert_eq!(holdings[0].sources, [(HoldingSource::Manual, dec!(2)), (HoldingSource::Income, dec!(0.02))]);
+
+    let state = state.apply(StoreAction::SetWalletBalances(vec![
+        // The reward already shows in the wallet's balance
+        (staking.key(), native(&staking, dec!(1.01))),
+        (base.key(), native(&base, dec!(0.5))),
+    ]));
+    let holdings = state.store.holdings();
+    assert_eq!(holdings.len(), 1);
+    assert_eq!(holdings[0].sources.last(), Some(&(HoldingSource::Income, dec!(0.02))));
+    assert_eq!(holdings[0].amount(), dec!(3.53));
+
+    // Once the wallet isn't watched, its rewards count again
+    let state = state.apply(StoreAction::RemoveWallet(staking.key()));
+    assert_eq!(state.store.holdings()[0].sources.last(), Some(&(HoldingSource::Income, dec!(0.03))));
+}
+
+// Portfolio value
+
+#[test]
+fn portfolio_value_of_empty_portfolio_is_zero() {
+    assert_eq!(Store::new().calculate_portfolio_value(), Decimal::ZERO);
+}
+
+#[test]
+fn portfolio_value_sums_holdings() {
+    let store = Store {
+        cryptocurrencies: market(),
+        portfolio: vec![item("bitcoin", dec!(0.5)), item("ethereum", dec!(2)), item("cardano", dec!(100))],
+        ..Store::new()
+    };
+
+    // 31771.435 + 6843.30 + 93
+    assert_eq!(store.calculate_portfolio_value(), dec!(38707.735));
+}
+
+#[test]
+fn portfolio_value_skips_items_without_price_data() {
+    let store = Store {
+        cryptocurrencies: market(),
+        portfolio: vec![item("bitcoin", dec!(1)), item("unlisted", dec!(1000))],
+        ..Store::new()
+    };
+
+    assert_eq!(store.calculate_portfolio_value(), dec!(63542.87));
+}
+
+#[test]
+fn portfolio_value_without_market_data_is_zero() {
+    let store = Store {
+        portfolio: vec![item("bitcoin", dec!(1))],
+        ..Store::new()
+    };
+
+    assert_eq!(store.calculate_portfolio_value(), Decimal::ZERO);
+}
+
+#[test]
+fn portfolio_value_does_not_drift() {
+    // 0.1 + 0.2 is 0.30000000000000004 in f64
+    let store = Store {
+        cryptocurrencies: vec![crypto("first", dec!(0.1), 8), crypto("second", dec!(0.2), 8)],
+        portfolio: vec![item("first", dec!(1)), item("second", dec!(1))],
+        ..Store::new()
+    };
+
+    assert_eq!(store.calculate_portfolio_value(), dec!(0.3));
+}
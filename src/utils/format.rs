@@ -0,0 +1,369 @@
+// src/utils/format.rs
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Significant digits shown for prices below one unit
+const PRICE_SIGNIFICANT_DIGITS: usize = 4;
+// Prices with at least this many leading fractional zeros use subscript notation
+const SUBSCRIPT_ZERO_THRESHOLD: usize = 4;
+// Maximum fractional digits shown for quantities
+const QUANTITY_DECIMALS: u32 = 8;
+
+// Fiat currency amounts are shown in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Chf,
+}
+
+impl Currency {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Chf => "CHF",
+        }
+    }
+}
+
+// Locale-specific symbols used when rendering numbers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberSymbols {
+    pub decimal: char,
+    pub group: &'static str,
+    pub percent: &'static str,
+    pub currency_prefix: bool,
+    // Suffixes for thousands, millions, billions and trillions
+    pub compact: [&'static str; 4],
+}
+
+impl Default for NumberSymbols {
+    fn default() -> Self {
+        Self {
+            decimal: '.',
+            group: ",",
+            percent: "%",
+            currency_prefix: true,
+            compact: ["K", "M", "B", "T"],
+        }
+    }
+}
+
+// Fixed number of decimals with thousands separators
+pub fn format_decimal(value: f64, decimals: usize, symbols: &NumberSymbols) -> String {
+    let raw = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = split_fraction(&raw);
+    join_parts(is_negative(value, &raw), int_part, frac_part, symbols)
+}
+
+// Two decimals for prices of one unit or more, significant digits below that,
+// and subscript-zero notation (0.0₅123) for micro prices
+pub fn format_price(value: f64, symbols: &NumberSymbols) -> String {
+    let magnitude = value.abs();
+    if magnitude >= 1.0 || magnitude == 0.0 || !magnitude.is_finite() {
+        return format_decimal(value, 2, symbols);
+    }
+
+    let leading_zeros = (-magnitude.log10()).floor() as usize;
+    let raw = format!("{:.*}", leading_zeros + PRICE_SIGNIFICANT_DIGITS, magnitude);
+    let (int_part, frac_part) = split_fraction(&raw);
+    let frac_part = frac_part.unwrap_or_default();
+    let negative = is_negative(value, &raw);
+
+    // Count zeros on the rounded digits so that carries such as 0.00009999 -> 0.0001 are respected
+    let zeros = frac_part.chars().take_while(|c| *c == '0').count();
+    if int_part == "0" && zeros >= SUBSCRIPT_ZERO_THRESHOLD && zeros < frac_part.len() {
+        let significant = frac_part[zeros..].trim_end_matches('0');
+        return format!(
+            "{}0{}0{}{}",
+            if negative { "-" } else { "" },
+            symbols.decimal,
+            subscript(zeros),
+            significant
+        );
+    }
+
+    let trimmed = trim_fraction(frac_part, 2);
+    join_parts(negative, int_part, Some(trimmed), symbols)
+}
+
+// Exact decimal with a fixed number of decimals, used for money totals
+pub fn format_money(value: &Decimal, decimals: u32, symbols: &NumberSymbols) -> String {
+    let raw = format!("{:.*}", decimals as usize, value.round_dp(decimals));
+    localize_number(&raw, symbols)
+}
+
+// Quantities keep up to eight decimals without trailing zeros
+pub fn format_quantity(value: &Decimal, symbols: &NumberSymbols) -> String {
+    let raw = value.round_dp(QUANTITY_DECIMALS).normalize().to_string();
+    localize_number(&raw, symbols)
+}
+
+// Apply locale separators to a plain "-1234.5" style number
+pub fn localize_number(raw: &str, symbols: &NumberSymbols) -> String {
+    let (negative, unsigned) = match raw.strip_prefix('-') {
+        Some(unsigned) => (unsigned.chars().any(|c| c.is_ascii_digit() && c != '0'), unsigned),
+        None => (false, raw),
+    };
+    let (int_part, frac_part) = split_fraction(unsigned);
+    join_parts(negative, int_part, frac_part, symbols)
+}
+
+// Three significant digits with a K/M/B/T suffix, or none below a thousand
+pub fn format_compact(value: f64, symbols: &NumberSymbols) -> String {
+    let magnitude = value.abs();
+    let scales = [1e12, 1e9, 1e6, 1e3];
+    // Values that round up to the next scale (999_999 -> 1000K) are shown in that scale
+    let (scaled, suffix) = match scales.iter().position(|scale| magnitude >= scale * 0.9995) {
+        Some(index) => (value / scales[index], symbols.compact[3 - index]),
+        None => (value, ""),
+    };
+    let decimals = match scaled.abs() {
+        m if m >= 99.95 => 0,
+        m if m >= 9.995 => 1,
+        _ => 2,
+    };
+    let raw = format!("{:.*}", decimals, scaled.abs());
+    let (int_part, frac_part) = split_fraction(&raw);
+    let frac_part = frac_part.map(|frac| trim_fraction(frac, 0));
+    let number = join_parts(is_negative(value, &raw), int_part, frac_part, symbols);
+    format!("{}{}", number, suffix)
+}
+
+pub fn format_percent(value: f64, decimals: usize, symbols: &NumberSymbols) -> String {
+    format!("{}{}", format_decimal(value, decimals, symbols), symbols.percent)
+}
+
+// Percentage with an explicit sign, e.g. +2.34% / -1.23%
+pub fn format_signed_percent(value: f64, decimals: usize, symbols: &NumberSymbols) -> String {
+    let formatted = format_percent(value, decimals, symbols);
+    if formatted.starts_with('-') || !formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        formatted
+    } else {
+        format!("+{}", formatted)
+    }
+}
+
+pub fn format_currency(formatted: String, currency: Currency, symbols: &NumberSymbols) -> String {
+    let symbol = currency.symbol();
+    // Letter codes such as CHF are set apart from the number
+    let gap = if symbol.chars().all(char::is_alphabetic) { "\u{a0}" } else { "" };
+    if symbols.currency_prefix {
+        match formatted.strip_prefix('-') {
+            Some(unsigned) => format!("-{}{}{}", symbol, gap, unsigned),
+            None => format!("{}{}{}", symbol, gap, formatted),
+        }
+    } else {
+        format!("{}\u{a0}{}", formatted, symbol)
+    }
+}
+
+fn split_fraction(raw: &str) -> (&str, Option<&str>) {
+    match raw.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (raw, None),
+    }
+}
+
+// Avoid rendering "-0.00" for values that round to zero
+fn is_negative(value: f64, rounded: &str) -> bool {
+    value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0')
+}
+
+fn trim_fraction(frac_part: &str, min_decimals: usize) -> &str {
+    let trimmed = frac_part.trim_end_matches('0');
+    if trimmed.len() < min_decimals {
+        &frac_part[..min_decimals.min(frac_part.len())]
+    } else {
+        trimmed
+    }
+}
+
+fn join_parts(
+    negative: bool,
+    int_part: &str,
+    frac_part: Option<&str>,
+    symbols: &NumberSymbols,
+) -> String {
+    let mut result = String::new();
+    if negative {
+        result.push('-');
+    }
+    result.push_str(&group_digits(int_part, symbols.group));
+    if let Some(frac_part) = frac_part.filter(|frac| !frac.is_empty()) {
+        result.push(symbols.decimal);
+        result.push_str(frac_part);
+    }
+    result
+}
+
+fn group_digits(digits: &str, separator: &str) -> String {
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn subscript(value: usize) -> String {
+    value
+        .to_string()
+        .chars()
+        .filter_map(|digit| digit.to_digit(10))
+        .filter_map(|digit| char::from_u32(0x2080 + digit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn en() -> NumberSymbols {
+        NumberSymbols::default()
+    }
+
+    fn de() -> NumberSymbols {
+        NumberSymbols {
+            decimal: ',',
+            group: ".",
+            percent: "\u{a0}%",
+            currency_prefix: false,
+            compact: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+        }
+    }
+
+    #[test]
+    fn decimal_groups_thousands() {
+        assert_eq!(format_decimal(1245678900000.0, 2, &en()), "1,245,678,900,000.00");
+        assert_eq!(format_decimal(999.5, 0, &en()), "1,000");
+        assert_eq!(format_decimal(63542.87, 2, &de()), "63.542,87");
+        assert_eq!(format_decimal(12.0, 0, &en()), "12");
+    }
+
+    #[test]
+    fn decimal_never_renders_negative_zero() {
+        assert_eq!(format_decimal(-0.001, 2, &en()), "0.00");
+        assert_eq!(format_decimal(-1234.5, 1, &en()), "-1,234.5");
+    }
+
+    #[test]
+    fn price_uses_two_decimals_above_one() {
+        assert_eq!(format_price(63542.87, &en()), "63,542.87");
+        assert_eq!(format_price(14.78, &en()), "14.78");
+        assert_eq!(format_price(1.0, &en()), "1.00");
+    }
+
+    #[test]
+    fn price_uses_significant_digits_below_one() {
+        assert_eq!(format_price(0.93, &en()), "0.93");
+        assert_eq!(format_price(0.012345, &en()), "0.01235");
+        assert_eq!(format_price(0.5, &de()), "0,50");
+        assert_eq!(format_price(0.000123456, &en()), "0.0001235");
+    }
+
+    #[test]
+    fn price_uses_subscript_zeros_for_micro_values() {
+        assert_eq!(format_price(0.00000123, &en()), "0.0₅123");
+        assert_eq!(format_price(0.0000000000456, &en()), "0.0₁₀456");
+        assert_eq!(format_price(0.00000123, &de()), "0,0₅123");
+    }
+
+    #[test]
+    fn price_respects_rounding_carry() {
+        assert_eq!(format_price(0.000099999, &en()), "0.0001");
+        assert_eq!(format_price(0.99999, &en()), "1.00");
+    }
+
+    #[test]
+    fn price_handles_zero_and_negative_values() {
+        assert_eq!(format_price(0.0, &en()), "0.00");
+        assert_eq!(format_price(-0.00000123, &en()), "-0.0₅123");
+    }
+
+    #[test]
+    fn quantity_trims_trailing_zeros() {
+        assert_eq!(format_quantity(&dec!(0.500), &en()), "0.5");
+        assert_eq!(format_quantity(&dec!(2), &en()), "2");
+        assert_eq!(format_quantity(&dec!(0.00000001), &en()), "0.00000001");
+        assert_eq!(format_quantity(&dec!(0.000000001), &en()), "0");
+        assert_eq!(format_quantity(&dec!(12345.678), &de()), "12.345,678");
+    }
+
+    #[test]
+    fn money_rounds_exactly() {
+        assert_eq!(format_money(&dec!(1234.565), 2, &en()), "1,234.56");
+        assert_eq!(format_money(&(dec!(0.1) + dec!(0.2)), 2, &en()), "0.30");
+        assert_eq!(format_money(&dec!(-9876543.2), 2, &de()), "-9.876.543,20");
+    }
+
+    #[test]
+    fn localize_number_applies_separators() {
+        assert_eq!(localize_number("1234567.891", &de()), "1.234.567,891");
+        assert_eq!(localize_number("-0.00", &en()), "0.00");
+        assert_eq!(localize_number("42", &en()), "42");
+    }
+
+    #[test]
+    fn compact_uses_suffixes() {
+        assert_eq!(format_compact(1245678900000.0, &en()), "1.25T");
+        assert_eq!(format_compact(1_200_000_000.0, &en()), "1.2B");
+        assert_eq!(format_compact(86234567890.0, &en()), "86.2B");
+        assert_eq!(format_compact(412345678900.0, &en()), "412B");
+        assert_eq!(format_compact(1500.0, &en()), "1.5K");
+        assert_eq!(format_compact(999.0, &en()), "999");
+        assert_eq!(format_compact(1_200_000_000.0, &de()), "1,2\u{a0}Mrd.");
+    }
+
+    #[test]
+    fn compact_promotes_values_that_round_up() {
+        assert_eq!(format_compact(999_999.0, &en()), "1M");
+        assert_eq!(format_compact(-2_500_000.0, &en()), "-2.5M");
+    }
+
+    #[test]
+    fn compact_keeps_three_digits_below_a_thousand() {
+        assert_eq!(format_compact(999.4, &en()), "999");
+        assert_eq!(format_compact(999.5, &en()), "1K");
+        assert_eq!(format_compact(1000.0, &en()), "1K");
+        assert_eq!(format_compact(12.345, &en()), "12.3");
+        assert_eq!(format_compact(1.5, &de()), "1,5");
+        assert_eq!(format_compact(-0.001, &en()), "0");
+    }
+
+    #[test]
+    fn signed_percent_includes_sign() {
+        assert_eq!(format_signed_percent(2.34, 2, &en()), "+2.34%");
+        assert_eq!(format_signed_percent(-1.23, 2, &en()), "-1.23%");
+        assert_eq!(format_signed_percent(0.0, 2, &en()), "0.00%");
+        assert_eq!(format_signed_percent(5.67, 2, &de()), "+5,67\u{a0}%");
+    }
+
+    #[test]
+    fn currency_places_symbol_per_locale() {
+        assert_eq!(format_currency("1,234.56".to_string(), Currency::Usd, &en()), "$1,234.56");
+        assert_eq!(format_currency("-12.00".to_string(), Currency::Usd, &en()), "-$12.00");
+        assert_eq!(format_currency("1.234,56".to_string(), Currency::Usd, &de()), "1.234,56\u{a0}$");
+    }
+
+    #[test]
+    fn currency_uses_configured_symbol() {
+        assert_eq!(format_currency("-12.00".to_string(), Currency::Eur, &en()), "-€12.00");
+        assert_eq!(format_currency("12.00".to_string(), Currency::Chf, &en()), "CHF\u{a0}12.00");
+        assert_eq!(format_currency("1.234,56".to_string(), Currency::Gbp, &de()), "1.234,56\u{a0}£");
+    }
+
+    #[test]
+    fn subscript_handles_multiple_digits() {
+        assert_eq!(subscript(5), "₅");
+        assert_eq!(subscript(12), "₁₂");
+    }
+}
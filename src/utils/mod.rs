@@ -0,0 +1,3 @@
+// src/utils/mod.rs
+pub mod format;
+pub mod settings;
@@ -0,0 +1,38 @@
+// src/utils/settings.rs
+use gloo::storage::{LocalStorage, Storage};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use yew::prelude::*;
+
+// Settings kept in local storage under `key`. They start at the defaults, so
+// server and client render alike, and switch to the stored ones once mounted.
+// Stored and updated values both pass through `normalize`; updates are saved.
+#[hook]
+pub fn use_stored_settings<T>(key: &'static str, normalize: fn(T) -> T) -> (T, Callback<T>)
+where
+    T: Clone + Default + Serialize + DeserializeOwned + 'static,
+{
+    let settings = use_state(T::default);
+
+    {
+        let settings = settings.clone();
+        use_effect_with(key, move |key| {
+            if let Ok(stored) = LocalStorage::get::<T>(*key) {
+                settings.set(normalize(stored));
+            }
+        });
+    }
+
+    let update = {
+        let settings = settings.clone();
+        Callback::from(move |next: T| {
+            let next = normalize(next);
+            if let Err(err) = LocalStorage::set(key, &next) {
+                log::warn!("Failed to persist {}: {}", key, err);
+            }
+            settings.set(next);
+        })
+    };
+
+    ((*settings).clone(), update)
+}
@@ -0,0 +1,91 @@
+// ssr-server/src/main.rs
+//
+// Serves the Trunk build with the landing and asset pages rendered on the
+// server: market data for the route is fetched up front, the page is
+// rendered to HTML with Yew's `ssr` feature and the client hydrates it from
+// the embedded state. Other routes and all static files are served as built.
+mod options;
+mod page;
+mod prefetch;
+mod server;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use page::Template;
+use server::State;
+use yew_crypto_tracker::config::Config;
+
+const WORKERS: usize = 4;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", options::USAGE);
+        return;
+    }
+    let options = options::parse(args).unwrap_or_else(|message| {
+        eprintln!("error: {}\n\n{}", message, options::USAGE);
+        process::exit(2);
+    });
+    let index = options.dist.join("index.html");
+    let template = fs::read_to_string(&index)
+        .map_err(|error| error.to_string())
+        .and_then(Template::new)
+        .unwrap_or_else(|error| {
+            eprintln!("error: cannot load {}: {}", index.display(), error);
+            process::exit(1);
+        });
+
+    let config = load_config(&options.dist.join("config.json"));
+
+    let http = tiny_http::Server::http(("0.0.0.0", options.port)).unwrap_or_else(|error| {
+        eprintln!("error: cannot listen on port {}: {}", options.port, error);
+        process::exit(1);
+    });
+    let http = Arc::new(http);
+    let state = Arc::new(State {
+        options,
+        config,
+        template,
+    });
+    println!("serving {} on http://localhost:{}", state.options.dist.display(), state.options.port);
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let http = http.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                for request in http.incoming_requests() {
+                    server::handle(&state, request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+// Same rules as the client: optional, and ignored as a whole when invalid
+fn load_config(path: &Path) -> Config {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Config::default(),
+        Err(error) => {
+            eprintln!("warning: using built-in settings, cannot read {}: {}", path.display(), error);
+            return Config::default();
+        }
+    };
+    Config::parse(&text).unwrap_or_else(|problems| {
+        for problem in problems {
+            eprintln!("warning: {}: {}", path.display(), problem);
+        }
+        eprintln!("warning: using built-in settings, {} is invalid", path.display());
+        Config::default()
+    })
+}
@@ -0,0 +1,89 @@
+// ssr-server/src/options.rs
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: ssr-server [OPTIONS]
+
+Options:
+  --port <PORT>            Port to listen on [default: 8080]
+  --dist <DIR>             Trunk output directory holding index.html [default: dist]
+  --api <URL>              API to prefetch market data from [default: the one in the app's config]
+  -h, --help               Print this help
+
+Build the client first with `trunk build --release`. A `config.json` in the
+dist directory is read at startup and handed to the client with each page.";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub port: u16,
+    pub dist: PathBuf,
+    pub api: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            port: 8080,
+            dist: PathBuf::from("dist"),
+            api: None,
+        }
+    }
+}
+
+// Accepts both `--flag value` and `--flag=value`
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+
+        match flag.as_str() {
+            "--port" => {
+                let port = value()?;
+                options.port = port
+                    .parse()
+                    .map_err(|_| format!("--port expects a whole number, got `{}`", port))?;
+            }
+            "--dist" => options.dist = PathBuf::from(value()?),
+            "--api" => options.api = Some(value()?.trim_end_matches('/').to_string()),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_both_flag_forms() {
+        let options = parse(args(&["--port", "3000", "--dist=site", "--api", "http://localhost:8787/"])).unwrap();
+
+        assert_eq!(options.port, 3000);
+        assert_eq!(options.dist, PathBuf::from("site"));
+        assert_eq!(options.api.as_deref(), Some("http://localhost:8787"));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse(args(&["--port"])).is_err());
+        assert!(parse(args(&["--port", "http"])).is_err());
+        assert!(parse(args(&["--verbose"])).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// ssr-server/src/page.rs
+//
+// Fills Trunk's `index.html` with a rendered page. The app's markup replaces
+// the empty body, where the client's `Renderer::hydrate` expects it, and its
+// state goes into the head.
+use yew_crypto_tracker::ssr::INITIAL_STATE_ID;
+
+pub struct Page<'a> {
+    pub lang: &'a str,
+    pub title: Option<String>,
+    pub body: String,
+    // Serialized `InitialState`
+    pub state: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Template {
+    source: String,
+}
+
+impl Template {
+    pub fn new(mut source: String) -> Result<Self, String> {
+        for marker in ["<html", "</head>", "<body"] {
+            if !source.contains(marker) {
+                return Err(format!("index.html has no `{}`", marker));
+            }
+        }
+        // Trunk's relative `./` base would resolve against deep links such as
+        // /details/bitcoin, breaking asset paths and the router's basename
+        if source.contains("<base") {
+            set_attribute_value(&mut source, "<base", "href", "/");
+        }
+        Ok(Self { source })
+    }
+
+    // The page as Trunk built it, for routes rendered on the client only
+    pub fn shell(&self) -> &str {
+        &self.source
+    }
+
+    pub fn render(&self, page: &Page) -> String {
+        let mut html = self.source.clone();
+
+        set_attribute_value(&mut html, "<html", "lang", page.lang);
+        if let Some(title) = &page.title {
+            if let (Some(start), Some(end)) = (html.find("<title>"), html.find("</title>")) {
+                html.replace_range(start + "<title>".len()..end, &escape(title));
+            }
+        }
+
+        let body = html.find("<body").and_then(|start| html[start..].find('>').map(|end| start + end + 1));
+        if let Some(body) = body {
+            html.insert_str(body, &page.body);
+        }
+
+        let script = format!(
+            "<script id=\"{}\" type=\"application/json\">{}</script>\n",
+            INITIAL_STATE_ID, page.state
+        );
+        if let Some(head_end) = html.find("</head>") {
+            html.insert_str(head_end, &script);
+        }
+        html
+    }
+}
+
+// Replaces the value of `name` on the first `tag`, adding the attribute if missing
+fn set_attribute_value(html: &mut String, tag: &str, name: &str, value: &str) {
+    let Some(start) = html.find(tag) else {
+        return;
+    };
+    let Some(end) = html[start..].find('>').map(|end| start + end) else {
+        return;
+    };
+    let needle = format!(" {}=\"", name);
+    match html[start..end].find(&needle) {
+        Some(offset) => {
+            let value_start = start + offset + needle.len();
+            if let Some(len) = html[value_start..end].find('"') {
+                html.replace_range(value_start..value_start + len, value);
+            }
+        }
+        None => html.insert_str(start + tag.len(), &format!("{}{}\"", needle, value)),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<title>Crypto Tracker</title>\n<base href=\"./\" />\n</head>\n<body class=\"antialiased\"></body>\n</html>";
+
+    fn page() -> Page<'static> {
+        Page {
+            lang: "de",
+            title: Some("Bitcoin <BTC>".to_string()),
+            body: "<div>app</div>".to_string(),
+            state: "{}".to_string(),
+        }
+    }
+
+    #[test]
+    fn fills_body_head_title_and_language() {
+        let html = Template::new(INDEX.to_string()).unwrap().render(&page());
+
+        assert!(html.contains("<html lang=\"de\">"));
+        assert!(html.contains("<title>Bitcoin &lt;BTC&gt;</title>"));
+        assert!(html.contains("<script id=\"initial-state\" type=\"application/json\">{}</script>\n</head>"));
+        assert!(html.contains("<body class=\"antialiased\"><div>app</div></body>"));
+    }
+
+    #[test]
+    fn serves_assets_from_the_root() {
+        let template = Template::new(INDEX.to_string()).unwrap();
+
+        assert!(template.shell().contains("<base href=\"/\" />"));
+    }
+
+    #[test]
+    fn adds_missing_language_and_keeps_title_without_override() {
+        let source = INDEX.replace(" lang=\"en\"", "");
+        let html = Template::new(source).unwrap().render(&Page { title: None, ..page() });
+
+        assert!(html.contains("<html lang=\"de\">"));
+        assert!(html.contains("<title>Crypto Tracker</title>"));
+    }
+
+    #[test]
+    fn rejects_templates_without_a_body() {
+        assert!(Template::new("<html><head></head></html>".to_string()).is_err());
+    }
+}
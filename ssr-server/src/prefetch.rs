@@ -0,0 +1,69 @@
+// ssr-server/src/prefetch.rs
+//
+// Loads the market data a route shows on first paint, from the API the app
+// talks to or, without one, from the sample data the app falls back to.
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use yew_crypto_tracker::app::Route;
+use yew_crypto_tracker::models::crypto::Cryptocurrency;
+use yew_crypto_tracker::services::api;
+use yew_crypto_tracker::store::Store;
+
+// A slow API shouldn't hold the page back for long; the client fetches anyway
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+pub fn store_for(api: Option<&str>, route: &Route) -> Result<Store, String> {
+    let mut store = Store::new();
+    match route {
+        Route::Home => store.cryptocurrencies = cryptocurrencies(api)?,
+        Route::Details { id } => store.selected_cryptocurrency = Some(cryptocurrency(api, id)?),
+        _ => {}
+    }
+    Ok(store)
+}
+
+fn cryptocurrencies(api: Option<&str>) -> Result<Vec<Cryptocurrency>, String> {
+    match api {
+        Some(base) => get_json(&format!("{}/cryptocurrencies", base)),
+        None => Ok(api::sample_cryptocurrencies()),
+    }
+}
+
+fn cryptocurrency(api: Option<&str>, id: &str) -> Result<Cryptocurrency, String> {
+    match api {
+        Some(base) => get_json(&format!("{}/cryptocurrencies/{}", base, id)),
+        None => api::sample_cryptocurrencies()
+            .into_iter()
+            .find(|crypto| crypto.id == id)
+            .ok_or_else(|| format!("no sample cryptocurrency `{}`", id)),
+    }
+}
+
+fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    ureq::get(url)
+        .timeout(TIMEOUT)
+        .call()
+        .map_err(|err| format!("{}: {}", url, err))?
+        .into_json()
+        .map_err(|err| format!("{}: {}", url, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_sample_data_without_an_api() {
+        let home = store_for(None, &Route::Home).unwrap();
+        assert_eq!(home.cryptocurrencies, api::sample_cryptocurrencies());
+        assert!(home.selected_cryptocurrency.is_none());
+
+        let details = store_for(None, &Route::Details { id: "ethereum".to_string() }).unwrap();
+        assert_eq!(details.selected_cryptocurrency.unwrap().symbol, "ETH");
+        assert!(details.cryptocurrencies.is_empty());
+
+        assert!(store_for(None, &Route::Details { id: "dogecoin".to_string() }).is_err());
+        assert_eq!(store_for(None, &Route::NotFound).unwrap(), Store::new());
+    }
+}
@@ -0,0 +1,161 @@
+// ssr-server/src/server.rs
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+use yew::ServerRenderer;
+use yew_crypto_tracker::app::{Route, ServerApp, ServerAppProps};
+use yew_crypto_tracker::config::Config;
+use yew_crypto_tracker::i18n::Locale;
+use yew_crypto_tracker::ssr::InitialState;
+use yew_router::Routable;
+
+use crate::options::Options;
+use crate::page::{Page, Template};
+use crate::prefetch;
+
+type Reply = Response<Cursor<Vec<u8>>>;
+
+pub struct State {
+    pub options: Options,
+    pub config: Config,
+    pub template: Template,
+}
+
+pub fn handle(state: &State, request: Request) {
+    let response = match request.method() {
+        Method::Get | Method::Head => {
+            let path = request.url().split('?').next().unwrap_or("/");
+            let language = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Accept-Language"))
+                .map(|header| header.value.as_str());
+            respond(state, path, language)
+        }
+        _ => Response::from_data(Vec::new()).with_status_code(StatusCode(405)),
+    };
+    let _ = request.respond(response);
+}
+
+fn respond(state: &State, path: &str, language: Option<&str>) -> Reply {
+    if let Some(file) = static_file(&state.options.dist, path) {
+        return file;
+    }
+
+    let route = Route::recognize(path).unwrap_or(Route::NotFound);
+    let status = if route == Route::NotFound { 404 } else { 200 };
+    if !prerendered(&route) {
+        return html(status, state.template.shell().to_string());
+    }
+
+    // Without the data the page would render empty; let the client load it
+    let api = state.options.api.clone().or_else(|| state.config.api_base_url());
+    let store = match prefetch::store_for(api.as_deref(), &route) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("warning: rendering {} on the client only: {}", path, err);
+            return html(status, state.template.shell().to_string());
+        }
+    };
+    let initial = InitialState {
+        path: path.to_string(),
+        config: state.config.clone(),
+        locale: language.map(preferred_locale).unwrap_or_default(),
+        store,
+    };
+
+    let page = Page {
+        lang: initial.locale.code(),
+        title: initial.store.selected_cryptocurrency.as_ref().map(|crypto| {
+            let app_name = initial.config.branding.app_name.as_deref().unwrap_or("Crypto Tracker");
+            format!("{} ({}) - {}", crypto.name, crypto.symbol, app_name)
+        }),
+        state: initial.to_script(),
+        body: futures::executor::block_on(
+            ServerRenderer::<ServerApp>::with_props(move || ServerAppProps { initial }).render(),
+        ),
+    };
+    html(status, state.template.render(&page))
+}
+
+// Public pages worth indexing; the rest depend on the visitor and are
+// rendered on the client
+fn prerendered(route: &Route) -> bool {
+    matches!(route, Route::Home | Route::Details { .. } | Route::NotFound)
+}
+
+// First supported language of an `Accept-Language` header
+fn preferred_locale(header: &str) -> Locale {
+    header
+        .split(',')
+        .filter_map(|tag| tag.split(';').next())
+        .find_map(|tag| Locale::from_code(tag.trim()))
+        .unwrap_or_default()
+}
+
+fn static_file(dist: &Path, path: &str) -> Option<Reply> {
+    let relative = path.trim_start_matches('/');
+    if relative.is_empty() || relative.split('/').any(|segment| segment.is_empty() || segment == "..") {
+        return None;
+    }
+    let file = dist.join(relative);
+    let body = fs::read(&file).ok()?;
+    Some(Response::from_data(body).with_header(header("Content-Type", content_type(&file))))
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file.extension().and_then(|extension| extension.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript",
+        // Lets browsers compile the module while it downloads
+        Some("wasm") => "application/wasm",
+        Some("css") => "text/css",
+        Some("json") => "application/json",
+        Some("webmanifest") => "application/manifest+json",
+        Some("png") => "image/png",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+fn html(status: u16, body: String) -> Reply {
+    Response::from_data(body.into_bytes())
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "text/html; charset=utf-8"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header names are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_first_supported_language() {
+        assert_eq!(preferred_locale("de-AT,de;q=0.9,en;q=0.8"), Locale::De);
+        assert_eq!(preferred_locale("es-ES, fr;q=0.7"), Locale::Fr);
+        assert_eq!(preferred_locale("ja"), Locale::En);
+    }
+
+    #[test]
+    fn only_prerenders_public_pages() {
+        assert!(prerendered(&Route::Home));
+        assert!(prerendered(&Route::Details { id: "bitcoin".to_string() }));
+        assert!(!prerendered(&Route::Portfolio));
+        assert!(!prerendered(&Route::Login));
+    }
+
+    #[test]
+    fn refuses_paths_outside_dist() {
+        let dist = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(static_file(dist, "/Cargo.toml").is_some());
+        assert!(static_file(dist, "/../Cargo.toml").is_none());
+        assert!(static_file(dist, "/src//main.rs").is_none());
+        assert!(static_file(dist, "/").is_none());
+    }
+}
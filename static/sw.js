@@ -0,0 +1,113 @@
+// static/sw.js
+//
+// Service worker of the installable build. Precaches the app shell so the
+// tracker opens without a connection, and keeps the last response of every
+// market data request to answer with when the network is gone.
+
+// Bump to drop the caches of older builds
+const VERSION = 'v4';
+const SHELL_CACHE = `crypto-tracker-shell-${VERSION}`;
+const DATA_CACHE = `crypto-tracker-data-${VERSION}`;
+const SHELL_FILES = ['manifest.webmanifest', 'icons/icon-192.png', 'icons/icon-512.png'];
+// Trunk's fingerprinted build output; a new build means new names
+const BUILD_ASSET = /\.(?:js|wasm|css)$/;
+
+// Market endpoints worth replaying offline. Portfolio and auth requests are
+// per user and always go to the network.
+const MARKET_DATA = /(\/exchanges\/[^/]+)?\/cryptocurrencies(\/[^/]+(\/history|\/ticker)?)?\/?$/;
+// Order books, trades and wallet balances go stale quickly, so they are
+// never stored
+const LIVE_DATA = /\/cryptocurrencies\/[^/]+\/(book(\/updates)?|trades)\/?$|\/rpc\//;
+
+self.addEventListener('install', (event) => {
+    event.waitUntil((async () => {
+        const cache = await caches.open(SHELL_CACHE);
+        const page = await fetch('./', { cache: 'no-cache' });
+        const html = await page.clone().text();
+        await cache.put('./', page);
+
+        // Trunk fingerprints the wasm, js and css file names, so take them from the page
+        const assets = [...html.matchAll(/["']([^"']+\.(?:js|wasm|css))["']/g)]
+            .map((match) => match[1])
+            .filter((url) => !url.endsWith('sw.js'));
+        await cache.addAll([...new Set([...SHELL_FILES, ...assets])]);
+        await self.skipWaiting();
+    })());
+});
+
+self.addEventListener('activate', (event) => {
+    event.waitUntil((async () => {
+        const keep = [SHELL_CACHE, DATA_CACHE];
+        const names = await caches.keys();
+        await Promise.all(names.filter((name) => !keep.includes(name)).map((name) => caches.delete(name)));
+        await self.clients.claim();
+    })());
+});
+
+self.addEventListener('fetch', (event) => {
+    const request = event.request;
+    if (request.method !== 'GET') {
+        return;
+    }
+    const url = new URL(request.url);
+
+    if (LIVE_DATA.test(url.pathname)) {
+        return;
+    }
+    if (request.mode === 'navigate') {
+        // Pages from `ssr-server` differ per route, so each is kept on its
+        // own; offline, any page will do as the client re-renders it for the URL
+        event.respondWith(networkFirst(request, SHELL_CACHE, './'));
+    } else if (url.origin === self.location.origin && url.pathname.endsWith('/config.json')) {
+        // Deployment settings can change without a new build
+        event.respondWith(networkFirst(request, SHELL_CACHE));
+    } else if (MARKET_DATA.test(url.pathname)) {
+        event.respondWith(networkFirst(request, DATA_CACHE));
+    } else if (isShellAsset(url)) {
+        event.respondWith(cacheFirst(request));
+    }
+    // Anything else, such as portfolio and auth requests under a same-origin
+    // `api_base_url`, goes to the network untouched
+});
+
+// Files precached on install; they never change under the same URL
+function isShellAsset(url) {
+    if (url.origin !== self.location.origin || url.pathname.endsWith('sw.js')) {
+        return false;
+    }
+    return BUILD_ASSET.test(url.pathname)
+        || SHELL_FILES.some((file) => new URL(file, self.registration.scope).pathname === url.pathname);
+}
+
+// Fresh data when reachable, the last stored copy (or `fallbackKey`'s)
+// otherwise. Error statuses from a reachable server are passed through
+// rather than masked.
+async function networkFirst(request, cacheName, fallbackKey) {
+    const cache = await caches.open(cacheName);
+    try {
+        const response = await fetch(request);
+        if (response.ok) {
+            await cache.put(request, response.clone());
+        }
+        return response;
+    } catch (error) {
+        const cached = await cache.match(request) || (fallbackKey && await cache.match(fallbackKey));
+        if (cached) {
+            return cached;
+        }
+        throw error;
+    }
+}
+
+async function cacheFirst(request) {
+    const cached = await caches.match(request);
+    if (cached) {
+        return cached;
+    }
+    const response = await fetch(request);
+    if (response.ok) {
+        const cache = await caches.open(SHELL_CACHE);
+        await cache.put(request, response.clone());
+    }
+    return response;
+}
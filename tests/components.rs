@@ -0,0 +1,431 @@
+// tests/components.rs
+//
+// Browser tests for the shared components. Run with
+// `wasm-pack test --headless --firefox` (or `--chrome`).
+#![cfg(target_arch = "wasm32")]
+
+mod support;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use wasm_bindgen_test::*;
+use yew::prelude::*;
+
+use support::{bitcoin, ethereum, holding, mount, settle};
+use yew_crypto_tracker::components::asset_search::AssetSearch;
+use yew_crypto_tracker::components::crypto_card::CryptoCard;
+use yew_crypto_tracker::components::crypto_list::CryptoList;
+use yew_crypto_tracker::components::error::Error;
+use yew_crypto_tracker::components::install_prompt::InstallButton;
+use yew_crypto_tracker::components::loading::Loading;
+use yew_crypto_tracker::components::exchange_prices::ExchangePrices;
+use yew_crypto_tracker::components::market_depth::MarketDepth;
+use yew_crypto_tracker::components::offline_banner::OfflineBanner;
+use yew_crypto_tracker::components::order_book::{OrderBookLadder, LADDER_LEVELS};
+use yew_crypto_tracker::components::portfolio_item::PortfolioItem;
+use yew_crypto_tracker::components::price_chart::PriceChart;
+use yew_crypto_tracker::components::recently_viewed::RecentlyViewedStrip;
+use yew_crypto_tracker::components::wallets::WalletManager;
+use yew_crypto_tracker::models::crypto::PortfolioItem as PortfolioItemModel;
+use yew_crypto_tracker::models::market::{BookLevel, OrderBook};
+use yew_crypto_tracker::store::Store;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// Collects every value emitted through the returned callback
+fn recorder<T: 'static>() -> (Rc<RefCell<Vec<T>>>, Callback<T>) {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let callback = {
+        let calls = calls.clone();
+        Callback::from(move |value| calls.borrow_mut().push(value))
+    };
+    (calls, callback)
+}
+
+#[wasm_bindgen_test]
+async fn loading_shows_spinner_text() {
+    let app = mount(Store::new(), html! { <Loading /> }).await;
+
+    assert!(app.query(".spinner").is_some());
+    assert_eq!(app.text_of(".loading-spinner p"), "Loading...");
+}
+
+#[wasm_bindgen_test]
+async fn error_renders_message_and_retries() {
+    let (retries, on_retry) = recorder::<()>();
+    let app = mount(
+        Store::new(),
+        html! { <Error message="Network down" on_retry={Some(on_retry)} /> },
+    )
+    .await;
+
+    assert_eq!(app.text_of(".error-message"), "Network down");
+    app.click("Retry").await;
+    assert_eq!(retries.borrow().len(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn error_without_retry_has_no_button() {
+    let app = mount(Store::new(), html! { <Error message="Gone" on_retry={None::<Callback<()>>} /> }).await;
+
+    assert_eq!(app.count("button"), 0);
+}
+
+#[wasm_bindgen_test]
+async fn crypto_list_renders_rows_and_navigates() {
+    let app = mount(
+        Store::new(),
+        html! { <CryptoList cryptocurrencies={vec![bitcoin(), ethereum()]} /> },
+    )
+    .await;
+
+    assert_eq!(app.count(".crypto-item"), 2);
+    assert!(app.text().contains("Bitcoin"));
+    assert!(app.text().contains("$50,000.00"));
+    assert!(app.text().contains("+2.50%"));
+    assert!(app.text().contains("-1.25%"));
+
+    app.click_element(".crypto-item:nth-child(2)").await;
+    assert_eq!(app.path(), "/details/ethereum");
+}
+
+#[wasm_bindgen_test]
+async fn crypto_card_adds_entered_amount() {
+    let (added, on_add) = recorder::<PortfolioItemModel>();
+    let app = mount(
+        Store::new(),
+        html! {
+            <CryptoCard
+                cryptocurrency={bitcoin()}
+                in_portfolio={false}
+                amount={None::<Decimal>}
+                on_add_to_portfolio={on_add}
+                on_update_portfolio={Callback::noop()}
+                on_remove_from_portfolio={Callback::noop()}
+            />
+        },
+    )
+    .await;
+
+    assert!(app.text().contains("Market Cap: $1.25T"));
+    app.click("Add to Portfolio").await;
+    app.type_into(".amount-field input", "0.25").await;
+    assert!(app.text_of(".amount-hint").contains("$12,500.00"));
+
+    app.click("Add").await;
+    assert_eq!(*added.borrow(), vec![holding("bitcoin", dec!(0.25))]);
+}
+
+#[wasm_bindgen_test]
+async fn crypto_card_rejects_invalid_amount() {
+    let (added, on_add) = recorder::<PortfolioItemModel>();
+    let app = mount(
+        Store::new(),
+        html! {
+            <CryptoCard
+                cryptocurrency={bitcoin()}
+                in_portfolio={false}
+                amount={None::<Decimal>}
+                on_add_to_portfolio={on_add}
+                on_update_portfolio={Callback::noop()}
+                on_remove_from_portfolio={Callback::noop()}
+            />
+        },
+    )
+    .await;
+
+    app.click("Add to Portfolio").await;
+    app.type_into(".amount-field input", "-3").await;
+    assert_eq!(app.text_of(".amount-error"), "The amount cannot be negative.");
+    assert!(app.button("Add").has_attribute("disabled"));
+    assert!(added.borrow().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn crypto_card_converts_fiat_entry() {
+    let (added, on_add) = recorder::<PortfolioItemModel>();
+    let app = mount(
+        Store::new(),
+        html! {
+            <CryptoCard
+                cryptocurrency={bitcoin()}
+                in_portfolio={false}
+                amount={None::<Decimal>}
+                on_add_to_portfolio={on_add}
+                on_update_portfolio={Callback::noop()}
+                on_remove_from_portfolio={Callback::noop()}
+            />
+        },
+    )
+    .await;
+
+    app.click("Add to Portfolio").await;
+    app.click("USD").await;
+    app.type_into(".amount-field input", "1000").await;
+    app.click("Add").await;
+    assert_eq!(*added.borrow(), vec![holding("bitcoin", dec!(0.02))]);
+}
+
+#[wasm_bindgen_test]
+async fn crypto_card_updates_and_removes_holding() {
+    let (updated, on_update) = recorder::<PortfolioItemModel>();
+    let (removed, on_remove) = recorder::<String>();
+    let app = mount(
+        Store::new(),
+        html! {
+            <CryptoCard
+                cryptocurrency={bitcoin()}
+                in_portfolio={true}
+                amount={Some(dec!(0.5))}
+                on_add_to_portfolio={Callback::noop()}
+                on_update_portfolio={on_update}
+                on_remove_from_portfolio={on_remove}
+            />
+        },
+    )
+    .await;
+
+    assert!(app.text().contains("Your holdings: 0.5 BTC"));
+    assert!(app.text().contains("Value: $25,000.00"));
+
+    app.type_into(".amount-field input", "0.75").await;
+    app.click("Update").await;
+    assert_eq!(*updated.borrow(), vec![holding("bitcoin", dec!(0.75))]);
+
+    app.click("Remove").await;
+    assert_eq!(*removed.borrow(), vec!["bitcoin".to_string()]);
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_item_shows_value_and_emits_actions() {
+    let (updated, on_update) = recorder::<PortfolioItemModel>();
+    let (removed, on_remove) = recorder::<String>();
+    let app = mount(
+        Store::new(),
+        html! {
+            <PortfolioItem
+                item={holding("ethereum", dec!(2))}
+                cryptocurrency={Some(ethereum())}
+                on_update={on_update}
+                on_remove={on_remove}
+            />
+        },
+    )
+    .await;
+
+    assert_eq!(app.text_of(".amount"), "2");
+    assert_eq!(app.text_of(".value"), "$4,000.00");
+
+    app.type_into(".amount-field input", "3").await;
+    app.click("Update").await;
+    assert_eq!(*updated.borrow(), vec![holding("ethereum", dec!(3))]);
+
+    app.click("Remove").await;
+    assert_eq!(*removed.borrow(), vec!["ethereum".to_string()]);
+
+    app.click_element(".item-info").await;
+    assert_eq!(app.path(), "/details/ethereum");
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_item_without_price_shows_placeholder() {
+    let app = mount(
+        Store::new(),
+        html! {
+            <PortfolioItem
+                item={holding("unlisted", dec!(1))}
+                cryptocurrency={None}
+                on_update={Callback::noop()}
+                on_remove={Callback::noop()}
+            />
+        },
+    )
+    .await;
+
+    assert!(app.query(".portfolio-item.loading").is_some());
+    assert!(app.text().contains("Loading..."));
+}
+
+fn fire(name: &str) {
+    let event = web_sys::Event::new(name).unwrap();
+    gloo::utils::window().dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn offline_banner_follows_connectivity_events() {
+    let app = mount(Store::new(), html! { <OfflineBanner /> }).await;
+    assert!(app.query(".offline-banner").is_none());
+
+    fire("offline");
+    settle().await;
+    assert!(app.text_of(".offline-banner").contains("offline"));
+
+    fire("online");
+    settle().await;
+    assert!(app.query(".offline-banner").is_none());
+}
+
+#[wasm_bindgen_test]
+async fn install_button_appears_once_installable() {
+    let app = mount(Store::new(), html! { <InstallButton /> }).await;
+    assert_eq!(app.count(".install-button"), 0);
+
+    fire("beforeinstallprompt");
+    settle().await;
+    assert_eq!(app.text_of(".install-button"), "Install app");
+
+    fire("appinstalled");
+    settle().await;
+    assert_eq!(app.count(".install-button"), 0);
+}
+
+fn recently_viewed_store() -> Store {
+    let mut store = Store::new();
+    store.recently_viewed.visit(&ethereum());
+    store.recently_viewed.visit(&bitcoin());
+    store
+}
+
+async fn focus_search(app: &support::Mounted) {
+    let event = web_sys::Event::new("focus").unwrap();
+    app.get(".asset-search input").dispatch_event(&event).unwrap();
+    settle().await;
+}
+
+#[wasm_bindgen_test]
+async fn recently_viewed_strip_links_and_clears() {
+    let app = mount(Store::new(), html! { <RecentlyViewedStrip /> }).await;
+    assert!(app.query(".recently-viewed").is_none());
+    drop(app);
+
+    let app = mount(recently_viewed_store(), html! { <RecentlyViewedStrip /> }).await;
+    assert_eq!(app.count(".recent-asset"), 2);
+    assert!(app.text_of(".recent-asset").contains("BTC"));
+
+    app.click_element(".recent-asset:nth-child(2)").await;
+    assert_eq!(app.path(), "/details/ethereum");
+
+    app.click("Clear history").await;
+    assert!(app.query(".recently-viewed").is_none());
+}
+
+#[wasm_bindgen_test]
+async fn search_offers_recent_assets_then_matches() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        ..recently_viewed_store()
+    };
+    let app = mount(store, html! { <AssetSearch /> }).await;
+    assert!(app.query(".search-dropdown").is_none());
+
+    focus_search(&app).await;
+    assert!(app.text_of(".search-recent").contains("Recently viewed"));
+    assert_eq!(app.count(".search-result"), 2);
+
+    app.type_into(".asset-search input", "eth").await;
+    assert_eq!(app.count(".search-result"), 1);
+    app.click_element(".search-result").await;
+    assert_eq!(app.path(), "/details/ethereum");
+    assert!(app.query(".search-dropdown").is_none());
+
+    app.type_into(".asset-search input", "doge").await;
+    assert!(app.text_of(".search-dropdown").contains("No matching assets"));
+}
+
+#[wasm_bindgen_test]
+async fn price_chart_draws_history_and_toggles_indicator_panes() {
+    <gloo::storage::LocalStorage as gloo::storage::Storage>::clear();
+    let app = mount(Store::new(), html! { <PriceChart id="bitcoin" /> }).await;
+    settle().await;
+
+    assert!(app.query(".chart-price .chart-close").is_some());
+    assert!(app.query(".chart-price .chart-sma").is_some());
+    assert_eq!(app.count(".chart-pane"), 0);
+
+    app.click_element(".chart-toggle-rsi").await;
+    app.click_element(".chart-toggle-macd").await;
+    assert!(app.query(".chart-pane-rsi .chart-rsi").is_some());
+    assert!(app.query(".chart-pane-macd .chart-macd-signal").is_some());
+    assert_eq!(app.count(".chart-parameter"), 5);
+
+    app.click_element(".chart-toggle-sma").await;
+    assert!(app.query(".chart-sma").is_none());
+
+    app.click("30 days").await;
+    settle().await;
+    assert!(app.query(".chart-price .chart-close").is_some());
+}
+
+#[wasm_bindgen_test]
+async fn order_book_ladder_shows_spread_and_mid_price() {
+    let level = |price: i64, amount: i64| BookLevel {
+        price: price.into(),
+        amount: amount.into(),
+    };
+    let book = OrderBook {
+        sequence: 1,
+        bids: (0..15).map(|index| level(99 - index, 1)).collect(),
+        asks: vec![level(101, 2), level(102, 1)],
+    };
+    let app = mount(Store::new(), html! { <OrderBookLadder book={book} /> }).await;
+
+    assert_eq!(app.count(".book-bid") as usize, LADDER_LEVELS);
+    assert_eq!(app.count(".book-ask"), 2);
+    // Asks are listed worst first so the best prices meet at the spread
+    assert!(app.text_of(".book-ask .book-price").contains("102"));
+    let spread = app.text_of(".book-spread");
+    assert!(spread.contains("Spread"));
+    assert!(spread.contains("100"));
+}
+
+#[wasm_bindgen_test]
+async fn market_depth_loads_the_sample_book_and_trades() {
+    let app = mount(Store::new(), html! { <MarketDepth id="bitcoin" /> }).await;
+    settle().await;
+
+    assert!(app.query(".depth-chart .depth-bids").is_some());
+    assert_eq!(app.count(".book-bid") as usize, LADDER_LEVELS);
+    assert!(app.count(".recent-trades .trade") > 0);
+}
+
+#[wasm_bindgen_test]
+async fn exchange_prices_aggregate_every_sample_exchange() {
+    let app = mount(Store::new(), html! { <ExchangePrices id="bitcoin" /> }).await;
+    settle().await;
+
+    assert_eq!(app.count(".exchange-row"), 4);
+    assert_eq!(app.count(".exchange-failed"), 0);
+    assert!(app.text_of(".exchange-aggregate").contains('$'));
+    assert_eq!(app.text_of(".exchange-sources"), "Based on 4 of 4 exchanges");
+}
+
+#[wasm_bindgen_test]
+async fn wallet_manager_validates_adds_and_removes_addresses() {
+    let app = mount(Store::new(), html! { <WalletManager /> }).await;
+    assert!(app.query(".wallets-empty").is_some());
+
+    // Bitcoin is picked by default, and an EVM address isn't one
+    app.type_into(".wallet-address-input", "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe").await;
+    app.click("Watch wallet").await;
+    assert_eq!(app.text_of(".wallet-error"), "This isn't a valid Bitcoin address.");
+
+    app.change(".wallet-chain-select", "arbitrum").await;
+    app.type_into(".wallet-label-input", "Rollup").await;
+    app.click("Watch wallet").await;
+    assert!(app.query(".wallet-error").is_none());
+    assert_eq!(app.count(".wallet-row"), 1);
+    assert_eq!(app.text_of(".wallet-name"), "Rollup");
+    assert_eq!(app.text_of(".wallet-chain"), "Arbitrum");
+    assert_eq!(app.text_of(".wallet-address"), "0xde0b29…7bae");
+
+    // Addresses are compared without case
+    app.type_into(".wallet-address-input", "0xDE0B295669A9FD93D5F28D9EC85E40F4CB697BAE").await;
+    app.click("Watch wallet").await;
+    assert_eq!(app.text_of(".wallet-error"), "This wallet is already being watched.");
+
+    app.click_element(".wallet-remove").await;
+    assert_eq!(app.count(".wallet-row"), 0);
+}
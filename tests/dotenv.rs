@@ -0,0 +1,111 @@
+// tests/dotenv.rs
+//
+// Tests for the `.env` parser used by build.rs.
+#[path = "../build/dotenv.rs"]
+mod dotenv;
+
+use dotenv::{parse, Parsed};
+
+fn parse_alone(source: &str) -> Parsed {
+    parse(source, |_| None)
+}
+
+fn values(parsed: &Parsed) -> Vec<(&str, &str)> {
+    parsed
+        .entries
+        .iter()
+        .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+        .collect()
+}
+
+#[test]
+fn skips_comments_and_blank_lines() {
+    let parsed = parse_alone("# settings\n\nAPI_BASE_URL=http://localhost:8787 # mock server\nAPP_TAG=a#b\n");
+
+    assert_eq!(values(&parsed), [("API_BASE_URL", "http://localhost:8787"), ("APP_TAG", "a#b")]);
+    assert!(parsed.warnings.is_empty());
+    assert_eq!(parsed.entries[0].line, 3);
+}
+
+#[test]
+fn accepts_export_prefix_and_spacing() {
+    let parsed = parse_alone("export APP_NAME = Tracker\n  export\tAPP_MODE=demo\nexporter=x\n");
+
+    assert_eq!(values(&parsed), [("APP_NAME", "Tracker"), ("APP_MODE", "demo"), ("exporter", "x")]);
+}
+
+#[test]
+fn single_quotes_are_literal() {
+    let parsed = parse_alone(r#"APP_TEXT='keep $HOME and \n as # is'"#);
+
+    assert_eq!(values(&parsed), [("APP_TEXT", r"keep $HOME and \n as # is")]);
+}
+
+#[test]
+fn double_quotes_translate_escapes() {
+    let parsed = parse_alone(r#"APP_TEXT="say \"hi\"\tthen\nbreak \\ \$5" # note"#);
+
+    assert_eq!(values(&parsed), [("APP_TEXT", "say \"hi\"\tthen\nbreak \\ $5")]);
+}
+
+#[test]
+fn quoted_values_span_lines() {
+    let parsed = parse_alone("APP_TEXT=\"first\nsecond\"\nAPP_NEXT=3\n");
+
+    assert_eq!(values(&parsed), [("APP_TEXT", "first\nsecond"), ("APP_NEXT", "3")]);
+    assert_eq!(parsed.entries[1].line, 3);
+}
+
+#[test]
+fn interpolates_earlier_keys_and_the_environment() {
+    let parsed = parse(
+        "APP_HOST=localhost\nAPI_BASE_URL=http://${APP_HOST}:$APP_PORT/api\nAPP_MODE=${APP_MISSING:-demo}\nAPP_RAW='$APP_HOST'\n",
+        |name| (name == "APP_PORT").then(|| "8787".to_string()),
+    );
+
+    assert_eq!(
+        values(&parsed),
+        [
+            ("APP_HOST", "localhost"),
+            ("API_BASE_URL", "http://localhost:8787/api"),
+            ("APP_MODE", "demo"),
+            ("APP_RAW", "$APP_HOST"),
+        ]
+    );
+    assert!(parsed.warnings.is_empty());
+}
+
+#[test]
+fn warns_about_unset_references() {
+    let parsed = parse_alone("APP_URL=http://$APP_HOST/\nAPP_PRICE=5$\n");
+
+    assert_eq!(values(&parsed), [("APP_URL", "http:///"), ("APP_PRICE", "5$")]);
+    assert_eq!(parsed.warnings, ["line 1: `APP_HOST` is not set"]);
+}
+
+#[test]
+fn reports_malformed_lines_and_keeps_the_rest() {
+    let parsed = parse_alone(
+        "JUST TEXT\n=value\n1ST=x\nAPP-NAME=x\nAPP_OK=yes\nAPP_BAD=\"open\nAPP_LOST=1\n",
+    );
+
+    assert_eq!(values(&parsed), [("APP_OK", "yes")]);
+    assert_eq!(
+        parsed.warnings,
+        [
+            "line 1: expected KEY=VALUE",
+            "line 2: missing key before `=`",
+            "line 3: key `1ST` must start with a letter or `_`",
+            "line 4: key `APP-NAME` may only contain letters, digits and `_`",
+            "line 6: unterminated \" quote",
+        ]
+    );
+}
+
+#[test]
+fn rejects_text_after_a_closing_quote() {
+    let parsed = parse_alone("APP_NAME=\"Crypto\" Tracker\n");
+
+    assert!(parsed.entries.is_empty());
+    assert_eq!(parsed.warnings, ["line 1: unexpected `Tracker` after the closing quote"]);
+}
@@ -0,0 +1,325 @@
+// tests/pages.rs
+//
+// Browser tests for pages mounted against a seeded store. Run with
+// `wasm-pack test --headless --firefox` (or `--chrome`).
+#![cfg(target_arch = "wasm32")]
+
+mod support;
+
+use rust_decimal_macros::dec;
+use wasm_bindgen_test::*;
+use yew::prelude::*;
+
+use support::{bitcoin, ethereum, holding, mount, mount_with_session, session, settle};
+use yew_crypto_tracker::components::require_auth::RequireAuth;
+use yew_crypto_tracker::models::wallet::{Chain, WatchedWallet};
+use yew_crypto_tracker::pages::{Arbitrage, Details, Login, NotFound, Portfolio, Simulate};
+use yew_crypto_tracker::services::chain::sample_balance;
+use yew_crypto_tracker::store::Store;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn portfolio_page_shows_empty_state() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin()],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+
+    assert!(app.query(".empty-portfolio").is_some());
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_page_totals_seeded_holdings() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1)), holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+
+    assert_eq!(app.count(".portfolio-item"), 2);
+    assert_eq!(app.text_of(".total-value"), "$9,000.00");
+    assert!(app.text().contains("2 holdings"));
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_page_merges_watched_wallet_balances() {
+    let cold = WatchedWallet {
+        chain: Chain::Bitcoin,
+        address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+        label: Some("Cold storage".to_string()),
+    };
+    let rollup = WatchedWallet {
+        chain: Chain::Base,
+        address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_string(),
+        label: None,
+    };
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1))],
+        wallets: vec![cold.clone(), rollup.clone()],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+    settle().await;
+
+    // Bitcoin adds the wallet to the typed-in amount, Ethereum is wallet-only
+    assert_eq!(app.count(".portfolio-item"), 2);
+    assert_eq!(app.text_of(".portfolio-item .amount"), (dec!(0.1) + sample_balance(&cold)).normalize().to_string());
+    let sources = app.text_of(".portfolio-item .holding-sources");
+    assert!(sources.starts_with("Manual 0.1"));
+    assert!(sources.contains("Cold storage (Bitcoin)"));
+    assert_eq!(app.text_of(".wallet-holding .amount"), sample_balance(&rollup).to_string());
+    assert!(app.text_of(".wallet-holding .holding-sources").contains("0xde0b29…7bae (Base)"));
+    assert_eq!(app.count(".wallet-row"), 2);
+    assert!(app.text().contains("2 holdings"));
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_page_suggests_trades_towards_target_allocation() {
+    <gloo::storage::LocalStorage as gloo::storage::Storage>::clear();
+    // $5,000 in bitcoin, $4,000 in ethereum
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1)), holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+
+    assert_eq!(app.count(".rebalance-row"), 2);
+    assert!(app.text_of(".rebalance-error").contains("0.00%"));
+
+    app.change(".rebalance-row:first-child .rebalance-target", "50").await;
+    assert!(app.text_of(".rebalance-error").contains("50.00%"));
+    app.change(".rebalance-row:last-child .rebalance-target", "50").await;
+    assert!(app.query(".rebalance-error").is_none());
+    assert_eq!(app.text_of(".rebalance-sell"), "Sell 0.01 BTC ($500.00)");
+    assert_eq!(app.text_of(".rebalance-buy"), "Buy 0.25 ETH ($500.00)");
+    assert_eq!(app.text_of(".rebalance-row:first-child .rebalance-after"), "50.00%");
+
+    // 5.56 points of drift are within a 10 point band
+    app.change(".rebalance label:first-child .rebalance-setting", "10").await;
+    assert_eq!(app.count(".rebalance-buy, .rebalance-sell"), 0);
+    assert!(app.query(".rebalance-balanced").is_some());
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_page_measures_risk_and_correlations() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1)), holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+    settle().await;
+
+    assert_eq!(app.count(".risk-row"), 2);
+    assert!(app.text_of(".risk-row:first-child .risk-volatility").ends_with('%'));
+    // Bitcoin is the benchmark, so moves one for one with it
+    assert_eq!(app.text_of(".risk-row:first-child .risk-beta"), "1.00");
+    assert!(app.query(".risk-portfolio .risk-var-historical").is_some());
+    assert!(app.text_of(".risk-note").starts_with("Over 89 daily returns"));
+    // Two by two, with each asset perfectly correlated with itself
+    assert_eq!(app.count(".heatmap-cell"), 4);
+    assert_eq!(app.text_of(".heatmap-cell"), "1.00");
+
+    app.change(".risk-window", "30").await;
+    settle().await;
+    assert!(app.text_of(".risk-note").starts_with("Over 29 daily returns"));
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_page_records_income_and_totals_it_per_period() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+    assert!(app.query(".income-empty").is_some());
+
+    app.click("Record income").await;
+    assert!(app.text_of(".income-error").contains("asset"));
+
+    app.change(".income-asset", "ethereum").await;
+    app.type_into(".income-amount", "40000000000000000000000000").await;
+    app.click("Record income").await;
+    assert!(app.text_of(".income-error").contains("too large"));
+
+    app.type_into(".income-amount", "0.01").await;
+    app.change(".income-date", "2024-01-31").await;
+    app.type_into(".income-value-input", "25").await;
+    app.click("Record income").await;
+    app.type_into(".income-amount", "0.01").await;
+    app.change(".income-date", "2024-03-01").await;
+    app.type_into(".income-value-input", "30").await;
+    app.click("Record income").await;
+
+    assert!(app.query(".income-error").is_none());
+    assert_eq!(app.count(".income-event"), 2);
+    assert_eq!(app.text_of(".income-total"), "$55.00");
+    assert_eq!(app.count(".income-period-row"), 2);
+    assert_eq!(app.text_of(".income-period-label"), "03/2024");
+    assert_eq!(app.count(".income-apy-row"), 1);
+    // Rewards grow the holding without changing the typed-in amount
+    assert_eq!(app.text_of(".portfolio-item .amount"), "2.02");
+    assert!(app.text_of(".portfolio-item .holding-sources").contains("Income 0.02"));
+
+    let export = app.query(".income-export").and_then(|link| link.get_attribute("href")).unwrap();
+    assert!(export.ends_with("year,kind,value%0A2024,staking,55.00%0A2024,total,55.00%0A"));
+
+    app.change(".income-period", "year").await;
+    assert_eq!(app.count(".income-period-row"), 1);
+    assert_eq!(app.text_of(".income-period-label"), "2024");
+    assert_eq!(app.text_of(".income-period-total"), "$55.00");
+
+    app.click_element(".income-remove").await;
+    assert_eq!(app.count(".income-event"), 1);
+    assert_eq!(app.text_of(".income-total"), "$25.00");
+}
+
+#[wasm_bindgen_test]
+async fn removing_a_holding_offers_undo() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1)), holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(
+        store,
+        html! {
+            <>
+                <Portfolio />
+                <yew_crypto_tracker::components::undo_toast::UndoToast />
+            </>
+        },
+    )
+    .await;
+
+    app.click("Remove").await;
+    assert_eq!(app.count(".portfolio-item"), 1);
+    assert!(app.text_of(".undo-toast").contains("Removed Bitcoin"));
+
+    app.click("Undo").await;
+    assert_eq!(app.count(".portfolio-item"), 2);
+    assert!(app.query(".undo-toast").is_none());
+}
+
+#[wasm_bindgen_test]
+async fn portfolio_header_reports_saved_edits() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        portfolio: vec![holding("bitcoin", dec!(0.1)), holding("ethereum", dec!(2))],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Portfolio /> }).await;
+
+    // A seeded portfolio counts as synced and has nothing to report yet
+    assert!(app.query(".sync-status").is_none());
+
+    app.click("Remove").await;
+    assert_eq!(app.text_of(".sync-status"), "All changes saved");
+}
+
+#[wasm_bindgen_test]
+async fn details_tabs_switch_between_chart_book_and_exchanges() {
+    let app = mount(Store::new(), html! { <Details id="bitcoin" /> }).await;
+    settle().await;
+
+    assert!(app.query(".price-chart").is_some());
+    assert!(app.query(".market-depth").is_none());
+
+    app.click("Order book").await;
+    settle().await;
+    assert!(app.query(".price-chart").is_none());
+    assert!(app.query(".market-depth").is_some());
+
+    app.click("Exchanges").await;
+    settle().await;
+    assert!(app.query(".details-tab-exchanges.details-tab-active").is_some());
+    assert_eq!(app.count(".exchange-row"), 4);
+}
+
+#[wasm_bindgen_test]
+async fn arbitrage_page_lists_sorts_and_filters_opportunities() {
+    <gloo::storage::LocalStorage as gloo::storage::Storage>::clear();
+    let app = mount(Store::new(), html! { <Arbitrage /> }).await;
+    settle().await;
+
+    // The sample exchanges cross on every asset but Bitcoin
+    assert_eq!(app.count(".arbitrage-row"), 4);
+    assert!(app.text_of(".arbitrage-row").contains("Ethereum"));
+
+    app.click("Asset").await;
+    assert!(app.text_of(".arbitrage-row").contains("Cardano"));
+
+    app.change(".arbitrage-page label:last-child .arbitrage-setting", "0.2").await;
+    assert_eq!(app.count(".arbitrage-row"), 2);
+}
+
+#[wasm_bindgen_test]
+async fn simulate_page_compares_dollar_cost_averaging_with_a_lump_sum() {
+    let store = Store {
+        cryptocurrencies: vec![bitcoin(), ethereum()],
+        ..Store::new()
+    };
+    let app = mount(store, html! { <Simulate /> }).await;
+    settle().await;
+
+    // $100 weekly over the last 90 days
+    assert!(app.text_of(".simulate-summary").starts_with("13 buys"));
+    assert_eq!(app.text_of(".simulate-dca .simulate-invested"), "$1,300.00");
+    assert_eq!(app.text_of(".simulate-lump-sum .simulate-invested"), "$1,300.00");
+    assert_eq!(app.count(".simulate-asset-row"), 1);
+
+    app.change(".simulate-schedule", "daily").await;
+    settle().await;
+    assert_eq!(app.text_of(".simulate-dca .simulate-invested"), "$9,100.00");
+
+    app.click_element(".simulate-asset[value='ethereum']").await;
+    settle().await;
+    assert_eq!(app.count(".simulate-asset-row"), 2);
+
+    app.change(".simulate-amount", "0").await;
+    assert!(app.query(".simulate-error").is_some());
+}
+
+#[wasm_bindgen_test]
+async fn not_found_page_links_home() {
+    let app = mount(Store::new(), html! { <NotFound /> }).await;
+
+    assert!(app.text().contains("404 - Page Not Found"));
+    app.click("Go to Home").await;
+    assert_eq!(app.path(), "/");
+}
+
+#[wasm_bindgen_test]
+async fn protected_page_redirects_to_login() {
+    let app = mount(Store::new(), html! { <RequireAuth><Portfolio /></RequireAuth> }).await;
+
+    assert!(app.query(".portfolio-page").is_none());
+    assert_eq!(app.path(), "/login");
+}
+
+#[wasm_bindgen_test]
+async fn protected_page_renders_when_signed_in() {
+    let app = mount_with_session(
+        Store::new(),
+        Some(session()),
+        html! { <RequireAuth><Portfolio /></RequireAuth> },
+    )
+    .await;
+
+    assert!(app.query(".portfolio-page").is_some());
+}
+
+#[wasm_bindgen_test]
+async fn login_requires_both_fields() {
+    let app = mount(Store::new(), html! { <Login /> }).await;
+
+    app.type_into("input[name=username]", "demo").await;
+    app.click_element("button[type=submit]").await;
+    assert_eq!(app.text_of(".login-error"), "Enter your username and password.");
+}
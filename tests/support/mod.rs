@@ -0,0 +1,210 @@
+// tests/support/mod.rs
+//
+// Helpers for mounting components into the test page and driving them.
+// Each test crate uses a different subset of these.
+#![allow(dead_code)]
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, EventInit, HtmlElement, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::Router;
+
+use yew_crypto_tracker::auth::AuthProvider;
+use yew_crypto_tracker::config::{Config, ConfigProvider};
+use yew_crypto_tracker::i18n::{I18nProvider, Locale};
+use yew_crypto_tracker::models::auth::{Session, User};
+use yew_crypto_tracker::models::crypto::{Cryptocurrency, PortfolioItem};
+use yew_crypto_tracker::store::{Store, StoreProvider};
+
+#[derive(Properties, PartialEq)]
+pub struct FixtureProps {
+    pub store: Store,
+    pub session: Option<Session>,
+    pub history: AnyHistory,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+// Providers every component expects, seeded with a known store, session,
+// the compiled default config and English strings
+#[function_component(Fixture)]
+pub fn fixture(props: &FixtureProps) -> Html {
+    html! {
+        <ConfigProvider config={Some(Config::default())}>
+            <I18nProvider locale={Some(Locale::En)}>
+                <AuthProvider session={props.session.clone()}>
+                    <StoreProvider initial={Some(props.store.clone())}>
+                        <Router history={props.history.clone()}>
+                            {props.children.clone()}
+                        </Router>
+                    </StoreProvider>
+                </AuthProvider>
+            </I18nProvider>
+        </ConfigProvider>
+    }
+}
+
+pub struct Mounted {
+    pub root: Element,
+    pub history: AnyHistory,
+    app: Option<yew::AppHandle<Fixture>>,
+}
+
+impl Mounted {
+    pub fn query(&self, selector: &str) -> Option<Element> {
+        self.root.query_selector(selector).unwrap()
+    }
+
+    pub fn get(&self, selector: &str) -> Element {
+        self.query(selector)
+            .unwrap_or_else(|| panic!("no element matches `{}`", selector))
+    }
+
+    pub fn count(&self, selector: &str) -> u32 {
+        self.root.query_selector_all(selector).unwrap().length()
+    }
+
+    pub fn text(&self) -> String {
+        self.root.text_content().unwrap_or_default()
+    }
+
+    pub fn text_of(&self, selector: &str) -> String {
+        self.get(selector).text_content().unwrap_or_default()
+    }
+
+    // First button whose label contains `label`
+    pub fn button(&self, label: &str) -> HtmlElement {
+        let buttons = self.root.query_selector_all("button").unwrap();
+        (0..buttons.length())
+            .filter_map(|index| buttons.item(index))
+            .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+            .find(|button| button.text_content().unwrap_or_default().contains(label))
+            .unwrap_or_else(|| panic!("no button labelled `{}`", label))
+    }
+
+    pub async fn click(&self, label: &str) {
+        self.button(label).click();
+        settle().await;
+    }
+
+    pub async fn click_element(&self, selector: &str) {
+        self.get(selector).dyn_into::<HtmlElement>().unwrap().click();
+        settle().await;
+    }
+
+    // Set an input's value and fire a bubbling `input` event
+    pub async fn type_into(&self, selector: &str, value: &str) {
+        let input: HtmlInputElement = self.get(selector).dyn_into().unwrap();
+        input.set_value(value);
+        let init = EventInit::new();
+        init.set_bubbles(true);
+        let event = Event::new_with_event_init_dict("input", &init).unwrap();
+        input.dispatch_event(&event).unwrap();
+        settle().await;
+    }
+
+    // Set an input's or select's value and fire a bubbling `change` event,
+    // as when the user commits an edit or picks an option
+    pub async fn change(&self, selector: &str, value: &str) {
+        let element = self.get(selector);
+        match element.dyn_ref::<HtmlSelectElement>() {
+            Some(select) => select.set_value(value),
+            None => element.dyn_ref::<HtmlInputElement>().unwrap().set_value(value),
+        }
+        let init = EventInit::new();
+        init.set_bubbles(true);
+        let event = Event::new_with_event_init_dict("change", &init).unwrap();
+        element.dispatch_event(&event).unwrap();
+        settle().await;
+    }
+
+    pub fn path(&self) -> String {
+        self.history.location().path().to_string()
+    }
+}
+
+impl Drop for Mounted {
+    fn drop(&mut self) {
+        if let Some(app) = self.app.take() {
+            app.destroy();
+        }
+        self.root.remove();
+    }
+}
+
+// Let Yew flush scheduled renders and effects
+pub async fn settle() {
+    yew::platform::time::sleep(Duration::from_millis(10)).await;
+}
+
+pub async fn mount(store: Store, children: Html) -> Mounted {
+    mount_with_session(store, None, children).await
+}
+
+pub async fn mount_with_session(store: Store, session: Option<Session>, children: Html) -> Mounted {
+    let document = gloo::utils::document();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let history = AnyHistory::from(MemoryHistory::new());
+    let props = FixtureProps {
+        store,
+        session,
+        history: history.clone(),
+        children,
+    };
+    let app = yew::Renderer::<Fixture>::with_root_and_props(root.clone(), props).render();
+    settle().await;
+
+    Mounted {
+        root,
+        history,
+        app: Some(app),
+    }
+}
+
+pub fn crypto(id: &str, name: &str, symbol: &str, price: Decimal) -> Cryptocurrency {
+    Cryptocurrency {
+        id: id.to_string(),
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        price,
+        market_cap: 1_250_000_000_000.0,
+        volume_24h: 45_000_000_000.0,
+        price_change_24h: 2.5,
+        decimals: 8,
+    }
+}
+
+pub fn bitcoin() -> Cryptocurrency {
+    crypto("bitcoin", "Bitcoin", "BTC", Decimal::from(50_000))
+}
+
+pub fn ethereum() -> Cryptocurrency {
+    Cryptocurrency {
+        price_change_24h: -1.25,
+        ..crypto("ethereum", "Ethereum", "ETH", Decimal::from(2_000))
+    }
+}
+
+pub fn holding(crypto_id: &str, amount: Decimal) -> PortfolioItem {
+    PortfolioItem {
+        crypto_id: crypto_id.to_string(),
+        amount,
+    }
+}
+
+pub fn session() -> Session {
+    Session {
+        access_token: "access".to_string(),
+        refresh_token: "refresh".to_string(),
+        expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+        user: User {
+            id: "demo".to_string(),
+            name: "Demo User".to_string(),
+        },
+    }
+}